@@ -0,0 +1,97 @@
+use super::runtime::Runtime;
+
+const PROJECT_LABEL: &str = "com.docker.compose.project";
+const SERVICE_LABEL: &str = "com.docker.compose.service";
+const WORKING_DIR_LABEL: &str = "com.docker.compose.project.working_dir";
+const CONFIG_FILES_LABEL: &str = "com.docker.compose.project.config_files";
+
+/// The Compose labels of a container that belongs to a project.
+#[derive(Clone)]
+pub struct ComposeInfo {
+    pub project: String,
+    pub service: String,
+    pub working_dir: String,
+    pub config_files: Vec<String>,
+}
+
+impl ComposeInfo {
+    /// `docker ps --format` fields that `from_fields` expects, in order.
+    pub fn format_fields() -> String {
+        [
+            PROJECT_LABEL,
+            SERVICE_LABEL,
+            WORKING_DIR_LABEL,
+            CONFIG_FILES_LABEL,
+        ]
+        .iter()
+        // Podman's `.Labels` is a map, Docker's and nerdctl's a comma-separated string.
+        .map(|label| match Runtime::current() {
+            Runtime::Docker | Runtime::Nerdctl => format!("{{{{.Label \"{}\"}}}}", label),
+            Runtime::Podman => format!("{{{{index .Labels \"{}\"}}}}", label),
+        })
+        .collect::<Vec<_>>()
+        .join("|")
+    }
+
+    /// Returns `None` for containers that were not created by Compose.
+    pub fn from_fields(fields: &[&str]) -> Option<Self> {
+        let project = fields.first()?.trim();
+        if project.is_empty() {
+            return None;
+        }
+        let field = |i: usize| fields.get(i).map(|f| f.trim()).unwrap_or_default();
+        Some(ComposeInfo {
+            project: project.to_string(),
+            service: field(1).to_string(),
+            working_dir: field(2).to_string(),
+            config_files: field(3)
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(|f| f.to_string())
+                .collect(),
+        })
+    }
+}
+
+/// Arguments for `docker compose` on an existing project. The project directory and
+/// files come from the container labels, so this works from any working directory.
+pub fn compose_args(info: &ComposeInfo, command: &[&str]) -> Vec<String> {
+    let mut args = vec![
+        "compose".to_string(),
+        "-p".to_string(),
+        info.project.clone(),
+    ];
+    if !info.working_dir.is_empty() {
+        args.push("--project-directory".to_string());
+        args.push(info.working_dir.clone());
+    }
+    for file in &info.config_files {
+        args.push("-f".to_string());
+        args.push(file.clone());
+    }
+    args.extend(command.iter().map(|arg| arg.to_string()));
+    args
+}
+
+/// Parses sizes as printed by the Docker CLI (`12.5MiB`, `1.2GB`, `512B`) into bytes.
+pub fn parse_size(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier = match unit.trim() {
+        "" | "B" => 1.0,
+        "kB" | "KB" => 1e3,
+        "KiB" => 1024.0,
+        "MB" => 1e6,
+        "MiB" => 1024.0 * 1024.0,
+        "GB" => 1e9,
+        "GiB" => 1024.0 * 1024.0 * 1024.0,
+        "TB" => 1e12,
+        "TiB" => 1024.0 * 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+    Some(number * multiplier)
+}
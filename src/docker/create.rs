@@ -0,0 +1,663 @@
+use super::files::host_path;
+use super::update::{parse_memory, MIN_MEMORY};
+use std::env;
+
+pub const RESTART_POLICIES: [&str; 4] = ["no", "on-failure", "always", "unless-stopped"];
+
+/// The settings of a new container, as entered in the create dialog. Each
+/// `*_args` method validates one setting and returns its `docker run` flags;
+/// empty settings are left to Docker's defaults.
+#[derive(Clone, Default)]
+pub struct RunOptions {
+    pub image: String,
+    pub name: String,
+    /// Port mappings separated by commas or spaces.
+    pub ports: String,
+    /// One `KEY=value` per line, so values may contain commas.
+    pub env: String,
+    /// One `SOURCE:TARGET[:OPTIONS]` per line.
+    pub volumes: String,
+    /// Empty for the default bridge network.
+    pub network: String,
+    pub restart_policy: String,
+    pub memory: String,
+    pub cpus: String,
+    /// One `KEY=value` per line.
+    pub labels: String,
+    /// Split like a shell would, so quoted arguments stay together.
+    pub command: String,
+}
+
+impl RunOptions {
+    pub fn image_args(&self) -> Result<Vec<String>, String> {
+        let image = self.image.trim();
+        if image.is_empty() {
+            return Err("An image is required".to_string());
+        }
+        if image.contains(char::is_whitespace) {
+            return Err(format!("Invalid image '{}'", image));
+        }
+        Ok(vec![image.to_string()])
+    }
+
+    pub fn name_args(&self) -> Result<Vec<String>, String> {
+        let name = self.name.trim();
+        if name.is_empty() {
+            return Ok(Vec::new());
+        }
+        if !is_valid_container_name(name) {
+            return Err(format!(
+                "Invalid name '{}': use letters, digits, '_', '.' and '-'",
+                name
+            ));
+        }
+        Ok(flag("--name", name))
+    }
+
+    pub fn port_args(&self) -> Result<Vec<String>, String> {
+        let mut args = Vec::new();
+        for spec in self.ports.split([',', ' ']).filter(|spec| !spec.is_empty()) {
+            check_port_mapping(spec)?;
+            args.extend(flag("-p", spec));
+        }
+        Ok(args)
+    }
+
+    pub fn env_args(&self) -> Result<Vec<String>, String> {
+        assignment_args(&self.env, "-e", "environment variable")
+    }
+
+    pub fn volume_args(&self) -> Result<Vec<String>, String> {
+        let mut args = Vec::new();
+        for spec in entries(&self.volumes) {
+            args.extend(flag("-v", &volume_spec(spec)?));
+        }
+        Ok(args)
+    }
+
+    pub fn network_args(&self) -> Vec<String> {
+        match self.network.as_str() {
+            "" => Vec::new(),
+            network => flag("--network", network),
+        }
+    }
+
+    pub fn restart_args(&self) -> Vec<String> {
+        match self.restart_policy.as_str() {
+            "" | "no" => Vec::new(),
+            policy => flag("--restart", policy),
+        }
+    }
+
+    pub fn memory_args(&self) -> Result<Vec<String>, String> {
+        let memory = self.memory.trim();
+        if memory.is_empty() {
+            return Ok(Vec::new());
+        }
+        match parse_memory(memory) {
+            Some(bytes) if bytes >= MIN_MEMORY => Ok(flag("--memory", memory)),
+            Some(_) => Err("Memory must be at least 6m".to_string()),
+            None => Err(format!("Invalid memory '{}', e.g. 512m or 2g", memory)),
+        }
+    }
+
+    pub fn cpu_args(&self) -> Result<Vec<String>, String> {
+        let cpus = self.cpus.trim();
+        if cpus.is_empty() {
+            return Ok(Vec::new());
+        }
+        match cpus.parse::<f64>() {
+            Ok(value) if value > 0.0 => Ok(flag("--cpus", cpus)),
+            _ => Err(format!("Invalid CPUs '{}', e.g. 0.5 or 2", cpus)),
+        }
+    }
+
+    pub fn label_args(&self) -> Result<Vec<String>, String> {
+        assignment_args(&self.labels, "--label", "label")
+    }
+
+    pub fn command_args(&self) -> Result<Vec<String>, String> {
+        split_command(&self.command)
+    }
+
+    /// Everything after `docker`, or the first invalid setting.
+    pub fn run_args(&self) -> Result<Vec<String>, String> {
+        let mut args = vec!["run".to_string(), "-d".to_string()];
+        args.extend(self.name_args()?);
+        args.extend(self.port_args()?);
+        args.extend(self.env_args()?);
+        args.extend(self.volume_args()?);
+        args.extend(self.network_args());
+        args.extend(self.restart_args());
+        args.extend(self.memory_args()?);
+        args.extend(self.cpu_args()?);
+        args.extend(self.label_args()?);
+        args.extend(self.image_args()?);
+        args.extend(self.command_args()?);
+        Ok(args)
+    }
+}
+
+fn flag(name: &str, value: &str) -> Vec<String> {
+    vec![name.to_string(), value.to_string()]
+}
+
+/// Non-blank lines that are not `#` comments.
+fn entries(text: &str) -> impl Iterator<Item = &str> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+/// `KEY=value` lines; a bare `KEY` is passed on as well.
+fn assignment_args(text: &str, flag_name: &str, what: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    for entry in entries(text) {
+        let key = entry.split('=').next().unwrap_or_default();
+        if key.is_empty() || key.contains(char::is_whitespace) {
+            return Err(format!("Invalid {} '{}', expected KEY=value", what, entry));
+        }
+        args.extend(flag(flag_name, entry));
+    }
+    Ok(args)
+}
+
+/// Docker's rule for container names: `[a-zA-Z0-9][a-zA-Z0-9_.-]+`.
+pub fn is_valid_container_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphanumeric())
+        && name.len() > 1
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+}
+
+/// Checks `[IP:][HOST_PORT]:CONTAINER_PORT[/PROTOCOL]`, where ports may be ranges.
+fn check_port_mapping(spec: &str) -> Result<(), String> {
+    let invalid = || {
+        format!(
+            "Invalid port mapping '{}', e.g. 8080:80, 127.0.0.1:53:53/udp or 80",
+            spec
+        )
+    };
+    let (ports, protocol) = match spec.split_once('/') {
+        Some((ports, protocol)) => (ports, Some(protocol)),
+        None => (spec, None),
+    };
+    if protocol.is_some_and(|protocol| !matches!(protocol, "tcp" | "udp" | "sctp")) {
+        return Err(invalid());
+    }
+    // An IPv6 address is bracketed, e.g. [::1]:8080:80.
+    let ports = match ports.strip_prefix('[') {
+        Some(rest) => rest.split_once("]:").ok_or_else(invalid)?.1,
+        None => ports,
+    };
+    let parts: Vec<&str> = ports.split(':').collect();
+    let (host, container) = match parts.as_slice() {
+        [container] => (None, *container),
+        [host, container] => (Some(*host), *container),
+        [ip, host, container] if !ip.is_empty() => (Some(*host), *container),
+        _ => return Err(invalid()),
+    };
+    let container_range = port_range(container).ok_or_else(invalid)?;
+    if let Some(host) = host.filter(|host| !host.is_empty()) {
+        let host_range = port_range(host).ok_or_else(invalid)?;
+        // A host range may also map onto a single container port.
+        if host_range.1 - host_range.0 != container_range.1 - container_range.0
+            && container_range.0 != container_range.1
+        {
+            return Err(format!("Port ranges in '{}' differ in size", spec));
+        }
+    }
+    Ok(())
+}
+
+/// `80` or `8000-8010`.
+fn port_range(value: &str) -> Option<(u16, u16)> {
+    let port = |value: &str| value.parse::<u16>().ok().filter(|port| *port > 0);
+    match value.split_once('-') {
+        Some((start, end)) => {
+            let (start, end) = (port(start)?, port(end)?);
+            (start <= end).then_some((start, end))
+        }
+        None => port(value).map(|port| (port, port)),
+    }
+}
+
+/// Validates a volume and makes a host path absolute, since `docker run`
+/// would take a relative path for a volume name.
+fn volume_spec(spec: &str) -> Result<String, String> {
+    let mut parts = spec.splitn(3, ':');
+    let source = parts.next().unwrap_or_default();
+    let Some(target) = parts.next() else {
+        // An anonymous volume at a container path.
+        return if source.starts_with('/') {
+            Ok(spec.to_string())
+        } else {
+            Err(format!(
+                "Invalid volume '{}', expected SOURCE:/container/path",
+                spec
+            ))
+        };
+    };
+    if !target.starts_with('/') {
+        return Err(format!(
+            "Container path '{}' in '{}' must be absolute",
+            target, spec
+        ));
+    }
+    let source = if source.starts_with(['/', '.', '~']) {
+        let mut path = host_path(source);
+        if path.is_relative() {
+            let cwd = env::current_dir().map_err(|e| e.to_string())?;
+            // Collecting the components drops the `.` of `./data`.
+            path = cwd.join(path).components().collect();
+        }
+        if !path.exists() {
+            return Err(format!("Host path {} does not exist", path.display()));
+        }
+        path.to_string_lossy().into_owned()
+    } else if is_valid_container_name(source) {
+        // Volume names follow the container name rules.
+        source.to_string()
+    } else {
+        return Err(format!("Invalid volume name '{}'", source));
+    };
+    Ok(match parts.next() {
+        Some(options) => format!("{}:{}:{}", source, target, options),
+        None => format!("{}:{}", source, target),
+    })
+}
+
+/// Splits a command line into arguments like a POSIX shell: quotes group words,
+/// single quotes are literal, and backslashes escape outside single quotes.
+pub fn split_command(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated ' in command".to_string()),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\' | '$' | '`')) => current.push(c),
+                            Some(c) => {
+                                current.push('\\');
+                                current.push(c);
+                            }
+                            None => return Err("Unterminated \" in command".to_string()),
+                        },
+                        Some(c) => current.push(c),
+                        None => return Err("Unterminated \" in command".to_string()),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                match chars.next() {
+                    Some(c) => current.push(c),
+                    None => return Err("Trailing \\ in command".to_string()),
+                }
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    args.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                current.push(c);
+            }
+        }
+    }
+    if in_word {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+/// Quotes an argument for display in a copy-pasteable shell command.
+pub fn shell_quote(arg: &str) -> String {
+    let safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-+=:,./@%".contains(c));
+    if safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn run_args_in_docker_order() {
+        let options = RunOptions {
+            image: " nginx:1.27 ".to_string(),
+            name: "web".to_string(),
+            ports: "8080:80, 443:443".to_string(),
+            env: "A=1\n# comment\n\nB=x,y".to_string(),
+            network: "backend".to_string(),
+            restart_policy: "always".to_string(),
+            memory: "512m".to_string(),
+            cpus: "1.5".to_string(),
+            labels: "team=web".to_string(),
+            command: "nginx -g 'daemon off;'".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            options.run_args().unwrap(),
+            args(&[
+                "run",
+                "-d",
+                "--name",
+                "web",
+                "-p",
+                "8080:80",
+                "-p",
+                "443:443",
+                "-e",
+                "A=1",
+                "-e",
+                "B=x,y",
+                "--network",
+                "backend",
+                "--restart",
+                "always",
+                "--memory",
+                "512m",
+                "--cpus",
+                "1.5",
+                "--label",
+                "team=web",
+                "nginx:1.27",
+                "nginx",
+                "-g",
+                "daemon off;",
+            ])
+        );
+    }
+
+    #[test]
+    fn empty_settings_use_docker_defaults() {
+        let options = RunOptions {
+            image: "alpine".to_string(),
+            restart_policy: "no".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(options.run_args().unwrap(), args(&["run", "-d", "alpine"]));
+    }
+
+    /// Valid options with one setting changed.
+    fn with(change: impl FnOnce(&mut RunOptions)) -> RunOptions {
+        let mut options = RunOptions {
+            image: "alpine".to_string(),
+            ..Default::default()
+        };
+        change(&mut options);
+        options
+    }
+
+    #[test]
+    fn rejects_invalid_settings() {
+        let cases = [
+            (with(|o| o.image.clear()), "An image is required"),
+            (
+                with(|o| o.image = "my image".to_string()),
+                "Invalid image 'my image'",
+            ),
+            (
+                with(|o| o.name = "-web".to_string()),
+                "Invalid name '-web': use letters, digits, '_', '.' and '-'",
+            ),
+            (
+                with(|o| o.ports = "8080:80,http".to_string()),
+                "Invalid port mapping 'http', e.g. 8080:80, 127.0.0.1:53:53/udp or 80",
+            ),
+            (
+                with(|o| o.env = "=value".to_string()),
+                "Invalid environment variable '=value', expected KEY=value",
+            ),
+            (
+                with(|o| o.volumes = "data".to_string()),
+                "Invalid volume 'data', expected SOURCE:/container/path",
+            ),
+            (
+                with(|o| o.memory = "4m".to_string()),
+                "Memory must be at least 6m",
+            ),
+            (
+                with(|o| o.memory = "lots".to_string()),
+                "Invalid memory 'lots', e.g. 512m or 2g",
+            ),
+            (
+                with(|o| o.cpus = "0".to_string()),
+                "Invalid CPUs '0', e.g. 0.5 or 2",
+            ),
+            (
+                with(|o| o.labels = "my label=x".to_string()),
+                "Invalid label 'my label=x', expected KEY=value",
+            ),
+            (
+                with(|o| o.command = "echo 'oops".to_string()),
+                "Unterminated ' in command",
+            ),
+        ];
+        for (options, error) in cases {
+            assert_eq!(options.run_args(), Err(error.to_string()));
+        }
+    }
+
+    #[test]
+    fn bare_keys_are_passed_on() {
+        let options = RunOptions {
+            env: "HOME\nPATH=/bin".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            options.env_args().unwrap(),
+            args(&["-e", "HOME", "-e", "PATH=/bin"])
+        );
+    }
+
+    #[test]
+    fn port_mappings() {
+        let valid = [
+            "80",
+            "8080:80",
+            ":80",
+            "53:53/udp",
+            "9000:9000/sctp",
+            "127.0.0.1:8080:80",
+            "127.0.0.1::80",
+            "[::1]:8080:80",
+            "[2001:db8::1]:53:53/udp",
+            "[::]::80",
+            "8000-8010:8000-8010",
+            "8000-8010:80",
+            "7000-7001",
+        ];
+        for spec in valid {
+            assert_eq!(check_port_mapping(spec), Ok(()), "{}", spec);
+        }
+
+        let invalid = [
+            "",
+            "http",
+            "0",
+            "65536",
+            "80/icmp",
+            "8080:",
+            "90-80:80",
+            "::1:8080:80",
+            "[::1]8080:80",
+            "[::1:8080:80",
+            ":8080:80",
+            "1.2.3.4:1:2:3",
+        ];
+        for spec in invalid {
+            assert!(check_port_mapping(spec).is_err(), "{}", spec);
+        }
+
+        assert_eq!(
+            check_port_mapping("8000-8001:80-82"),
+            Err("Port ranges in '8000-8001:80-82' differ in size".to_string())
+        );
+    }
+
+    #[test]
+    fn named_and_anonymous_volumes() {
+        assert_eq!(
+            volume_spec("data:/var/lib/data"),
+            Ok("data:/var/lib/data".to_string())
+        );
+        assert_eq!(
+            volume_spec("data:/data:ro,z"),
+            Ok("data:/data:ro,z".to_string())
+        );
+        assert_eq!(volume_spec("/cache"), Ok("/cache".to_string()));
+
+        assert_eq!(
+            volume_spec("cache"),
+            Err("Invalid volume 'cache', expected SOURCE:/container/path".to_string())
+        );
+        assert_eq!(
+            volume_spec("data:relative"),
+            Err("Container path 'relative' in 'data:relative' must be absolute".to_string())
+        );
+        assert_eq!(
+            volume_spec("my data:/data"),
+            Err("Invalid volume name 'my data'".to_string())
+        );
+    }
+
+    #[test]
+    fn host_paths_are_made_absolute() {
+        // Tests run in the package root.
+        let cwd = env::current_dir().unwrap();
+        let src = cwd.join("src").display().to_string();
+        assert_eq!(volume_spec("./src:/app"), Ok(format!("{}:/app", src)));
+        assert_eq!(volume_spec("./src:/app:ro"), Ok(format!("{}:/app:ro", src)));
+        assert_eq!(volume_spec(".:/app"), Ok(format!("{}:/app", cwd.display())));
+
+        let home = env::var("HOME").unwrap();
+        assert_eq!(volume_spec("~:/home"), Ok(format!("{}:/home", home)));
+        let missing = Path::new(&home).join("no-such-dir");
+        assert_eq!(
+            volume_spec("~/no-such-dir:/data:ro"),
+            Err(format!("Host path {} does not exist", missing.display()))
+        );
+
+        let missing = cwd.join("no-such-dir");
+        assert_eq!(
+            volume_spec("./no-such-dir:/data"),
+            Err(format!("Host path {} does not exist", missing.display()))
+        );
+    }
+
+    #[test]
+    fn splits_commands_like_a_shell() {
+        let cases: &[(&str, &[&str])] = &[
+            ("", &[]),
+            ("   ", &[]),
+            ("ls -la  /tmp", &["ls", "-la", "/tmp"]),
+            ("echo 'a  b' \"c d\"", &["echo", "a  b", "c d"]),
+            ("echo ''", &["echo", ""]),
+            ("echo 'it'\\''s'", &["echo", "it's"]),
+            (
+                r#"echo "say \"hi\" \$HOME \n""#,
+                &["echo", r#"say "hi" $HOME \n"#],
+            ),
+            (r"echo a\ b", &["echo", "a b"]),
+            ("sh -c 'echo $((1+2))'", &["sh", "-c", "echo $((1+2))"]),
+            ("grüße 'ünïcode'", &["grüße", "ünïcode"]),
+        ];
+        for (line, expected) in cases {
+            assert_eq!(split_command(line).unwrap(), args(expected), "{}", line);
+        }
+    }
+
+    #[test]
+    fn rejects_unterminated_quotes() {
+        assert_eq!(
+            split_command("echo 'oops"),
+            Err("Unterminated ' in command".to_string())
+        );
+        assert_eq!(
+            split_command("echo \"oops"),
+            Err("Unterminated \" in command".to_string())
+        );
+        assert_eq!(
+            split_command("echo \"oops\\"),
+            Err("Unterminated \" in command".to_string())
+        );
+        assert_eq!(
+            split_command("echo oops\\"),
+            Err("Trailing \\ in command".to_string())
+        );
+    }
+
+    #[test]
+    fn quotes_only_when_needed() {
+        assert_eq!(shell_quote("nginx:1.27"), "nginx:1.27");
+        assert_eq!(shell_quote("KEY=a,b/c@d%"), "KEY=a,b/c@d%");
+        assert_eq!(shell_quote(""), "''");
+        assert_eq!(shell_quote("daemon off;"), "'daemon off;'");
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn quoted_arguments_split_back_unchanged() {
+        let originals = [
+            "plain",
+            "",
+            "two words",
+            "it's",
+            "''",
+            "\"double\"",
+            r"back\slash",
+            "$HOME `id` $(id)",
+            "tab\there",
+            "line\nbreak",
+            "ünïcode ✓",
+            "'mixed \"quotes\"'",
+        ];
+        let line: Vec<String> = originals.iter().map(|arg| shell_quote(arg)).collect();
+        assert_eq!(split_command(&line.join(" ")).unwrap(), args(&originals));
+        for arg in originals {
+            assert_eq!(split_command(&shell_quote(arg)).unwrap(), [arg], "{}", arg);
+        }
+    }
+
+    #[test]
+    fn container_names() {
+        for name in ["web", "web-1", "a.b_c", "9lives"] {
+            assert!(is_valid_container_name(name), "{}", name);
+        }
+        for name in ["", "a", "-web", "_web", "web app", "wéb", "web/1"] {
+            assert!(!is_valid_container_name(name), "{}", name);
+        }
+    }
+}
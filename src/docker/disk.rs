@@ -0,0 +1,181 @@
+use super::hosts;
+use super::runtime::cli;
+use serde::{Deserialize, Deserializer};
+use std::sync::mpsc::{self, Receiver};
+
+/// One line of `docker system df`.
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct DiskCategory {
+    #[serde(rename = "Type")]
+    pub kind: String,
+    pub total_count: String,
+    pub active: String,
+    pub size: String,
+    pub reclaimable: String,
+}
+
+/// `docker system df -v`. The CLI formats every value as a string.
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "PascalCase")]
+pub struct DiskUsage {
+    #[serde(skip)]
+    pub summary: Vec<DiskCategory>,
+    #[serde(default)]
+    pub images: Vec<ImageUsage>,
+    #[serde(default)]
+    pub containers: Vec<ContainerUsage>,
+    #[serde(default)]
+    pub volumes: Vec<VolumeUsage>,
+    #[serde(default)]
+    pub build_cache: Vec<BuildCacheUsage>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ImageUsage {
+    #[serde(rename = "ID")]
+    pub id: String,
+    pub repository: String,
+    pub tag: String,
+    pub size: String,
+    #[serde(default)]
+    pub shared_size: String,
+    #[serde(default)]
+    pub unique_size: String,
+    #[serde(default, deserialize_with = "lenient_string")]
+    pub containers: String,
+}
+
+impl ImageUsage {
+    pub fn name(&self) -> String {
+        if self.repository == "<none>" {
+            self.id
+                .trim_start_matches("sha256:")
+                .chars()
+                .take(12)
+                .collect()
+        } else {
+            format!("{}:{}", self.repository, self.tag)
+        }
+    }
+
+    pub fn in_use(&self) -> bool {
+        self.containers != "0"
+    }
+
+    pub fn dangling(&self) -> bool {
+        self.repository == "<none>" && self.tag == "<none>"
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ContainerUsage {
+    pub names: String,
+    pub image: String,
+    pub size: String,
+    pub state: String,
+    pub status: String,
+}
+
+impl ContainerUsage {
+    /// States that `docker container prune` removes.
+    pub fn stopped(&self) -> bool {
+        matches!(self.state.as_str(), "exited" | "created" | "dead")
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct VolumeUsage {
+    pub name: String,
+    #[serde(default, deserialize_with = "lenient_string")]
+    pub links: String,
+    #[serde(default)]
+    pub size: String,
+}
+
+impl VolumeUsage {
+    pub fn in_use(&self) -> bool {
+        self.links != "0"
+    }
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct BuildCacheUsage {
+    #[serde(rename = "ID")]
+    pub id: String,
+    pub cache_type: String,
+    #[serde(default)]
+    pub description: String,
+    pub size: String,
+    #[serde(default, deserialize_with = "lenient_string")]
+    pub in_use: String,
+    #[serde(default)]
+    pub last_used_since: String,
+}
+
+impl BuildCacheUsage {
+    pub fn in_use(&self) -> bool {
+        self.in_use == "true"
+    }
+}
+
+/// Runs `docker system df` and `docker system df -v`.
+fn disk_usage() -> Result<DiskUsage, String> {
+    let mut usage = detailed_usage()?;
+    usage.summary = docker_json(&["system", "df", "--format", "{{json .}}"])?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    Ok(usage)
+}
+
+/// Runs only `docker system df -v`, leaving `summary` empty.
+pub(super) fn detailed_usage() -> Result<DiskUsage, String> {
+    let verbose = docker_json(&["system", "df", "-v", "--format", "{{json .}}"])?;
+    serde_json::from_str(&verbose)
+        .map_err(|e| format!("unexpected `docker system df -v` output: {}", e))
+}
+
+/// Counts and flags come back as strings from some CLI versions and as JSON
+/// numbers or booleans from others.
+fn lenient_string<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    Ok(match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::String(value) => value,
+        serde_json::Value::Null => String::new(),
+        value => value.to_string(),
+    })
+}
+
+fn docker_json(args: &[&str]) -> Result<String, String> {
+    let output = cli()
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run docker: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Computes disk usage in the background; sizing every layer and volume can take seconds.
+pub struct DiskUsageTask {
+    rx: Receiver<Result<DiskUsage, String>>,
+}
+
+impl DiskUsageTask {
+    pub fn spawn() -> Self {
+        let (tx, rx) = mpsc::channel();
+        hosts::spawn(move || {
+            let _ = tx.send(disk_usage());
+        });
+        DiskUsageTask { rx }
+    }
+
+    pub fn poll(&mut self) -> Option<Result<DiskUsage, String>> {
+        self.rx.try_recv().ok()
+    }
+}
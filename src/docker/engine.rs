@@ -0,0 +1,225 @@
+use super::hosts::{engine_endpoint, Endpoint};
+use crate::k8s::InsecureVerifier;
+use rustls::{
+    crypto::ring, pki_types::ServerName, ClientConfig, ClientConnection, RootCertStore, StreamOwned,
+};
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    os::unix::net::UnixStream,
+    sync::Arc,
+    time::Duration,
+};
+
+/// How long to wait for a tcp:// daemon to accept the connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connects to the Engine API of the current runtime and host, see `engine_endpoint`.
+fn connect() -> Result<Box<dyn ReadWrite>, String> {
+    let endpoint = engine_endpoint()?;
+    let host = endpoint.host.as_str();
+    if let Some(address) = host.strip_prefix("tcp://") {
+        let stream = connect_tcp(address).map_err(|e| format!("connect to {}: {}", address, e))?;
+        return match &endpoint.tls_dir {
+            Some(_) => tls_stream(stream, address, &endpoint),
+            None => Ok(Box::new(stream)),
+        };
+    }
+    if host.starts_with("ssh://") {
+        return Err(format!(
+            "{} is only reachable through the docker CLI",
+            endpoint.name
+        ));
+    }
+    let path = host.strip_prefix("unix://").unwrap_or(host);
+    let stream = UnixStream::connect(path).map_err(|e| format!("connect to {}: {}", path, e))?;
+    Ok(Box::new(stream))
+}
+
+/// Tries each address the host resolves to, giving each `CONNECT_TIMEOUT`.
+fn connect_tcp(address: &str) -> std::io::Result<TcpStream> {
+    let mut last_error = None;
+    for socket in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&socket, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error
+        .unwrap_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "no address found")))
+}
+
+/// TLS with the endpoint's `ca.pem`, falling back to the public roots, and its
+/// client certificate when there is one, like `docker --tlsverify`.
+fn tls_stream(
+    tcp: TcpStream,
+    address: &str,
+    endpoint: &Endpoint,
+) -> Result<Box<dyn ReadWrite>, String> {
+    let dir = endpoint.tls_dir.clone().unwrap_or_default();
+    let provider = Arc::new(ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("TLS setup failed: {}", e))?;
+    let builder = if endpoint.skip_tls_verify {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(InsecureVerifier(provider)))
+    } else {
+        let mut roots = RootCertStore::empty();
+        match fs::read(dir.join("ca.pem")) {
+            Ok(ca_pem) => {
+                for cert in rustls_pemfile::certs(&mut ca_pem.as_slice()) {
+                    let cert = cert.map_err(|e| format!("Invalid ca.pem: {}", e))?;
+                    roots
+                        .add(cert)
+                        .map_err(|e| format!("Invalid ca.pem: {}", e))?;
+                }
+            }
+            Err(_) => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+        }
+        builder.with_root_certificates(roots)
+    };
+    let config = match (
+        fs::read(dir.join("cert.pem")),
+        fs::read(dir.join("key.pem")),
+    ) {
+        (Ok(cert_pem), Ok(key_pem)) => {
+            let certs = rustls_pemfile::certs(&mut cert_pem.as_slice())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Invalid cert.pem: {}", e))?;
+            let key = rustls_pemfile::private_key(&mut key_pem.as_slice())
+                .map_err(|e| format!("Invalid key.pem: {}", e))?
+                .ok_or_else(|| "No private key found in key.pem".to_string())?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|e| format!("Invalid client certificate: {}", e))?
+        }
+        _ => builder.with_no_client_auth(),
+    };
+    let server = address
+        .rsplit_once(':')
+        .map_or(address, |(server, _)| server)
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let name = ServerName::try_from(server.to_string())
+        .map_err(|e| format!("Invalid server name '{}': {}", server, e))?;
+    let connection = ClientConnection::new(Arc::new(config), name)
+        .map_err(|e| format!("TLS setup failed: {}", e))?;
+    Ok(Box::new(StreamOwned::new(connection, tcp)))
+}
+
+trait ReadWrite: Read + Write + Send {}
+impl<T: Read + Write + Send> ReadWrite for T {}
+
+/// Sends a request to the Engine API and returns the response body as a stream.
+/// HTTP/1.0 keeps the body unchunked, so it simply ends when the daemon closes it.
+pub fn engine_stream(
+    method: &str,
+    path: &str,
+    headers: &[(&str, String)],
+) -> Result<Box<dyn BufRead + Send>, String> {
+    let stream = send_request(method, path, headers, 0)?;
+    read_response(stream)
+}
+
+/// Like `engine_stream`, but sends `length` bytes of `body` with the request,
+/// calling `progress` with the number of bytes sent so far.
+pub fn engine_upload(
+    method: &str,
+    path: &str,
+    headers: &[(&str, String)],
+    body: &mut dyn Read,
+    length: u64,
+    progress: &mut dyn FnMut(u64),
+) -> Result<Box<dyn BufRead + Send>, String> {
+    let mut stream = send_request(method, path, headers, length)?;
+    let mut buffer = vec![0; 64 * 1024];
+    let mut sent = 0;
+    loop {
+        let read = body
+            .read(&mut buffer)
+            .map_err(|e| format!("read upload: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        if let Err(e) = stream.write_all(&buffer[..read]) {
+            // The daemon may reject the request before reading the whole body;
+            // its response explains why better than the broken pipe does.
+            read_response(stream)?;
+            return Err(format!("send request body: {}", e));
+        }
+        sent += read as u64;
+        progress(sent);
+    }
+    read_response(stream)
+}
+
+fn send_request(
+    method: &str,
+    path: &str,
+    headers: &[(&str, String)],
+    content_length: u64,
+) -> Result<Box<dyn ReadWrite>, String> {
+    let mut stream = connect()?;
+    let mut request = format!(
+        "{} {} HTTP/1.0\r\nHost: docker\r\nContent-Length: {}\r\n",
+        method, path, content_length
+    );
+    for (name, value) in headers {
+        request.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    request.push_str("\r\n");
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|e| format!("send request: {}", e))?;
+    Ok(stream)
+}
+
+fn read_response(stream: Box<dyn ReadWrite>) -> Result<Box<dyn BufRead + Send>, String> {
+    let mut reader = BufReader::new(stream);
+    let mut status_line = String::new();
+    reader
+        .read_line(&mut status_line)
+        .map_err(|e| format!("read response: {}", e))?;
+    let status: u16 = status_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|code| code.parse().ok())
+        .ok_or_else(|| format!("malformed response: {}", status_line.trim()))?;
+
+    loop {
+        let mut header = String::new();
+        let read = reader
+            .read_line(&mut header)
+            .map_err(|e| format!("read response: {}", e))?;
+        if read == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    if !(200..300).contains(&status) {
+        let mut body = String::new();
+        let _ = reader.read_to_string(&mut body);
+        let message = serde_json::from_str::<serde_json::Value>(&body)
+            .ok()
+            .and_then(|v| v.get("message").and_then(|m| m.as_str()).map(String::from))
+            .unwrap_or_else(|| body.trim().to_string());
+        return Err(format!("HTTP {}: {}", status, message));
+    }
+    Ok(Box::new(reader))
+}
+
+/// Percent-encodes a query parameter value.
+pub fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
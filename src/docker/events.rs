@@ -0,0 +1,208 @@
+use super::engine::engine_stream;
+use super::hosts::{self, active_endpoint_name, on_endpoint, Endpoint};
+use chrono::{DateTime, Local};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    io::BufRead,
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::Duration,
+};
+
+const RETRY_DELAY: Duration = Duration::from_secs(3);
+
+/// One message of the Engine API `/events` stream.
+#[derive(Deserialize)]
+struct RawEvent {
+    #[serde(rename = "Type", default)]
+    kind: String,
+    #[serde(rename = "Action", default)]
+    action: String,
+    #[serde(rename = "Actor", default)]
+    actor: Actor,
+    #[serde(default)]
+    time: i64,
+}
+
+#[derive(Deserialize, Default)]
+struct Actor {
+    #[serde(rename = "ID", default)]
+    id: String,
+    #[serde(rename = "Attributes", default)]
+    attributes: HashMap<String, String>,
+}
+
+#[derive(Clone)]
+pub struct DockerEvent {
+    pub time: i64,
+    /// `container`, `image`, `network`, `volume`, ...
+    pub kind: String,
+    /// `start`, `die`, `health_status`, `pull`, ...
+    pub action: String,
+    pub id: String,
+    /// Container, network or volume name; image reference for image events.
+    pub name: String,
+    /// Exit code of `die`, new state of `health_status`, or the image of a container.
+    pub detail: String,
+    /// The endpoint that reported it.
+    pub host: String,
+}
+
+impl DockerEvent {
+    fn from_raw(raw: RawEvent, host: &str) -> Self {
+        let attributes = &raw.actor.attributes;
+        // Health changes arrive as "health_status: healthy".
+        let (action, health) = match raw.action.split_once(": ") {
+            Some((action, state)) => (action.to_string(), Some(state.to_string())),
+            None => (raw.action.clone(), None),
+        };
+        let detail = match (action.as_str(), health) {
+            (_, Some(state)) => state,
+            ("die", _) => attributes
+                .get("exitCode")
+                .map(|code| format!("exit code {}", code))
+                .unwrap_or_default(),
+            _ if raw.kind == "container" => attributes.get("image").cloned().unwrap_or_default(),
+            _ => attributes.get("container").cloned().unwrap_or_default(),
+        };
+        // Image events carry the reference ("nginx:latest") or the image ID as actor.
+        let name = match attributes.get("name") {
+            Some(name) if raw.kind != "image" => name.clone(),
+            _ => raw
+                .actor
+                .id
+                .trim_start_matches("sha256:")
+                .chars()
+                .take(if raw.actor.id.starts_with("sha256:") {
+                    12
+                } else {
+                    usize::MAX
+                })
+                .collect(),
+        };
+        DockerEvent {
+            time: raw.time,
+            kind: raw.kind,
+            action,
+            id: raw.actor.id,
+            name,
+            detail,
+            host: host.to_string(),
+        }
+    }
+
+    pub fn local_time(&self) -> String {
+        DateTime::from_timestamp(self.time, 0)
+            .map(|time| time.with_timezone(&Local).format("%H:%M:%S").to_string())
+            .unwrap_or_default()
+    }
+
+    /// Whether the event changes what `docker ps` shows.
+    pub fn affects_containers(&self) -> bool {
+        self.kind == "container"
+    }
+
+    /// Whether the event changes what the Volumes view shows: the volumes and
+    /// the containers that mount them.
+    pub fn affects_volumes(&self) -> bool {
+        self.kind == "volume" || self.affects_containers()
+    }
+
+    /// Failures worth highlighting: non-zero exits, OOM kills and failing health checks.
+    pub fn is_failure(&self) -> bool {
+        match self.action.as_str() {
+            "oom" => true,
+            "die" => !self.detail.is_empty() && self.detail != "exit code 0",
+            "health_status" => self.detail == "unhealthy",
+            _ => false,
+        }
+    }
+}
+
+enum StreamMessage {
+    Connected,
+    Event(DockerEvent),
+    Error(String),
+}
+
+/// Follows the event streams of one or more daemons, reconnecting when one drops.
+pub struct EventStream {
+    rx: Receiver<(usize, StreamMessage)>,
+    /// Per stream: `None` until it first connects or fails, then its last error if down.
+    streams: Vec<Option<Option<String>>>,
+    /// Whether every stream is up.
+    pub connected: bool,
+    /// Why a stream is down, prefixed with its host.
+    pub last_error: Option<String>,
+}
+
+impl EventStream {
+    /// One stream per endpoint, or a single one for the current endpoint when
+    /// `endpoints` is empty. Each event is tagged with its endpoint's name.
+    pub fn spawn(endpoints: &[Endpoint]) -> Self {
+        let (tx, rx) = mpsc::channel();
+        if endpoints.is_empty() {
+            let tx = tx.clone();
+            hosts::spawn(move || event_loop(0, tx));
+        }
+        for (index, endpoint) in endpoints.iter().enumerate() {
+            let tx = tx.clone();
+            on_endpoint(endpoint, || hosts::spawn(move || event_loop(index, tx)));
+        }
+        EventStream {
+            rx,
+            streams: vec![None; endpoints.len().max(1)],
+            connected: false,
+            last_error: None,
+        }
+    }
+
+    /// Returns the events received since the last call.
+    pub fn poll(&mut self) -> Vec<DockerEvent> {
+        let mut events = Vec::new();
+        while let Ok((index, message)) = self.rx.try_recv() {
+            match message {
+                StreamMessage::Connected => self.streams[index] = Some(None),
+                StreamMessage::Event(event) => events.push(event),
+                StreamMessage::Error(error) => self.streams[index] = Some(Some(error)),
+            }
+        }
+        self.connected = self.streams.iter().all(|stream| stream == &Some(None));
+        self.last_error = self.streams.iter().flatten().flatten().next().cloned();
+        events
+    }
+}
+
+fn event_loop(index: usize, tx: Sender<(usize, StreamMessage)>) {
+    let host = active_endpoint_name();
+    let send = |message| tx.send((index, message)).is_ok();
+    loop {
+        let error = match engine_stream("GET", "/events", &[]) {
+            Ok(reader) => {
+                if !send(StreamMessage::Connected) {
+                    return;
+                }
+                for line in reader.lines() {
+                    let Ok(line) = line else { break };
+                    let Ok(raw) = serde_json::from_str::<RawEvent>(&line) else {
+                        continue;
+                    };
+                    // Health checks run an exec every interval; those only add noise.
+                    if raw.action.starts_with("exec_") {
+                        continue;
+                    }
+                    if !send(StreamMessage::Event(DockerEvent::from_raw(raw, &host))) {
+                        return;
+                    }
+                }
+                "event stream closed".to_string()
+            }
+            Err(e) => e,
+        };
+        if !send(StreamMessage::Error(format!("{}: {}", host, error))) {
+            return;
+        }
+        thread::sleep(RETRY_DELAY);
+    }
+}
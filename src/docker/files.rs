@@ -0,0 +1,288 @@
+use super::engine::{encode_query, engine_stream, engine_upload};
+use super::hosts;
+use super::runtime::cli;
+use std::{
+    env, fs,
+    io::{Read, Write},
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+    sync::mpsc::{self, Receiver, Sender},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// One line of `ls -lAn` inside a container.
+#[derive(Clone)]
+pub struct FileEntry {
+    pub name: String,
+    /// First character of the mode: `d`, `-`, `l`, `c`, ...
+    pub kind: char,
+    pub mode: String,
+    pub size: u64,
+    pub modified: String,
+    pub link_target: Option<String>,
+}
+
+impl FileEntry {
+    pub fn is_dir(&self) -> bool {
+        self.kind == 'd'
+    }
+}
+
+/// Lists a directory by running `ls` in the container, so it needs a running
+/// container that ships `ls`.
+pub fn list_dir(container: &str, path: &str) -> Result<Vec<FileEntry>, String> {
+    // The trailing slash makes ls follow a symlinked directory.
+    let target = format!("{}/", path.trim_end_matches('/'));
+    let output = cli()
+        .args(["exec", "-e", "LC_ALL=C", container, "ls", "-lAn", &target])
+        .output()
+        .map_err(|e| format!("failed to run docker: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let mut entries: Vec<FileEntry> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(parse_ls_line)
+        .collect();
+    entries.sort_by(|a, b| b.is_dir().cmp(&a.is_dir()).then(a.name.cmp(&b.name)));
+    Ok(entries)
+}
+
+/// Parses `drwxr-xr-x 2 0 0 4096 Oct 18 10:00 name`, keeping spaces in the name.
+fn parse_ls_line(line: &str) -> Option<FileEntry> {
+    let mode = line.split_whitespace().next()?;
+    let kind = mode.chars().next()?;
+    if mode.len() < 10 || !"-dlcbps".contains(kind) {
+        // "total 64"
+        return None;
+    }
+    // Device files show "major, minor" instead of a size.
+    let size_fields = if matches!(kind, 'c' | 'b') { 2 } else { 1 };
+    let fields = 4 + size_fields + 3;
+
+    let mut rest = line;
+    let mut tokens = Vec::new();
+    for _ in 0..fields {
+        rest = rest.trim_start();
+        let end = rest.find(char::is_whitespace)?;
+        tokens.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+    let name = rest.trim_start_matches(' ');
+    let (name, link_target) = match name.split_once(" -> ") {
+        Some((name, target)) if kind == 'l' => (name, Some(target.to_string())),
+        _ => (name, None),
+    };
+    if name.is_empty() {
+        return None;
+    }
+    Some(FileEntry {
+        name: name.to_string(),
+        kind,
+        mode: mode.to_string(),
+        size: if size_fields == 1 {
+            tokens[4].parse().unwrap_or(0)
+        } else {
+            0
+        },
+        modified: tokens[fields - 3..].join(" "),
+        link_target,
+    })
+}
+
+pub fn join_path(dir: &str, name: &str) -> String {
+    format!("{}/{}", dir.trim_end_matches('/'), name)
+}
+
+pub fn parent_path(path: &str) -> String {
+    match path.trim_end_matches('/').rsplit_once('/') {
+        Some(("", _)) | None => "/".to_string(),
+        Some((parent, _)) => parent.to_string(),
+    }
+}
+
+enum TransferMessage {
+    Total(u64),
+    Progress(u64),
+    Done(Result<(), String>),
+}
+
+/// A copy between the host and a container running in the background, like
+/// `docker cp` but with byte counts.
+pub struct Transfer {
+    pub label: String,
+    pub transferred: u64,
+    /// Known for uploads and for downloads of single files.
+    pub total: Option<u64>,
+    pub result: Option<Result<(), String>>,
+    /// Container and directory an upload writes into.
+    pub upload_target: Option<(String, String)>,
+    rx: Receiver<TransferMessage>,
+}
+
+impl Transfer {
+    /// Downloads `path` from the container into the host directory `destination`.
+    pub fn download(container: &str, path: &str, destination: &Path, total: Option<u64>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let request = format!(
+            "/containers/{}/archive?path={}",
+            container,
+            encode_query(path)
+        );
+        let target = destination.to_path_buf();
+        hosts::spawn(move || {
+            let result = download(&request, &target, &tx);
+            let _ = tx.send(TransferMessage::Done(result));
+        });
+        Transfer {
+            label: format!("{} → {}", path, destination.display()),
+            transferred: 0,
+            total,
+            result: None,
+            upload_target: None,
+            rx,
+        }
+    }
+
+    /// Uploads the host file or directory `local` into the container directory `directory`.
+    pub fn upload(container: &str, local: &Path, directory: &str) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let request = format!(
+            "/containers/{}/archive?path={}",
+            container,
+            encode_query(directory)
+        );
+        let local_path = local.to_path_buf();
+        hosts::spawn(move || {
+            let result = upload(&request, &local_path, &tx);
+            let _ = tx.send(TransferMessage::Done(result));
+        });
+        Transfer {
+            label: format!("{} → {}", local.display(), directory),
+            transferred: 0,
+            total: None,
+            result: None,
+            upload_target: Some((container.to_string(), directory.to_string())),
+            rx,
+        }
+    }
+
+    /// Applies queued progress. Returns true when the transfer has just finished.
+    pub fn poll(&mut self) -> bool {
+        let mut finished = false;
+        while let Ok(message) = self.rx.try_recv() {
+            match message {
+                TransferMessage::Total(bytes) => self.total = Some(bytes),
+                TransferMessage::Progress(bytes) => self.transferred = bytes,
+                TransferMessage::Done(result) => {
+                    finished = true;
+                    self.result = Some(result);
+                }
+            }
+        }
+        finished
+    }
+
+    pub fn ratio(&self) -> Option<f64> {
+        match self.result {
+            Some(Ok(())) => Some(1.0),
+            _ => self
+                .total
+                .filter(|total| *total > 0)
+                .map(|total| (self.transferred as f64 / total as f64).min(1.0)),
+        }
+    }
+}
+
+/// Streams the archive of a container path into `tar -x`.
+fn download(request: &str, destination: &Path, tx: &Sender<TransferMessage>) -> Result<(), String> {
+    let mut reader = engine_stream("GET", request, &[])?;
+    fs::create_dir_all(destination)
+        .map_err(|e| format!("create {}: {}", destination.display(), e))?;
+    let mut tar = Command::new("tar")
+        .arg("-x")
+        .arg("-C")
+        .arg(destination)
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run tar: {}", e))?;
+
+    let mut stdin = tar.stdin.take().ok_or("tar has no stdin")?;
+    let mut buffer = vec![0; 64 * 1024];
+    let mut received = 0;
+    loop {
+        let read = reader
+            .read(&mut buffer)
+            .map_err(|e| format!("read archive: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        stdin
+            .write_all(&buffer[..read])
+            .map_err(|e| format!("extract: {}", e))?;
+        received += read as u64;
+        let _ = tx.send(TransferMessage::Progress(received));
+    }
+    drop(stdin);
+
+    let output = tar.wait_with_output().map_err(|e| format!("tar: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(())
+}
+
+/// Packs `local` with `tar -c` and sends it to the archive endpoint. The archive
+/// goes through a temporary file since the request needs its length up front.
+fn upload(request: &str, local: &Path, tx: &Sender<TransferMessage>) -> Result<(), String> {
+    let name = local
+        .file_name()
+        .ok_or_else(|| format!("{} has no file name", local.display()))?;
+    let parent = local
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    let archive = env::temp_dir().join(format!("container-upload-{}-{}.tar", process::id(), nanos));
+
+    let result = (|| {
+        let output = Command::new("tar")
+            .arg("-c")
+            .arg("-f")
+            .arg(&archive)
+            .arg("-C")
+            .arg(parent)
+            .arg(name)
+            .output()
+            .map_err(|e| format!("failed to run tar: {}", e))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        let mut file = fs::File::open(&archive).map_err(|e| format!("open archive: {}", e))?;
+        let length = file
+            .metadata()
+            .map_err(|e| format!("open archive: {}", e))?
+            .len();
+        let _ = tx.send(TransferMessage::Total(length));
+        let headers = [("Content-Type", "application/x-tar".to_string())];
+        let mut progress = |sent| {
+            let _ = tx.send(TransferMessage::Progress(sent));
+        };
+        engine_upload("PUT", request, &headers, &mut file, length, &mut progress).map(|_| ())
+    })();
+    let _ = fs::remove_file(&archive);
+    result
+}
+
+/// Expands `~` and a leading `~/` in a host path.
+pub fn host_path(path: &str) -> PathBuf {
+    match (path, path.strip_prefix("~/"), env::var_os("HOME")) {
+        ("~", _, Some(home)) => PathBuf::from(home),
+        (_, Some(rest), Some(home)) => PathBuf::from(home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
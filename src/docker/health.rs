@@ -0,0 +1,155 @@
+use super::hosts;
+use super::runtime::cli;
+use chrono::{DateTime, Local, Utc};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    sync::mpsc::{self, Receiver},
+};
+
+/// Restart and exit information `docker ps` does not show.
+#[derive(Clone, Default)]
+pub struct ContainerState {
+    pub restart_count: u32,
+    pub exit_code: i64,
+    pub oom_killed: bool,
+    /// Docker shows "(Paused)" in the status; Podman and nerdctl only "Paused".
+    pub paused: bool,
+    /// When the current run started, i.e. the time of the last restart.
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+/// `.State.Health` of `docker inspect`.
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct ContainerHealth {
+    pub status: String,
+    #[serde(default)]
+    pub failing_streak: u32,
+    #[serde(default)]
+    pub log: Vec<HealthCheck>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "PascalCase")]
+pub struct HealthCheck {
+    pub start: String,
+    pub exit_code: i64,
+    #[serde(default)]
+    pub output: String,
+}
+
+impl HealthCheck {
+    pub fn local_time(&self) -> String {
+        DateTime::parse_from_rfc3339(&self.start)
+            .map(|time| time.with_timezone(&Local).format("%H:%M:%S").to_string())
+            .unwrap_or_else(|_| self.start.clone())
+    }
+}
+
+/// Inspects all `ids` at once. Containers removed in the meantime are left out.
+pub fn container_states(ids: &[&str]) -> HashMap<String, ContainerState> {
+    if ids.is_empty() {
+        return HashMap::new();
+    }
+    let output = cli()
+        .args([
+            "inspect",
+            "--format",
+            "{{.Id}}|{{.RestartCount}}|{{.State.ExitCode}}|{{.State.OOMKilled}}|{{.State.Paused}}|{{.State.StartedAt}}",
+        ])
+        .args(ids)
+        .output();
+    let Ok(output) = output else {
+        return HashMap::new();
+    };
+    // A container that disappeared fails the command but the others are still printed.
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split('|').collect();
+            if parts.len() < 6 {
+                return None;
+            }
+            Some((
+                parts[0].to_string(),
+                ContainerState {
+                    restart_count: parts[1].parse().unwrap_or(0),
+                    exit_code: parts[2].parse().unwrap_or(0),
+                    oom_killed: parts[3] == "true",
+                    paused: parts[4] == "true",
+                    started_at: parse_started_at(parts[5]),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Docker prints RFC 3339; Podman prints Go's default time format,
+/// e.g. `2024-05-01 12:00:00.5 +0000 UTC`. Never-started containers have a zero time.
+fn parse_started_at(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.trim();
+    let parsed = DateTime::parse_from_rfc3339(value).or_else(|_| {
+        let without_zone_name = value.rsplit_once(' ').map_or(value, |(time, _)| time);
+        DateTime::parse_from_str(without_zone_name, "%Y-%m-%d %H:%M:%S%.f %z")
+    });
+    parsed
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+        .filter(|time| time.timestamp() > 0)
+}
+
+/// Inspects a container's health checks in the background; on a remote
+/// host every `docker inspect` is a round trip.
+pub struct HealthTask {
+    pub id: String,
+    rx: Receiver<Result<Option<ContainerHealth>, String>>,
+}
+
+impl HealthTask {
+    pub fn spawn(id: &str) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let container = id.to_string();
+        hosts::spawn(move || {
+            let _ = tx.send(inspect_health(&container));
+        });
+        HealthTask {
+            id: id.to_string(),
+            rx,
+        }
+    }
+
+    pub fn poll(&mut self) -> Option<Result<Option<ContainerHealth>, String>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Health check status and recent results; `None` when the container has no health check.
+fn inspect_health(id: &str) -> Result<Option<ContainerHealth>, String> {
+    let output = cli()
+        .args(["inspect", "--format", "{{json .State.Health}}", id])
+        .output()
+        .map_err(|e| format!("failed to run docker: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    serde_json::from_slice(&output.stdout).map_err(|e| format!("unexpected health output: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_start_times_of_docker_and_podman() {
+        let docker = parse_started_at("2024-05-01T12:00:00.123456789Z").unwrap();
+        assert_eq!(docker.to_rfc3339(), "2024-05-01T12:00:00.123456789+00:00");
+
+        let podman = parse_started_at("2024-05-01 14:00:00.5 +0200 CEST").unwrap();
+        assert_eq!(podman.to_rfc3339(), "2024-05-01T12:00:00.500+00:00");
+
+        assert!(parse_started_at("0001-01-01T00:00:00Z").is_none());
+        assert!(parse_started_at("<no value>").is_none());
+        assert!(parse_started_at("").is_none());
+    }
+}
@@ -0,0 +1,365 @@
+use super::runtime::{engine_host, Runtime};
+use serde::Deserialize;
+use std::{
+    cell::RefCell, collections::HashMap, env, fs, path::PathBuf, process::Command, sync::Mutex,
+    thread,
+};
+
+/// A daemon the Docker tab can manage: a context from `~/.docker/contexts`
+/// (or Docker's built-in `default`), or an address entered by hand.
+#[derive(Clone, PartialEq)]
+pub struct Endpoint {
+    pub name: String,
+    /// `unix://`, `tcp://` or `ssh://` address.
+    pub host: String,
+    /// Set for contexts, which the CLI resolves itself with `--context`.
+    pub context: Option<String>,
+    /// Directory with `ca.pem`, `cert.pem` and `key.pem` for TLS over tcp.
+    pub tls_dir: Option<PathBuf>,
+    pub skip_tls_verify: bool,
+}
+
+impl Endpoint {
+    /// Parses a `DOCKER_HOST`-style address, optionally followed by the CLI's
+    /// `--tlsverify` or `--tls` (TLS without verifying the daemon). Without
+    /// either, TLS is on only when `DOCKER_TLS_VERIFY` is set, as for the CLI.
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut words = input.split_whitespace();
+        let host = words.next().unwrap_or_default();
+        let mut flag = None;
+        for word in words {
+            match word {
+                "--tls" => flag = flag.or(Some(true)),
+                "--tlsverify" => flag = Some(false),
+                _ => {
+                    return Err(format!(
+                        "Unknown option '{}', expected --tlsverify or --tls",
+                        word
+                    ))
+                }
+            }
+        }
+        let address = ["unix://", "tcp://", "ssh://"]
+            .iter()
+            .find_map(|scheme| host.strip_prefix(scheme))
+            .ok_or_else(|| {
+                format!(
+                    "Invalid host '{}', expected unix://, tcp:// or ssh://",
+                    host
+                )
+            })?;
+        if address.is_empty() {
+            return Err(format!("Invalid host '{}'", host));
+        }
+        if host.starts_with("tcp://") && !address.contains(':') {
+            return Err(format!(
+                "Host '{}' needs a port, e.g. tcp://{}:2376",
+                host, address
+            ));
+        }
+        let (tls_dir, skip_tls_verify) = match flag {
+            Some(_) if !host.starts_with("tcp://") => {
+                return Err(format!(
+                    "TLS options only apply to tcp:// hosts, not '{}'",
+                    host
+                ))
+            }
+            Some(skip_tls_verify) => (Some(cert_dir()?), skip_tls_verify),
+            None if host.starts_with("tcp://") => (env_tls()?, false),
+            None => (None, false),
+        };
+        Ok(Endpoint {
+            name: host.to_string(),
+            host: host.to_string(),
+            context: None,
+            tls_dir,
+            skip_tls_verify,
+        })
+    }
+
+    pub fn tls_label(&self) -> &'static str {
+        match (&self.tls_dir, self.skip_tls_verify) {
+            (None, _) => "",
+            (Some(_), false) => "verify",
+            (Some(_), true) => "no verify",
+        }
+    }
+
+    /// Points a docker CLI command at this endpoint.
+    pub(super) fn configure(&self, command: &mut Command) {
+        if let Some(context) = &self.context {
+            command
+                .args(["--context", context])
+                .env_remove("DOCKER_HOST");
+            return;
+        }
+        command
+            .env("DOCKER_HOST", &self.host)
+            .env_remove("DOCKER_CONTEXT");
+        if let Some(dir) = &self.tls_dir {
+            command.env("DOCKER_CERT_PATH", dir);
+            if self.skip_tls_verify {
+                command.arg("--tls").env_remove("DOCKER_TLS_VERIFY");
+            } else {
+                command.env("DOCKER_TLS_VERIFY", "1");
+            }
+        }
+    }
+}
+
+/// The certificate directory when `DOCKER_TLS_VERIFY` is set. Like the docker
+/// CLI, `DOCKER_CERT_PATH` alone does not turn TLS on, and TLS from the
+/// environment always verifies the daemon.
+fn env_tls() -> Result<Option<PathBuf>, String> {
+    if env::var_os("DOCKER_TLS_VERIFY").is_some_and(|value| !value.is_empty()) {
+        cert_dir().map(Some)
+    } else {
+        Ok(None)
+    }
+}
+
+/// `DOCKER_CERT_PATH`, or the config directory. An error when it does not
+/// exist, so asking for TLS never falls back to plain text.
+fn cert_dir() -> Result<PathBuf, String> {
+    let dir = env::var_os("DOCKER_CERT_PATH")
+        .map(PathBuf::from)
+        .or_else(config_dir)
+        .ok_or_else(|| "TLS needs certificates, set DOCKER_CERT_PATH".to_string())?;
+    if dir.is_dir() {
+        Ok(dir)
+    } else {
+        Err(format!(
+            "TLS certificate directory {} does not exist, set DOCKER_CERT_PATH",
+            dir.display()
+        ))
+    }
+}
+
+/// `DOCKER_CONFIG`, or `~/.docker`.
+pub(super) fn config_dir() -> Option<PathBuf> {
+    env::var_os("DOCKER_CONFIG")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".docker")))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContextMeta {
+    name: String,
+    #[serde(default)]
+    endpoints: HashMap<String, ContextEndpoint>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ContextEndpoint {
+    #[serde(default)]
+    host: String,
+    #[serde(rename = "SkipTLSVerify", default)]
+    skip_tls_verify: bool,
+}
+
+/// Docker's built-in `default` context, followed by the contexts in
+/// `contexts/meta/<id>/meta.json`, whose TLS files are in `contexts/tls/<id>/docker`.
+pub fn list_contexts() -> Vec<Endpoint> {
+    let default = Endpoint {
+        name: "default".to_string(),
+        host: env::var("DOCKER_HOST")
+            .ok()
+            .filter(|host| !host.is_empty())
+            .unwrap_or_else(|| engine_host().unwrap_or_default()),
+        context: Some("default".to_string()),
+        // An unusable certificate directory is reported when the daemon is used.
+        tls_dir: env_tls().ok().flatten(),
+        skip_tls_verify: false,
+    };
+    let Some(contexts_dir) = config_dir().map(|dir| dir.join("contexts")) else {
+        return vec![default];
+    };
+    let mut contexts: Vec<Endpoint> = fs::read_dir(contexts_dir.join("meta"))
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let meta: ContextMeta =
+                serde_json::from_str(&fs::read_to_string(entry.path().join("meta.json")).ok()?)
+                    .ok()?;
+            let endpoint = meta.endpoints.get("docker")?;
+            let tls_dir = contexts_dir
+                .join("tls")
+                .join(entry.file_name())
+                .join("docker");
+            Some(Endpoint {
+                host: endpoint.host.clone(),
+                context: Some(meta.name.clone()),
+                tls_dir: tls_dir.is_dir().then_some(tls_dir),
+                skip_tls_verify: endpoint.skip_tls_verify,
+                name: meta.name,
+            })
+        })
+        .collect();
+    contexts.sort_by(|a, b| a.name.cmp(&b.name));
+    contexts.insert(0, default);
+    contexts
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct CliConfig {
+    #[serde(default)]
+    current_context: String,
+}
+
+/// `DOCKER_CONTEXT`, or `currentContext` in `config.json`; `None` for `default`.
+fn current_context() -> Option<Endpoint> {
+    let name = env::var("DOCKER_CONTEXT").ok().or_else(|| {
+        let config = fs::read_to_string(config_dir()?.join("config.json")).ok()?;
+        serde_json::from_str::<CliConfig>(&config)
+            .ok()
+            .map(|config| config.current_context)
+    })?;
+    if name.is_empty() || name == "default" {
+        return None;
+    }
+    list_contexts()
+        .into_iter()
+        .find(|context| context.name == name)
+}
+
+static SELECTED: Mutex<Option<Endpoint>> = Mutex::new(None);
+
+thread_local! {
+    /// Set while working on one host of the all-hosts view.
+    static OVERRIDE: RefCell<Option<Endpoint>> = const { RefCell::new(None) };
+}
+
+/// The endpoint picked in the host picker, unless overridden on this thread;
+/// `None` leaves the CLI on its own default.
+pub(super) fn selected_endpoint() -> Option<Endpoint> {
+    OVERRIDE
+        .with(|current| current.borrow().clone())
+        .or_else(|| SELECTED.lock().ok()?.clone())
+}
+
+pub fn select_endpoint(endpoint: Endpoint) {
+    if let Ok(mut selected) = SELECTED.lock() {
+        *selected = Some(endpoint);
+    }
+}
+
+/// The name shown for the current daemon.
+pub fn active_endpoint_name() -> String {
+    selected_endpoint()
+        .or_else(|| {
+            env::var_os("DOCKER_HOST")
+                .is_none()
+                .then(current_context)
+                .flatten()
+        })
+        .map_or_else(|| "default".to_string(), |endpoint| endpoint.name)
+}
+
+/// The address and TLS settings for the Engine API.
+pub(super) fn engine_endpoint() -> Result<Endpoint, String> {
+    if Runtime::current() == Runtime::Docker {
+        if let Some(endpoint) = selected_endpoint() {
+            // The default context takes its TLS settings from the environment.
+            if endpoint.context.as_deref() == Some("default") && endpoint.host.starts_with("tcp://")
+            {
+                env_tls()?;
+            }
+            return Ok(endpoint);
+        }
+        if env::var_os("DOCKER_HOST").is_none() {
+            if let Some(endpoint) = current_context() {
+                return Ok(endpoint);
+            }
+        }
+    }
+    let host = engine_host()?;
+    let tls_dir = if host.starts_with("tcp://") {
+        env_tls()?
+    } else {
+        None
+    };
+    Ok(Endpoint {
+        name: "default".to_string(),
+        host,
+        context: None,
+        tls_dir,
+        skip_tls_verify: false,
+    })
+}
+
+/// Runs `f` against `endpoint` on this thread.
+pub fn on_endpoint<T>(endpoint: &Endpoint, f: impl FnOnce() -> T) -> T {
+    let _guard = override_endpoint(endpoint.clone());
+    f()
+}
+
+/// Targets `endpoint` on this thread until the guard is dropped.
+pub fn override_endpoint(endpoint: Endpoint) -> EndpointGuard {
+    let previous = OVERRIDE.with(|current| current.replace(Some(endpoint)));
+    EndpointGuard { previous }
+}
+
+pub struct EndpointGuard {
+    previous: Option<Endpoint>,
+}
+
+impl Drop for EndpointGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        OVERRIDE.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Like `thread::spawn`, but the thread keeps talking to the caller's endpoint.
+pub(super) fn spawn(f: impl FnOnce() + Send + 'static) {
+    let endpoint = OVERRIDE.with(|current| current.borrow().clone());
+    thread::spawn(move || match endpoint {
+        Some(endpoint) => on_endpoint(&endpoint, f),
+        None => f(),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_explicit_tls_options() {
+        // The only test reading DOCKER_CERT_PATH, so setting it races with nothing.
+        let missing = env::temp_dir().join(format!("docker-certs-missing-{}", std::process::id()));
+        env::set_var("DOCKER_CERT_PATH", &missing);
+        let error = Endpoint::parse("tcp://build:2376 --tlsverify")
+            .err()
+            .unwrap();
+        assert!(error.contains("does not exist"), "{}", error);
+
+        let certs = env::temp_dir();
+        env::set_var("DOCKER_CERT_PATH", &certs);
+        let endpoint = Endpoint::parse("tcp://build:2376 --tls").unwrap();
+        assert_eq!(endpoint.tls_dir, Some(certs));
+        assert_eq!(endpoint.host, "tcp://build:2376");
+        assert_eq!(endpoint.name, "tcp://build:2376");
+        assert!(endpoint.skip_tls_verify);
+
+        for input in [
+            "tcp://build:2376 --tlsverify",
+            "tcp://build:2376 --tlsverify --tls",
+        ] {
+            let endpoint = Endpoint::parse(input).unwrap();
+            assert!(!endpoint.skip_tls_verify);
+        }
+
+        let endpoint = Endpoint::parse(" unix:///run/docker.sock ").unwrap();
+        assert_eq!(endpoint.host, "unix:///run/docker.sock");
+        assert_eq!(endpoint.tls_label(), "");
+
+        assert!(Endpoint::parse("unix:///run/docker.sock --tls").is_err());
+        assert!(Endpoint::parse("tcp://build:2376 --insecure").is_err());
+        assert!(Endpoint::parse("tcp://build").is_err());
+        assert!(Endpoint::parse("build:2376").is_err());
+    }
+}
@@ -0,0 +1,231 @@
+use super::hosts;
+use super::runtime::cli;
+use serde::Deserialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::mpsc::{self, Receiver},
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct ImageInspect {
+    id: String,
+    #[serde(default)]
+    created: String,
+    #[serde(default)]
+    architecture: String,
+    #[serde(default)]
+    os: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    repo_digests: Vec<String>,
+    #[serde(default)]
+    config: ImageConfig,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase")]
+struct ImageConfig {
+    #[serde(default)]
+    entrypoint: Option<Vec<String>>,
+    #[serde(default)]
+    cmd: Option<Vec<String>>,
+    #[serde(default)]
+    exposed_ports: Option<BTreeMap<String, serde_json::Value>>,
+    #[serde(default)]
+    labels: Option<BTreeMap<String, String>>,
+}
+
+/// One layer from `docker history`, newest first.
+#[derive(Clone)]
+pub struct ImageLayer {
+    pub created_by: String,
+    pub size: String,
+    pub created_since: String,
+}
+
+#[derive(Clone)]
+pub struct ImageDetails {
+    pub id: String,
+    pub created: String,
+    pub architecture: String,
+    pub os: String,
+    pub size: u64,
+    pub digests: Vec<String>,
+    pub entrypoint: Vec<String>,
+    pub cmd: Vec<String>,
+    pub exposed_ports: Vec<String>,
+    pub labels: BTreeMap<String, String>,
+    pub history: Vec<ImageLayer>,
+}
+
+/// Inspects an image in the background.
+pub struct ImageInspectTask {
+    pub id: String,
+    rx: Receiver<Result<ImageDetails, String>>,
+}
+
+impl ImageInspectTask {
+    pub fn spawn(id: &str) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let image = id.to_string();
+        hosts::spawn(move || {
+            let _ = tx.send(inspect_image(&image));
+        });
+        ImageInspectTask {
+            id: id.to_string(),
+            rx,
+        }
+    }
+
+    pub fn poll(&mut self) -> Option<Result<ImageDetails, String>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Finds the containers of every image in the background; that takes an
+/// inspect of every container.
+pub struct ImageUsersTask {
+    rx: Receiver<HashMap<String, Vec<String>>>,
+}
+
+impl ImageUsersTask {
+    pub fn spawn() -> Self {
+        let (tx, rx) = mpsc::channel();
+        hosts::spawn(move || {
+            let _ = tx.send(image_users());
+        });
+        ImageUsersTask { rx }
+    }
+
+    pub fn poll(&mut self) -> Option<HashMap<String, Vec<String>>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Collects `docker image inspect` and `docker history`.
+fn inspect_image(id: &str) -> Result<ImageDetails, String> {
+    let inspect: Vec<ImageInspect> =
+        serde_json::from_slice(&docker_output(&["image", "inspect", id])?)
+            .map_err(|e| format!("unexpected inspect output: {}", e))?;
+    let image = inspect
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("image {} not found", id))?;
+
+    let history = String::from_utf8_lossy(&docker_output(&[
+        "history",
+        "--no-trunc",
+        "--format",
+        "{{.CreatedBy}}|{{.Size}}|{{.CreatedSince}}",
+        id,
+    ])?)
+    .lines()
+    .filter_map(|line| {
+        // The command itself may contain '|', so split from the right.
+        let mut parts = line.rsplitn(3, '|');
+        let created_since = parts.next()?.to_string();
+        let size = parts.next()?.to_string();
+        let created_by = parts.next()?.to_string();
+        Some(ImageLayer {
+            created_by: clean_created_by(&created_by),
+            size,
+            created_since,
+        })
+    })
+    .collect();
+
+    let config = image.config;
+    Ok(ImageDetails {
+        id: image.id,
+        created: image.created,
+        architecture: image.architecture,
+        os: image.os,
+        size: image.size,
+        digests: image.repo_digests,
+        entrypoint: config.entrypoint.unwrap_or_default(),
+        cmd: config.cmd.unwrap_or_default(),
+        exposed_ports: config
+            .exposed_ports
+            .unwrap_or_default()
+            .into_keys()
+            .collect(),
+        labels: config.labels.unwrap_or_default(),
+        history,
+    })
+}
+
+/// The containers, running or not, created from each image, keyed by the
+/// image's full ID without `sha256:`. Unlike `ps --filter ancestor=`, this
+/// leaves out containers of images built on top of it.
+fn image_users() -> HashMap<String, Vec<String>> {
+    let Ok(ids) = docker_output(&["ps", "-aq"]) else {
+        return HashMap::new();
+    };
+    let ids: Vec<String> = String::from_utf8_lossy(&ids)
+        .split_whitespace()
+        .map(|id| id.to_string())
+        .collect();
+    if ids.is_empty() {
+        return HashMap::new();
+    }
+
+    let mut args = vec!["inspect", "--format", "{{.Name}}|{{.Image}}"];
+    args.extend(ids.iter().map(|id| id.as_str()));
+    docker_output(&args)
+        .map(|output| parse_image_users(&String::from_utf8_lossy(&output)))
+        .unwrap_or_default()
+}
+
+fn parse_image_users(output: &str) -> HashMap<String, Vec<String>> {
+    let mut users: HashMap<String, Vec<String>> = HashMap::new();
+    for line in output.lines() {
+        let Some((name, image)) = line.split_once('|') else {
+            continue;
+        };
+        users
+            .entry(image.trim().trim_start_matches("sha256:").to_string())
+            .or_default()
+            .push(name.trim().trim_start_matches('/').to_string());
+    }
+    users
+}
+
+/// Strips the `/bin/sh -c #(nop)` noise that the classic builder puts in history.
+fn clean_created_by(created_by: &str) -> String {
+    let command = created_by
+        .trim()
+        .trim_start_matches("/bin/sh -c ")
+        .trim_start_matches("#(nop) ")
+        .trim();
+    command.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn docker_output(args: &[&str]) -> Result<Vec<u8>, String> {
+    let output = cli()
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run docker: {}", e))?;
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_containers_by_exact_image_id() {
+        let users = parse_image_users(
+            "/web|sha256:aaa111\n/worker|sha256:aaa111\n/child|sha256:bbb222\npodman-app|ccc333\n\n",
+        );
+        assert_eq!(users["aaa111"], ["web", "worker"]);
+        assert_eq!(users["bbb222"], ["child"]);
+        assert_eq!(users["ccc333"], ["podman-app"]);
+        assert_eq!(users.len(), 3);
+    }
+}
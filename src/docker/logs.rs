@@ -0,0 +1,73 @@
+use super::runtime::cli;
+use std::{
+    io::{BufRead, BufReader, Read},
+    process::{Child, Stdio},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+};
+
+/// Follows the output of a `docker logs -f` or `docker compose logs -f` process.
+/// The process is killed when the stream is dropped.
+pub struct CommandLogStream {
+    rx: Receiver<String>,
+    child: Option<Child>,
+}
+
+impl CommandLogStream {
+    pub fn spawn(args: Vec<String>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let child = cli()
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn();
+
+        let child = match child {
+            Ok(mut child) => {
+                // Containers write to both; `docker logs` passes them through as-is.
+                if let Some(stdout) = child.stdout.take() {
+                    forward_lines(stdout, tx.clone(), true);
+                }
+                if let Some(stderr) = child.stderr.take() {
+                    forward_lines(stderr, tx, false);
+                }
+                Some(child)
+            }
+            Err(e) => {
+                let _ = tx.send(format!("<error: failed to run docker: {}>", e));
+                None
+            }
+        };
+
+        CommandLogStream { rx, child }
+    }
+
+    /// Returns the lines received since the last call.
+    pub fn poll(&mut self) -> Vec<String> {
+        self.rx.try_iter().collect()
+    }
+}
+
+fn forward_lines(reader: impl Read + Send + 'static, tx: Sender<String>, report_end: bool) {
+    thread::spawn(move || {
+        for line in BufReader::new(reader).lines() {
+            let Ok(line) = line else { break };
+            if tx.send(line).is_err() {
+                return;
+            }
+        }
+        if report_end {
+            let _ = tx.send("<log stream ended>".to_string());
+        }
+    });
+}
+
+impl Drop for CommandLogStream {
+    fn drop(&mut self) {
+        if let Some(child) = self.child.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
@@ -0,0 +1,37 @@
+mod compose;
+mod create;
+mod disk;
+mod engine;
+mod events;
+mod files;
+mod health;
+mod hosts;
+mod images;
+mod logs;
+mod networks;
+mod pull;
+mod runtime;
+mod task;
+mod top;
+mod update;
+mod volumes;
+
+pub use compose::{compose_args, parse_size, ComposeInfo};
+pub use create::{is_valid_container_name, shell_quote, RunOptions, RESTART_POLICIES};
+pub use disk::{DiskUsage, DiskUsageTask};
+pub use events::{DockerEvent, EventStream};
+pub use files::{host_path, join_path, list_dir, parent_path, FileEntry, Transfer};
+pub use health::{container_states, ContainerHealth, ContainerState, HealthTask};
+pub use hosts::{
+    active_endpoint_name, list_contexts, on_endpoint, override_endpoint, select_endpoint, Endpoint,
+    EndpointGuard,
+};
+pub use images::{ImageDetails, ImageInspectTask, ImageUsersTask};
+pub use logs::CommandLogStream;
+pub use networks::{list_networks, DockerNetwork};
+pub use pull::ImagePull;
+pub use runtime::{cli, containerd_namespace, next_containerd_namespace, Prune, Runtime};
+pub use task::DockerTask;
+pub use top::{container_top, signal_process, ContainerProcess};
+pub use update::{inspect_limits, ContainerLimits};
+pub use volumes::{DockerVolume, VolumeListTask};
@@ -0,0 +1,131 @@
+use super::runtime::cli;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Networks that Docker (or Podman, for `podman`) creates itself and refuses to remove.
+const BUILT_IN_NETWORKS: [&str; 4] = ["bridge", "host", "none", "podman"];
+
+/// Docker's inspect format; the lowercase aliases and `subnets` are Podman's,
+/// which has no scope and does not list attached containers.
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct NetworkInspect {
+    #[serde(alias = "id")]
+    id: String,
+    #[serde(alias = "name")]
+    name: String,
+    #[serde(alias = "driver")]
+    driver: String,
+    #[serde(default = "local_scope")]
+    scope: String,
+    #[serde(alias = "internal", default)]
+    internal: bool,
+    #[serde(rename = "IPAM", default)]
+    ipam: Ipam,
+    #[serde(rename = "subnets", default)]
+    subnets: Vec<IpamConfig>,
+    #[serde(default)]
+    containers: Option<BTreeMap<String, AttachedContainer>>,
+}
+
+fn local_scope() -> String {
+    "local".to_string()
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase")]
+struct Ipam {
+    #[serde(default)]
+    config: Option<Vec<IpamConfig>>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct IpamConfig {
+    #[serde(alias = "subnet", default)]
+    subnet: String,
+    #[serde(alias = "gateway", default)]
+    gateway: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct AttachedContainer {
+    name: String,
+    #[serde(rename = "IPv4Address", default)]
+    ipv4_address: String,
+}
+
+#[derive(Clone)]
+pub struct DockerNetwork {
+    pub id: String,
+    pub name: String,
+    pub driver: String,
+    pub scope: String,
+    pub internal: bool,
+    pub subnets: Vec<String>,
+    pub gateways: Vec<String>,
+    /// `(container name, IPv4 address)` of the running containers attached to it.
+    pub containers: Vec<(String, String)>,
+}
+
+impl DockerNetwork {
+    pub fn is_built_in(&self) -> bool {
+        BUILT_IN_NETWORKS.contains(&self.name.as_str())
+    }
+}
+
+/// Lists networks with their subnets and attached containers via `docker network inspect`.
+pub fn list_networks() -> Vec<DockerNetwork> {
+    let ids = match cli().args(["network", "ls", "-q", "--no-trunc"]).output() {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>(),
+        _ => return Vec::new(),
+    };
+    if ids.is_empty() {
+        return Vec::new();
+    }
+
+    let output = match cli().args(["network", "inspect"]).args(&ids).output() {
+        Ok(output) if output.status.success() => output.stdout,
+        _ => return Vec::new(),
+    };
+    let inspected: Vec<NetworkInspect> = serde_json::from_slice(&output).unwrap_or_default();
+
+    let mut networks: Vec<DockerNetwork> = inspected
+        .into_iter()
+        .map(|network| {
+            let mut config = network.ipam.config.unwrap_or_default();
+            config.extend(network.subnets);
+            let mut containers: Vec<(String, String)> = network
+                .containers
+                .unwrap_or_default()
+                .into_values()
+                .map(|c| (c.name, c.ipv4_address))
+                .collect();
+            containers.sort();
+            DockerNetwork {
+                id: network.id,
+                name: network.name,
+                driver: network.driver,
+                scope: network.scope,
+                internal: network.internal,
+                subnets: config
+                    .iter()
+                    .filter(|c| !c.subnet.is_empty())
+                    .map(|c| c.subnet.clone())
+                    .collect(),
+                gateways: config
+                    .iter()
+                    .filter(|c| !c.gateway.is_empty())
+                    .map(|c| c.gateway.clone())
+                    .collect(),
+                containers,
+            }
+        })
+        .collect();
+    networks.sort_by(|a, b| a.name.cmp(&b.name));
+    networks
+}
@@ -0,0 +1,253 @@
+use super::engine::{encode_query, engine_stream};
+use super::hosts;
+use base64::Engine;
+use serde::Deserialize;
+use serde_json::json;
+use std::{
+    fs,
+    io::BufRead,
+    sync::mpsc::{self, Receiver},
+};
+
+const DOCKER_HUB: &str = "https://index.docker.io/v1/";
+
+/// One line of the `POST /images/create` progress stream.
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct PullMessage {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    status: String,
+    #[serde(default)]
+    progress_detail: ProgressDetail,
+    error: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+struct ProgressDetail {
+    current: Option<u64>,
+    total: Option<u64>,
+}
+
+#[derive(Clone)]
+pub struct LayerProgress {
+    pub id: String,
+    pub status: String,
+    pub downloaded: u64,
+    pub size: u64,
+    pub extracted: u64,
+    pub extract_total: u64,
+}
+
+impl LayerProgress {
+    /// Progress of the current phase, from 0 to 1.
+    pub fn ratio(&self) -> f64 {
+        match self.status.as_str() {
+            "Pull complete" | "Already exists" | "Download complete" | "Verifying Checksum" => 1.0,
+            "Extracting" if self.extract_total > 0 => {
+                self.extracted as f64 / self.extract_total as f64
+            }
+            "Downloading" if self.size > 0 => self.downloaded as f64 / self.size as f64,
+            _ => 0.0,
+        }
+    }
+}
+
+/// An image pull running in the background, like `docker pull` but with the
+/// per-layer byte counts the daemon reports.
+pub struct ImagePull {
+    pub reference: String,
+    pub layers: Vec<LayerProgress>,
+    pub status: String,
+    pub digest: Option<String>,
+    pub error: Option<String>,
+    pub finished: bool,
+    rx: Receiver<Result<PullMessage, String>>,
+}
+
+impl ImagePull {
+    pub fn spawn(reference: &str) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let path = pull_path(reference);
+        let auth = registry_auth(reference);
+        hosts::spawn(move || {
+            let headers: Vec<(&str, String)> = auth
+                .map(|auth| vec![("X-Registry-Auth", auth)])
+                .unwrap_or_default();
+            let reader = match engine_stream("POST", &path, &headers) {
+                Ok(reader) => reader,
+                Err(e) => {
+                    let _ = tx.send(Err(e));
+                    return;
+                }
+            };
+            for line in reader.lines() {
+                let Ok(line) = line else { break };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let message = serde_json::from_str(&line).map_err(|e| e.to_string());
+                if tx.send(message).is_err() {
+                    return;
+                }
+            }
+        });
+
+        ImagePull {
+            reference: reference.to_string(),
+            layers: Vec::new(),
+            status: "Connecting...".to_string(),
+            digest: None,
+            error: None,
+            finished: false,
+            rx,
+        }
+    }
+
+    /// Applies queued progress. Returns true when the pull has just finished.
+    pub fn poll(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        loop {
+            match self.rx.try_recv() {
+                Ok(Ok(message)) => self.apply(message),
+                Ok(Err(error)) => self.error = Some(error),
+                Err(mpsc::TryRecvError::Empty) => return false,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    self.finished = true;
+                    return true;
+                }
+            }
+        }
+    }
+
+    fn apply(&mut self, message: PullMessage) {
+        if let Some(error) = message.error {
+            self.error = Some(error);
+            return;
+        }
+        if let Some(digest) = message.status.strip_prefix("Digest: ") {
+            self.digest = Some(digest.to_string());
+            return;
+        }
+        // Layer lines carry the short layer ID; the rest describe the whole pull.
+        if message.id.is_empty() || message.status.starts_with("Pulling from") {
+            self.status = message.status;
+            return;
+        }
+
+        let index = match self.layers.iter().position(|l| l.id == message.id) {
+            Some(index) => index,
+            None => {
+                self.layers.push(LayerProgress {
+                    id: message.id.clone(),
+                    status: String::new(),
+                    downloaded: 0,
+                    size: 0,
+                    extracted: 0,
+                    extract_total: 0,
+                });
+                self.layers.len() - 1
+            }
+        };
+        let layer = &mut self.layers[index];
+        let detail = &message.progress_detail;
+        match message.status.as_str() {
+            "Downloading" => {
+                layer.downloaded = detail.current.unwrap_or(layer.downloaded);
+                layer.size = detail.total.unwrap_or(layer.size);
+            }
+            "Extracting" => {
+                layer.extracted = detail.current.unwrap_or(layer.extracted);
+                layer.extract_total = detail.total.unwrap_or(layer.extract_total);
+            }
+            "Download complete" => layer.downloaded = layer.size,
+            _ => {}
+        }
+        layer.status = message.status;
+    }
+
+    /// Bytes downloaded and the total size of the layers seen so far.
+    pub fn totals(&self) -> (u64, u64) {
+        self.layers.iter().fold((0, 0), |(done, total), l| {
+            (done + l.downloaded, total + l.size)
+        })
+    }
+}
+
+/// `/images/create` query for a reference such as `nginx`, `nginx:1.27`,
+/// `localhost:5000/app:dev` or `alpine@sha256:...`.
+fn pull_path(reference: &str) -> String {
+    let (image, tag) = split_reference(reference);
+    let mut path = format!("/images/create?fromImage={}", encode_query(image));
+    if let Some(tag) = tag {
+        path.push_str(&format!("&tag={}", encode_query(tag)));
+    }
+    path
+}
+
+fn split_reference(reference: &str) -> (&str, Option<&str>) {
+    if reference.contains('@') {
+        return (reference, None);
+    }
+    let last_slash = reference.rfind('/').map(|i| i + 1).unwrap_or(0);
+    match reference[last_slash..].rfind(':') {
+        Some(i) => (
+            &reference[..last_slash + i],
+            Some(&reference[last_slash + i + 1..]),
+        ),
+        None => (reference, Some("latest")),
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct DockerConfig {
+    #[serde(default)]
+    auths: std::collections::HashMap<String, AuthEntry>,
+}
+
+#[derive(Deserialize, Default)]
+struct AuthEntry {
+    auth: Option<String>,
+}
+
+/// Credentials from `~/.docker/config.json` for the reference's registry, encoded as
+/// `X-Registry-Auth`. Credential helpers are not consulted, so those pulls go anonymous.
+fn registry_auth(reference: &str) -> Option<String> {
+    let first = reference.split('/').next().unwrap_or_default();
+    let registry = if reference.contains('/')
+        && (first.contains('.') || first.contains(':') || first == "localhost")
+    {
+        first.to_string()
+    } else {
+        DOCKER_HUB.to_string()
+    };
+
+    let config: DockerConfig =
+        serde_json::from_str(&fs::read_to_string(hosts::config_dir()?.join("config.json")).ok()?)
+            .ok()?;
+    // Keys look like `https://index.docker.io/v1/` or `registry.example.com:5000`.
+    let host = |address: &str| {
+        let address = address
+            .trim_start_matches("https://")
+            .trim_start_matches("http://");
+        address.split('/').next().unwrap_or_default().to_string()
+    };
+    let entry = config
+        .auths
+        .iter()
+        .find(|(key, _)| host(key) == host(&registry))?;
+    let decoded = base64::engine::general_purpose::STANDARD
+        .decode(entry.1.auth.as_ref()?)
+        .ok()?;
+    let (username, password) = std::str::from_utf8(&decoded).ok()?.split_once(':')?;
+
+    let auth = json!({
+        "username": username,
+        "password": password,
+        "serveraddress": registry,
+    });
+    Some(base64::engine::general_purpose::URL_SAFE.encode(auth.to_string()))
+}
@@ -0,0 +1,253 @@
+use super::hosts::selected_endpoint;
+use std::{
+    env,
+    path::Path,
+    process::Command,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        OnceLock,
+    },
+};
+
+const DOCKER_SOCKET: &str = "/var/run/docker.sock";
+const PODMAN_ROOT_SOCKET: &str = "/run/podman/podman.sock";
+const CONTAINERD_SOCKET: &str = "/run/containerd/containerd.sock";
+
+/// The containerd namespaces of Kubernetes (CRI) and of plain `nerdctl`/`ctr`.
+pub const CONTAINERD_NAMESPACES: [&str; 2] = ["k8s.io", "default"];
+
+/// The container engine behind the Docker tab. All three speak the Docker
+/// CLI, so only output formats and a few commands differ; containerd has no
+/// Engine API.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Runtime {
+    Docker,
+    Podman,
+    /// containerd through `nerdctl`, one namespace at a time.
+    Nerdctl,
+}
+
+/// What a prune removes. The disk usage categories map onto these, plus
+/// networks from the Networks view.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Prune {
+    DanglingImages,
+    UnusedImages,
+    StoppedContainers,
+    Networks,
+    /// Every unused volume, named ones included.
+    Volumes,
+    BuildCache,
+}
+
+impl Prune {
+    fn noun(self) -> &'static str {
+        match self {
+            Prune::DanglingImages | Prune::UnusedImages => "images",
+            Prune::StoppedContainers => "containers",
+            Prune::Networks => "networks",
+            Prune::Volumes => "volumes",
+            Prune::BuildCache => "build cache",
+        }
+    }
+}
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+static NAMESPACE: AtomicUsize = AtomicUsize::new(0);
+
+impl Runtime {
+    /// `CONTAINER_RUNTIME=docker|podman|nerdctl`, or detected on first use.
+    pub fn current() -> Runtime {
+        *RUNTIME.get_or_init(detect)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Runtime::Docker => "Docker",
+            Runtime::Podman => "Podman",
+            Runtime::Nerdctl => "containerd",
+        }
+    }
+
+    /// The CLI arguments for `prune`, or why this runtime cannot do it:
+    /// Docker needs `-a` to remove named volumes, Podman's `volume prune`
+    /// already does and it has no `builder prune`, and nerdctl has neither.
+    pub fn prune_args(self, prune: Prune) -> Result<Vec<&'static str>, String> {
+        let args = match (prune, self) {
+            (Prune::DanglingImages, _) => vec!["image", "prune", "-f"],
+            (Prune::UnusedImages, _) => vec!["image", "prune", "-a", "-f"],
+            (Prune::StoppedContainers, _) => vec!["container", "prune", "-f"],
+            (Prune::Networks, _) => vec!["network", "prune", "-f"],
+            (Prune::Volumes, Runtime::Docker) => vec!["volume", "prune", "-a", "-f"],
+            (Prune::Volumes, Runtime::Podman) => vec!["volume", "prune", "-f"],
+            (Prune::BuildCache, Runtime::Docker) => vec!["builder", "prune", "-a", "-f"],
+            (Prune::Volumes | Prune::BuildCache, _) => {
+                return Err(format!(
+                    "{} cannot prune {} from here",
+                    self.name(),
+                    prune.noun()
+                ))
+            }
+        };
+        Ok(args)
+    }
+
+    fn program(self) -> &'static str {
+        match self {
+            Runtime::Docker => "docker",
+            Runtime::Podman => "podman",
+            Runtime::Nerdctl => "nerdctl",
+        }
+    }
+}
+
+/// Prefers Docker, unless `docker` is Podman's compatibility wrapper, or only
+/// `podman` is installed, or a Kubernetes node runs containerd without a
+/// Docker daemon.
+fn detect() -> Runtime {
+    match env::var("CONTAINER_RUNTIME").as_deref() {
+        Ok("docker") => return Runtime::Docker,
+        Ok("podman") => return Runtime::Podman,
+        Ok("nerdctl" | "containerd") => return Runtime::Nerdctl,
+        _ => {}
+    }
+    if env::var_os("DOCKER_HOST").is_some() {
+        return Runtime::Docker;
+    }
+    if on_path("nerdctl")
+        && Path::new(CONTAINERD_SOCKET).exists()
+        && !Path::new(DOCKER_SOCKET).exists()
+    {
+        return Runtime::Nerdctl;
+    }
+    if on_path("docker") {
+        let is_podman = Command::new("docker")
+            .arg("--version")
+            .output()
+            .is_ok_and(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .to_lowercase()
+                    .contains("podman")
+            });
+        return if is_podman {
+            Runtime::Podman
+        } else {
+            Runtime::Docker
+        };
+    }
+    if on_path("podman") {
+        Runtime::Podman
+    } else if on_path("nerdctl") {
+        Runtime::Nerdctl
+    } else {
+        Runtime::Docker
+    }
+}
+
+fn on_path(program: &str) -> bool {
+    env::var_os("PATH")
+        .is_some_and(|paths| env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+}
+
+/// The runtime's CLI. The Docker CLI targets the selected host, or without
+/// `DOCKER_HOST` is pointed at Podman's socket when that is the only one
+/// present; `nerdctl` gets the selected namespace.
+pub fn cli() -> Command {
+    let runtime = Runtime::current();
+    let mut command = Command::new(runtime.program());
+    match runtime {
+        Runtime::Docker => {
+            if let Some(endpoint) = selected_endpoint() {
+                endpoint.configure(&mut command);
+            } else if env::var_os("DOCKER_HOST").is_none() {
+                if let Ok(host) = engine_host() {
+                    if host != format!("unix://{}", DOCKER_SOCKET) {
+                        command.env("DOCKER_HOST", host);
+                    }
+                }
+            }
+        }
+        Runtime::Nerdctl => {
+            command.args(["--namespace", containerd_namespace()]);
+        }
+        Runtime::Podman => {}
+    }
+    command
+}
+
+pub fn containerd_namespace() -> &'static str {
+    CONTAINERD_NAMESPACES[NAMESPACE.load(Ordering::Relaxed)]
+}
+
+/// Switches `nerdctl` to the other namespace and returns it.
+pub fn next_containerd_namespace() -> &'static str {
+    let next = (NAMESPACE.load(Ordering::Relaxed) + 1) % CONTAINERD_NAMESPACES.len();
+    NAMESPACE.store(next, Ordering::Relaxed);
+    CONTAINERD_NAMESPACES[next]
+}
+
+/// Where the Engine API listens, as a `unix://` or `tcp://` address:
+/// `DOCKER_HOST` (or Podman's `CONTAINER_HOST`), else the first socket found.
+pub fn engine_host() -> Result<String, String> {
+    let runtime = Runtime::current();
+    if runtime == Runtime::Nerdctl {
+        return Err("containerd has no Docker Engine API; this needs Docker or Podman".to_string());
+    }
+    let configured = env::var("DOCKER_HOST").ok().or_else(|| {
+        (runtime == Runtime::Podman)
+            .then(|| env::var("CONTAINER_HOST").ok())
+            .flatten()
+    });
+    if let Some(host) = configured.filter(|host| !host.is_empty()) {
+        return Ok(host);
+    }
+    let podman_user_socket = env::var("XDG_RUNTIME_DIR")
+        .ok()
+        .map(|dir| format!("{}/podman/podman.sock", dir));
+    let candidates: Vec<String> = match runtime {
+        Runtime::Podman => vec![podman_user_socket, Some(PODMAN_ROOT_SOCKET.to_string())],
+        _ => vec![Some(DOCKER_SOCKET.to_string()), podman_user_socket],
+    }
+    .into_iter()
+    .flatten()
+    .collect();
+    let socket = candidates
+        .iter()
+        .find(|socket| Path::new(socket).exists())
+        .unwrap_or(&candidates[0]);
+    Ok(format!("unix://{}", socket))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prunes_only_what_the_runtime_supports() {
+        assert_eq!(
+            Runtime::Docker.prune_args(Prune::Volumes),
+            Ok(vec!["volume", "prune", "-a", "-f"])
+        );
+        assert_eq!(
+            Runtime::Podman.prune_args(Prune::Volumes),
+            Ok(vec!["volume", "prune", "-f"])
+        );
+        assert_eq!(
+            Runtime::Podman.prune_args(Prune::BuildCache),
+            Err("Podman cannot prune build cache from here".to_string())
+        );
+        assert_eq!(
+            Runtime::Nerdctl.prune_args(Prune::Volumes),
+            Err("containerd cannot prune volumes from here".to_string())
+        );
+        assert!(Runtime::Nerdctl.prune_args(Prune::BuildCache).is_err());
+        for runtime in [Runtime::Docker, Runtime::Podman, Runtime::Nerdctl] {
+            assert_eq!(
+                runtime.prune_args(Prune::UnusedImages),
+                Ok(vec!["image", "prune", "-a", "-f"])
+            );
+            assert!(runtime.prune_args(Prune::StoppedContainers).is_ok());
+            assert!(runtime.prune_args(Prune::Networks).is_ok());
+        }
+    }
+}
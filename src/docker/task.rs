@@ -0,0 +1,65 @@
+use super::hosts;
+use super::runtime::cli;
+use std::{
+    process::Stdio,
+    sync::mpsc::{self, Receiver},
+};
+
+/// A Docker CLI command running in the background, so slow operations such as
+/// `docker compose pull` do not freeze the UI.
+pub struct DockerTask {
+    rx: Receiver<String>,
+}
+
+impl DockerTask {
+    /// Runs `docker` commands in order, stopping at the first failure, and reports
+    /// `label` with the outcome when they finish.
+    pub fn spawn(label: String, steps: Vec<Vec<String>>) -> Self {
+        Self::spawn_with(label, move || {
+            steps
+                .iter()
+                .find_map(|args| run_step(args).err())
+                .map_or(Ok(()), Err)
+        })
+    }
+
+    /// Like `spawn`, for work that takes more than running commands in order.
+    pub fn spawn_with(
+        label: String,
+        work: impl FnOnce() -> Result<(), String> + Send + 'static,
+    ) -> Self {
+        let (tx, rx) = mpsc::channel();
+        hosts::spawn(move || {
+            let message = match work() {
+                Ok(()) => format!("{}: done", label),
+                Err(reason) => format!("{}: failed: {}", label, reason),
+            };
+            let _ = tx.send(message);
+        });
+        DockerTask { rx }
+    }
+
+    /// Returns the result message once the command has finished.
+    pub fn poll(&mut self) -> Option<String> {
+        self.rx.try_recv().ok()
+    }
+}
+
+fn run_step(args: &[String]) -> Result<(), String> {
+    let output = cli()
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("failed to run docker: {}", e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    Err(stderr
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("unknown error")
+        .trim()
+        .to_string())
+}
@@ -0,0 +1,176 @@
+use super::runtime::{cli, Runtime};
+use std::fs;
+
+/// One line of `docker top`. The PID is as seen by the host, not the container.
+pub struct ContainerProcess {
+    pub pid: u32,
+    pub user: String,
+    pub cpu: f64,
+    /// Podman's `top` has no memory descriptor.
+    pub memory: Option<f64>,
+    pub command: String,
+}
+
+pub fn container_top(container: &str) -> Result<Vec<ContainerProcess>, String> {
+    // Podman takes AIX descriptors, where `hpid` is the host PID.
+    let (columns, fields): (&[&str], usize) = match Runtime::current() {
+        Runtime::Docker | Runtime::Nerdctl => (&["-eo", "pid,user,pcpu,pmem,args"], 4),
+        Runtime::Podman => (&["hpid", "user", "pcpu", "args"], 3),
+    };
+    let output = cli()
+        .arg("top")
+        .arg(container)
+        .args(columns)
+        .output()
+        .map_err(|e| format!("Failed to run docker top: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .skip(1)
+        .filter_map(|line| parse_process(line, fields))
+        .collect())
+}
+
+/// Splits off `count` columns; the command is the rest of the line.
+fn parse_process(line: &str, count: usize) -> Option<ContainerProcess> {
+    let mut rest = line.trim_start();
+    let mut fields = Vec::with_capacity(count);
+    for _ in 0..count {
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    Some(ContainerProcess {
+        pid: fields[0].parse().ok()?,
+        user: fields[1].to_string(),
+        cpu: fields[2].parse().unwrap_or(0.0),
+        memory: fields.get(3).map(|memory| memory.parse().unwrap_or(0.0)),
+        command: rest.to_string(),
+    })
+}
+
+/// Sends `signal` to a process listed by `docker top`, identified by its host PID.
+pub fn signal_process(container: &str, host_pid: u32, signal: &str) -> Result<(), String> {
+    let (id, main_pid) = inspect_main_pid(container)?;
+    // Inside its own namespace PID 1 only receives signals it installed a handler
+    // for, so the main process is signalled by the engine from outside with
+    // `kill --signal`, where SIGKILL and SIGSTOP always get through.
+    if host_pid == main_pid {
+        return run(&["kill", "--signal", signal, container]);
+    }
+
+    let container_pid = namespace_pid(host_pid, &id)?;
+    let output = cli()
+        .args(["exec", container, "kill", &format!("-{}", signal)])
+        .arg(container_pid.to_string())
+        .output()
+        .map_err(|e| format!("failed to run docker: {}", e))?;
+    if output.status.success() {
+        return Ok(());
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // 126 and 127 are the exit codes for a command that cannot be run or found.
+    if matches!(output.status.code(), Some(126 | 127))
+        || stderr.contains("executable file not found")
+    {
+        return Err(format!(
+            "unsupported: the container has no kill command, only its main process (PID {}) can be signalled",
+            main_pid
+        ));
+    }
+    Err(last_line(&stderr))
+}
+
+/// The full container ID and the host PID of its main process.
+fn inspect_main_pid(container: &str) -> Result<(String, u32), String> {
+    let output = cli()
+        .args(["inspect", "--format", "{{.Id}}|{{.State.Pid}}", container])
+        .output()
+        .map_err(|e| format!("failed to run docker: {}", e))?;
+    if !output.status.success() {
+        return Err(last_line(&String::from_utf8_lossy(&output.stderr)));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .trim()
+        .split_once('|')
+        .and_then(|(id, pid)| Some((id.to_string(), pid.parse().ok()?)))
+        .ok_or_else(|| format!("{} is not running", container))
+}
+
+fn run(args: &[&str]) -> Result<(), String> {
+    let output = cli()
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run docker: {}", e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(last_line(&String::from_utf8_lossy(&output.stderr)))
+    }
+}
+
+fn last_line(stderr: &str) -> String {
+    stderr
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .unwrap_or("unknown error")
+        .trim()
+        .to_string()
+}
+
+/// Translates a host PID from `docker top` into the container's PID namespace via
+/// this machine's /proc. That only describes the container when the engine runs
+/// here, not over tcp/ssh or in a VM, so the process must be in the container's cgroup.
+fn namespace_pid(host_pid: u32, container_id: &str) -> Result<u32, String> {
+    let unavailable = || {
+        format!(
+            "unsupported: PID {} is not on this machine; only the main process can be signalled on a remote or VM-based engine",
+            host_pid
+        )
+    };
+    let cgroup =
+        fs::read_to_string(format!("/proc/{}/cgroup", host_pid)).map_err(|_| unavailable())?;
+    let status =
+        fs::read_to_string(format!("/proc/{}/status", host_pid)).map_err(|_| unavailable())?;
+    parse_namespace_pid(&status, &cgroup, container_id).ok_or_else(unavailable)
+}
+
+/// The innermost `NSpid` of a process whose cgroup path names the container.
+fn parse_namespace_pid(status: &str, cgroup: &str, container_id: &str) -> Option<u32> {
+    if container_id.is_empty() || !cgroup.contains(container_id) {
+        return None;
+    }
+    status
+        .lines()
+        .find_map(|line| line.strip_prefix("NSpid:"))
+        .and_then(|pids| pids.split_whitespace().last())
+        .and_then(|pid| pid.parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "4f1c2a9e8b7d6c5f4e3d2c1b0a9f8e7d6c5b4a3f2e1d0c9b8a7f6e5d4c3b2a1f";
+
+    #[test]
+    fn maps_host_pids_of_the_container_only() {
+        let status = "Name:\tnginx\nPid:\t48213\nNSpid:\t48213\t29\nPPid:\t48190\n";
+        let docker = format!("0::/system.slice/docker-{}.scope\n", ID);
+        let podman = format!("0::/machine.slice/libpod-{}.scope/container\n", ID);
+
+        assert_eq!(parse_namespace_pid(status, &docker, ID), Some(29));
+        assert_eq!(parse_namespace_pid(status, &podman, ID), Some(29));
+        // A host process that happens to have the same PID as a remote container's.
+        assert_eq!(
+            parse_namespace_pid(status, "0::/user.slice/session-2.scope\n", ID),
+            None
+        );
+        assert_eq!(parse_namespace_pid("Name:\tsh\n", &docker, ID), None);
+        assert_eq!(parse_namespace_pid(status, &docker, ""), None);
+    }
+}
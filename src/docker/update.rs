@@ -0,0 +1,262 @@
+use super::runtime::cli;
+use serde::Deserialize;
+
+const DEFAULT_CPU_PERIOD: i64 = 100_000;
+pub(super) const MIN_MEMORY: i64 = 6 * 1024 * 1024;
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct HostConfig {
+    cpu_shares: i64,
+    cpu_period: i64,
+    cpu_quota: i64,
+    nano_cpus: i64,
+    memory: i64,
+    memory_swap: i64,
+    pids_limit: Option<i64>,
+    restart_policy: RestartPolicy,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "PascalCase", default)]
+struct RestartPolicy {
+    name: String,
+    maximum_retry_count: i64,
+}
+
+/// The limits `docker update` can change, as editable text. Empty means unlimited.
+#[derive(Clone, Default)]
+pub struct ContainerLimits {
+    pub cpu_shares: String,
+    /// Number of CPUs, e.g. "1.5".
+    pub cpus: String,
+    pub memory: String,
+    pub memory_swap: String,
+    pub pids_limit: String,
+    pub restart_policy: String,
+    /// The daemon refuses to mix `--cpus` with `--cpu-quota`, so keep whichever
+    /// the container was created with.
+    uses_cpu_quota: bool,
+    cpu_period: i64,
+}
+
+pub fn inspect_limits(id: &str) -> Result<ContainerLimits, String> {
+    let output = cli()
+        .args(["inspect", "--format", "{{json .HostConfig}}", id])
+        .output()
+        .map_err(|e| format!("failed to run docker: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    let config: HostConfig = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("unexpected inspect output: {}", e))?;
+
+    let cpu_period = if config.cpu_period > 0 {
+        config.cpu_period
+    } else {
+        DEFAULT_CPU_PERIOD
+    };
+    let cpus = if config.cpu_quota > 0 {
+        format_cpus(config.cpu_quota as f64 / cpu_period as f64)
+    } else if config.nano_cpus > 0 {
+        format_cpus(config.nano_cpus as f64 / 1e9)
+    } else {
+        String::new()
+    };
+    let restart_policy = match config.restart_policy.name.as_str() {
+        "" => "no".to_string(),
+        "on-failure" if config.restart_policy.maximum_retry_count > 0 => {
+            format!("on-failure:{}", config.restart_policy.maximum_retry_count)
+        }
+        name => name.to_string(),
+    };
+    Ok(ContainerLimits {
+        cpu_shares: positive(config.cpu_shares),
+        cpus,
+        memory: format_memory(config.memory),
+        memory_swap: match config.memory_swap {
+            -1 => "-1".to_string(),
+            swap => format_memory(swap),
+        },
+        pids_limit: positive(config.pids_limit.unwrap_or(0)),
+        restart_policy,
+        uses_cpu_quota: config.cpu_quota > 0,
+        cpu_period,
+    })
+}
+
+impl ContainerLimits {
+    /// Validates `edited` and builds `docker update` arguments for the fields that
+    /// differ from these current limits.
+    pub fn update_args(
+        &self,
+        edited: &ContainerLimits,
+        container: &str,
+    ) -> Result<Vec<String>, String> {
+        let mut args = vec!["update".to_string()];
+        let mut flag = |name: &str, value: String| {
+            args.push(name.to_string());
+            args.push(value);
+        };
+
+        let cpu_shares = edited.cpu_shares.trim();
+        if cpu_shares != self.cpu_shares {
+            match cpu_shares.parse::<i64>() {
+                Ok(shares) if (2..=262_144).contains(&shares) => {
+                    flag("--cpu-shares", shares.to_string())
+                }
+                _ => return Err("CPU shares must be a number from 2 to 262144".to_string()),
+            }
+        }
+
+        let cpus = edited.cpus.trim();
+        if cpus != self.cpus {
+            match (cpus.parse::<f64>(), self.uses_cpu_quota) {
+                (_, true) if cpus.is_empty() => flag("--cpu-quota", "-1".to_string()),
+                (Ok(cpus), true) if cpus > 0.0 => flag(
+                    "--cpu-quota",
+                    ((cpus * self.cpu_period as f64).round() as i64).to_string(),
+                ),
+                (Ok(cpus), false) if cpus > 0.0 => flag("--cpus", cpus.to_string()),
+                _ if cpus.is_empty() => {
+                    return Err(
+                        "docker update cannot remove a --cpus limit; enter a higher value"
+                            .to_string(),
+                    )
+                }
+                _ => return Err("CPUs must be a positive number, e.g. 0.5 or 2".to_string()),
+            }
+        }
+
+        let memory = edited.memory.trim();
+        let memory_bytes = parse_memory(memory);
+        if memory != self.memory {
+            if memory.is_empty() {
+                return Err(
+                    "docker update cannot remove a memory limit; enter a higher value".to_string(),
+                );
+            }
+            match memory_bytes {
+                Some(bytes) if bytes >= MIN_MEMORY => flag("--memory", memory.to_string()),
+                Some(_) => return Err("Memory limit must be at least 6m".to_string()),
+                None => return Err("Memory must look like 512m or 2g".to_string()),
+            }
+        }
+
+        // The existing swap limit also caps a new memory limit.
+        let swap = edited.memory_swap.trim();
+        if swap != self.memory_swap || memory != self.memory {
+            match (swap, memory_bytes) {
+                ("-1" | "", _) => {}
+                (_, None) => {
+                    return Err(
+                        "Memory + swap needs a memory limit; use -1 or leave it empty".to_string(),
+                    )
+                }
+                (_, Some(memory_bytes)) => match parse_memory(swap) {
+                    Some(swap_bytes) if swap_bytes >= memory_bytes => {}
+                    Some(_) => {
+                        return Err("Memory + swap must be at least the memory limit".to_string())
+                    }
+                    None => {
+                        return Err(
+                            "Memory + swap must look like 1g, or -1 for unlimited swap".to_string()
+                        )
+                    }
+                },
+            }
+        }
+        if swap != self.memory_swap {
+            if swap.is_empty() {
+                return Err("Use -1 to remove the swap limit".to_string());
+            }
+            flag("--memory-swap", swap.to_string());
+        }
+
+        let pids = edited.pids_limit.trim();
+        if pids != self.pids_limit {
+            match pids {
+                "" | "0" | "-1" => flag("--pids-limit", "-1".to_string()),
+                _ => match pids.parse::<i64>() {
+                    Ok(limit) if limit > 0 => flag("--pids-limit", limit.to_string()),
+                    _ => {
+                        return Err(
+                            "PIDs limit must be a positive number, or empty for unlimited"
+                                .to_string(),
+                        )
+                    }
+                },
+            }
+        }
+
+        let restart = edited.restart_policy.trim();
+        if restart != self.restart_policy {
+            let valid = match restart.split_once(':') {
+                Some(("on-failure", retries)) => retries.parse::<u32>().is_ok(),
+                Some(_) => false,
+                None => matches!(restart, "no" | "always" | "unless-stopped" | "on-failure"),
+            };
+            if !valid {
+                return Err(
+                    "Restart policy must be no, always, unless-stopped or on-failure[:N]"
+                        .to_string(),
+                );
+            }
+            flag("--restart", restart.to_string());
+        }
+
+        if args.len() == 1 {
+            return Err("Nothing changed".to_string());
+        }
+        args.push(container.to_string());
+        Ok(args)
+    }
+}
+
+fn positive(value: i64) -> String {
+    if value > 0 {
+        value.to_string()
+    } else {
+        String::new()
+    }
+}
+
+fn format_cpus(cpus: f64) -> String {
+    let text = format!("{:.2}", cpus);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+/// Formats bytes with the largest docker unit that divides them exactly.
+fn format_memory(bytes: i64) -> String {
+    if bytes <= 0 {
+        return String::new();
+    }
+    for (unit, size) in [("g", 1 << 30), ("m", 1 << 20), ("k", 1 << 10)] {
+        if bytes % size == 0 {
+            return format!("{}{}", bytes / size, unit);
+        }
+    }
+    bytes.to_string()
+}
+
+/// Parses docker's memory syntax: a number with an optional b, k, m or g suffix.
+pub(super) fn parse_memory(value: &str) -> Option<i64> {
+    let value = value.trim().to_ascii_lowercase();
+    // "512mb" is accepted as well as "512m".
+    let value = match value.strip_suffix('b') {
+        Some(rest) if rest.ends_with(['k', 'm', 'g']) => rest.to_string(),
+        _ => value,
+    };
+    let (number, multiplier) = match value.chars().last()? {
+        'b' => (&value[..value.len() - 1], 1),
+        'k' => (&value[..value.len() - 1], 1 << 10),
+        'm' => (&value[..value.len() - 1], 1 << 20),
+        'g' => (&value[..value.len() - 1], 1 << 30),
+        _ => (value.as_str(), 1),
+    };
+    let number: f64 = number.parse().ok()?;
+    if number <= 0.0 {
+        return None;
+    }
+    Some((number * multiplier as f64) as i64)
+}
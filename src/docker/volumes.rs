@@ -0,0 +1,188 @@
+use super::disk::detailed_usage;
+use super::hosts;
+use super::runtime::cli;
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    sync::mpsc::{self, Receiver},
+};
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct VolumeInspect {
+    name: String,
+    driver: String,
+    #[serde(default)]
+    mountpoint: String,
+    #[serde(default)]
+    created_at: String,
+}
+
+#[derive(Clone)]
+pub struct DockerVolume {
+    pub name: String,
+    pub driver: String,
+    pub mountpoint: String,
+    pub created: String,
+    /// Only known when `docker system df -v` reports it, e.g. not for remote drivers.
+    pub size: Option<String>,
+    /// All containers, running or not, that mount the volume.
+    pub containers: Vec<VolumeUser>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct VolumeUser {
+    pub name: String,
+    /// Anything but a container that has exited or never started, so also paused,
+    /// restarting and unknown states.
+    pub running: bool,
+}
+
+/// Lists volumes in the background; their sizes come from `docker system df -v`,
+/// which can take seconds.
+pub struct VolumeListTask {
+    rx: Receiver<Vec<DockerVolume>>,
+}
+
+impl VolumeListTask {
+    pub fn spawn() -> Self {
+        let (tx, rx) = mpsc::channel();
+        hosts::spawn(move || {
+            let _ = tx.send(list_volumes());
+        });
+        VolumeListTask { rx }
+    }
+
+    pub fn poll(&mut self) -> Option<Vec<DockerVolume>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Lists volumes with their size and the containers that mount them.
+fn list_volumes() -> Vec<DockerVolume> {
+    let names = match cli().args(["volume", "ls", "-q"]).output() {
+        Ok(output) if output.status.success() => String::from_utf8_lossy(&output.stdout)
+            .split_whitespace()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>(),
+        _ => return Vec::new(),
+    };
+    if names.is_empty() {
+        return Vec::new();
+    }
+
+    let output = match cli().args(["volume", "inspect"]).args(&names).output() {
+        Ok(output) if output.status.success() => output.stdout,
+        _ => return Vec::new(),
+    };
+    let inspected: Vec<VolumeInspect> = serde_json::from_slice(&output).unwrap_or_default();
+
+    let sizes = volume_sizes();
+    let users = volume_users();
+    let mut volumes: Vec<DockerVolume> = inspected
+        .into_iter()
+        .map(|volume| DockerVolume {
+            size: sizes.get(&volume.name).cloned(),
+            containers: users.get(&volume.name).cloned().unwrap_or_default(),
+            name: volume.name,
+            driver: volume.driver,
+            mountpoint: volume.mountpoint,
+            created: volume.created_at,
+        })
+        .collect();
+    volumes.sort_by(|a, b| a.name.cmp(&b.name));
+    volumes
+}
+
+fn volume_sizes() -> HashMap<String, String> {
+    detailed_usage()
+        .map(|usage| {
+            usage
+                .volumes
+                .into_iter()
+                // The daemon reports "N/A" when it could not measure the volume.
+                .filter(|v| !v.size.is_empty() && v.size != "N/A")
+                .map(|v| (v.name, v.size))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Maps volume names to the containers mounting them, from `docker ps --format {{.Mounts}}`.
+fn volume_users() -> HashMap<String, Vec<VolumeUser>> {
+    let output = cli()
+        .args([
+            "ps",
+            "-a",
+            "--no-trunc",
+            "--format",
+            "{{.Names}}|{{.State}}|{{.Mounts}}",
+        ])
+        .output();
+    match output {
+        Ok(output) => parse_volume_users(&String::from_utf8_lossy(&output.stdout)),
+        Err(_) => HashMap::new(),
+    }
+}
+
+fn parse_volume_users(output: &str) -> HashMap<String, Vec<VolumeUser>> {
+    let mut users: HashMap<String, Vec<VolumeUser>> = HashMap::new();
+    for line in output.lines() {
+        let mut parts = line.splitn(3, '|');
+        let (Some(container), Some(state), Some(mounts)) =
+            (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        let running = !matches!(state.trim(), "exited" | "created" | "dead");
+        for mount in mounts
+            .split(',')
+            .map(|m| m.trim())
+            .filter(|m| !m.is_empty())
+        {
+            users
+                .entry(mount.to_string())
+                .or_default()
+                .push(VolumeUser {
+                    name: container.to_string(),
+                    running,
+                });
+        }
+    }
+    users
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(name: &str, running: bool) -> VolumeUser {
+        VolumeUser {
+            name: name.to_string(),
+            running,
+        }
+    }
+
+    #[test]
+    fn parses_volume_users_with_their_state() {
+        let output = "web|running|data,cache\n\
+                      migrate|exited|data\n\
+                      worker|paused|cache\n\
+                      fresh|created|data\n\
+                      plain|running|\n\
+                      broken line\n";
+
+        let users = parse_volume_users(output);
+
+        assert_eq!(
+            users["data"],
+            [
+                user("web", true),
+                user("migrate", false),
+                user("fresh", false)
+            ]
+        );
+        assert_eq!(users["cache"], [user("web", true), user("worker", true)]);
+        assert_eq!(users.len(), 2);
+    }
+}
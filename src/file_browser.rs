@@ -0,0 +1,162 @@
+use crate::docker::{join_path, list_dir, parent_path, FileEntry};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+
+/// Full-screen browser over the filesystem of one container.
+pub struct FileBrowser {
+    pub container_id: String,
+    pub container_name: String,
+    pub path: String,
+    entries: Result<Vec<FileEntry>, String>,
+    list_state: TableState,
+}
+
+impl FileBrowser {
+    pub fn open(container_id: &str, container_name: &str) -> Self {
+        let mut browser = FileBrowser {
+            container_id: container_id.to_string(),
+            container_name: container_name.to_string(),
+            path: "/".to_string(),
+            entries: Ok(Vec::new()),
+            list_state: TableState::default(),
+        };
+        browser.refresh();
+        browser
+    }
+
+    pub fn refresh(&mut self) {
+        self.entries = list_dir(&self.container_id, &self.path);
+        let len = self.entries.as_ref().map_or(0, |entries| entries.len());
+        match self.list_state.selected() {
+            _ if len == 0 => self.list_state.select(None),
+            Some(selected) if selected < len => {}
+            _ => self.list_state.select(Some(0)),
+        }
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let len = self.entries.as_ref().map_or(0, |entries| entries.len());
+        if len == 0 {
+            return;
+        }
+        let selected = self.list_state.selected().unwrap_or(0) as isize;
+        self.list_state
+            .select(Some((selected + delta).clamp(0, len as isize - 1) as usize));
+    }
+
+    pub fn selected(&self) -> Option<&FileEntry> {
+        let entries = self.entries.as_ref().ok()?;
+        entries.get(self.list_state.selected()?)
+    }
+
+    pub fn selected_path(&self) -> Option<String> {
+        self.selected()
+            .map(|entry| join_path(&self.path, &entry.name))
+    }
+
+    /// Opens the selected directory or symlink. A symlink that turns out not to
+    /// be a directory leaves the listing as it was.
+    pub fn enter(&mut self) -> Result<(), String> {
+        let Some(entry) = self.selected() else {
+            return Ok(());
+        };
+        if !entry.is_dir() && entry.kind != 'l' {
+            return Ok(());
+        }
+        let path = join_path(&self.path, &entry.name);
+        let entries = list_dir(&self.container_id, &path)?;
+        self.path = path;
+        self.entries = Ok(entries);
+        self.list_state.select(Some(0));
+        Ok(())
+    }
+
+    /// Goes to the parent directory, selecting the one just left.
+    pub fn go_up(&mut self) {
+        if self.path == "/" {
+            return;
+        }
+        let left = self.path.rsplit('/').next().unwrap_or_default().to_string();
+        self.path = parent_path(&self.path);
+        self.refresh();
+        if let Ok(entries) = &self.entries {
+            let index = entries.iter().position(|entry| entry.name == left);
+            self.list_state.select(index.or(Some(0)));
+        }
+    }
+
+    pub fn render(&mut self, f: &mut ratatui::Frame, area: Rect) {
+        let title = format!(
+            "{}:{} (↑↓ Navigate | Enter Open | Backspace Up | D Download | U Upload here | R Refresh | C Clear finished | Esc Close)",
+            self.container_name, self.path
+        );
+        let block = Block::default().borders(Borders::ALL).title(title);
+        let entries = match &self.entries {
+            Ok(entries) => entries,
+            Err(e) => {
+                let error = Paragraph::new(format!(
+                    "Cannot list {}: {}\nBrowsing runs `ls` inside the container, so it must be running.",
+                    self.path, e
+                ))
+                .style(Style::default().fg(Color::Red))
+                .block(block);
+                f.render_widget(error, area);
+                return;
+            }
+        };
+
+        let rows: Vec<Row> = entries
+            .iter()
+            .map(|entry| {
+                let name = match &entry.link_target {
+                    Some(target) => format!("{} -> {}", entry.name, target),
+                    None if entry.is_dir() => format!("{}/", entry.name),
+                    None => entry.name.clone(),
+                };
+                let style = match entry.kind {
+                    'd' => Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                    'l' => Style::default().fg(Color::Magenta),
+                    '-' => Style::default(),
+                    _ => Style::default().fg(Color::DarkGray),
+                };
+                Row::new(vec![
+                    Cell::from(entry.mode.clone()),
+                    Cell::from(if entry.kind == '-' {
+                        entry.size.to_string()
+                    } else {
+                        String::new()
+                    }),
+                    Cell::from(entry.modified.clone()),
+                    Cell::from(name),
+                ])
+                .style(style)
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(11),
+                Constraint::Length(12),
+                Constraint::Length(13),
+                Constraint::Min(20),
+            ],
+        )
+        .header(
+            Row::new(vec!["Mode", "Size", "Modified", "Name"]).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol(">> ");
+        f.render_stateful_widget(table, area, &mut self.list_state);
+    }
+}
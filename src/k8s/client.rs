@@ -0,0 +1,892 @@
+use super::config::{ExecConfig, KubeConfig};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    client::WebPkiServerVerifier,
+    crypto::{ring, CryptoProvider},
+    pki_types::{CertificateDer, ServerName, UnixTime},
+    ClientConfig, ClientConnection, DigitallySignedStruct, RootCertStore, SignatureScheme,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    fs,
+    io::{self, BufRead, BufReader, ErrorKind, Read, Write},
+    net::{Shutdown, TcpStream, ToSocketAddrs},
+    process::{Command, Stdio},
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+use tungstenite::{client::IntoClientRequest, http::HeaderValue, WebSocket};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// A minimal synchronous client for the Kubernetes API server.
+pub struct KubeClient {
+    config: KubeConfig,
+    state: Mutex<ClientState>,
+}
+
+struct ClientState {
+    agent: ureq::Agent,
+    tls: Option<Arc<ClientConfig>>,
+    exec_credential: Option<ExecCredential>,
+}
+
+/// What a single request needs: the HTTP agent, TLS settings and the Authorization header.
+struct Session {
+    agent: ureq::Agent,
+    tls: Option<Arc<ClientConfig>>,
+    authorization: Option<String>,
+}
+
+/// The raw connection behind a WebSocket opened against the API server.
+/// Handles made with `try_clone` share it, so one thread can block reading
+/// while another writes; each write goes out whole, so frames never interleave.
+pub struct KubeStream {
+    tcp: TcpStream,
+    tls: Option<Arc<Mutex<ClientConnection>>>,
+    write_lock: Arc<Mutex<()>>,
+}
+
+impl KubeStream {
+    fn new(tcp: TcpStream, tls: Option<ClientConnection>) -> Self {
+        KubeStream {
+            tcp,
+            tls: tls.map(|connection| Arc::new(Mutex::new(connection))),
+            write_lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub fn try_clone(&self) -> io::Result<Self> {
+        Ok(KubeStream {
+            tcp: self.tcp.try_clone()?,
+            tls: self.tls.clone(),
+            write_lock: self.write_lock.clone(),
+        })
+    }
+
+    /// Closes the connection under every handle, waking a blocked reader.
+    pub fn shutdown(&self) {
+        let _ = self.tcp.shutdown(Shutdown::Both);
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> io::Result<MutexGuard<'_, T>> {
+    mutex
+        .lock()
+        .map_err(|_| io::Error::other("connection state poisoned"))
+}
+
+impl Read for KubeStream {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let Some(tls) = &self.tls else {
+            return (&self.tcp).read(buf);
+        };
+        let mut ciphertext = [0u8; 16 * 1024];
+        loop {
+            {
+                let mut connection = lock(tls)?;
+                match connection.reader().read(buf) {
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {}
+                    result => return result,
+                }
+                // Handshake messages the server waits for before sending more.
+                while connection.wants_write() {
+                    connection.write_tls(&mut &self.tcp)?;
+                }
+            }
+
+            // Wait for the server without holding the lock, so writes go on.
+            let n = (&self.tcp).read(&mut ciphertext)?;
+            let mut connection = lock(tls)?;
+            let mut data = &ciphertext[..n];
+            loop {
+                // An empty read tells rustls the server closed the connection.
+                connection.read_tls(&mut data)?;
+                connection.process_new_packets().map_err(io::Error::other)?;
+                if data.is_empty() {
+                    break;
+                }
+            }
+            if n == 0 {
+                return connection.reader().read(buf);
+            }
+        }
+    }
+}
+
+impl Write for KubeStream {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let _write = lock(&self.write_lock)?;
+        match &self.tls {
+            None => (&self.tcp).write_all(buf)?,
+            Some(tls) => {
+                let mut connection = lock(tls)?;
+                connection.writer().write_all(buf)?;
+                while connection.wants_write() {
+                    connection.write_tls(&mut &self.tcp)?;
+                }
+            }
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+struct ExecCredential {
+    token: Option<String>,
+    expires: Option<DateTime<Utc>>,
+    cert_pem: Option<Vec<u8>>,
+    key_pem: Option<Vec<u8>>,
+}
+
+#[derive(Deserialize)]
+struct ExecCredentialResponse {
+    status: Option<ExecCredentialStatus>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecCredentialStatus {
+    token: Option<String>,
+    expiration_timestamp: Option<DateTime<Utc>>,
+    client_certificate_data: Option<String>,
+    client_key_data: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiStatus {
+    message: Option<String>,
+    reason: Option<String>,
+}
+
+impl KubeClient {
+    pub fn new(config: KubeConfig) -> Result<Self, String> {
+        let exec_credential = match &config.exec {
+            Some(exec) => Some(run_exec_plugin(exec, &config)?),
+            None => None,
+        };
+        let (agent, tls) = build_agent(&config, exec_credential.as_ref())?;
+
+        Ok(KubeClient {
+            config,
+            state: Mutex::new(ClientState {
+                agent,
+                tls,
+                exec_credential,
+            }),
+        })
+    }
+
+    pub fn from_default_config() -> Result<Self, String> {
+        Self::new(KubeConfig::load()?)
+    }
+
+    pub fn config(&self) -> &KubeConfig {
+        &self.config
+    }
+
+    pub fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T, String> {
+        let response = self.call("GET", path, None, Some(REQUEST_TIMEOUT))?;
+        response
+            .into_json()
+            .map_err(|e| format!("Invalid response from {}: {}", path, e))
+    }
+
+    pub fn put_json(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        self.send_json("PUT", path, "application/json", body)
+    }
+
+    pub fn post_json(
+        &self,
+        path: &str,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        self.send_json("POST", path, "application/json", body)
+    }
+
+    /// Applies a JSON merge patch (RFC 7386) to an object.
+    pub fn merge_patch(
+        &self,
+        path: &str,
+        patch: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        self.send_json("PATCH", path, "application/merge-patch+json", patch)
+    }
+
+    fn send_json(
+        &self,
+        method: &str,
+        path: &str,
+        content_type: &str,
+        body: &serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let body = body.to_string();
+        let response = self.call(
+            method,
+            path,
+            Some((content_type, &body)),
+            Some(REQUEST_TIMEOUT),
+        )?;
+        response
+            .into_json()
+            .map_err(|e| format!("Invalid response from {}: {}", path, e))
+    }
+
+    pub fn delete(&self, path: &str) -> Result<(), String> {
+        self.call("DELETE", path, None, Some(REQUEST_TIMEOUT))
+            .map(|_| ())
+    }
+
+    /// Opens a streaming request (e.g. `?watch=1`) and returns a line reader over the body.
+    pub fn stream(&self, path: &str) -> Result<impl BufRead + Send, String> {
+        let response = self.call("GET", path, None, None)?;
+        Ok(BufReader::new(response.into_reader()))
+    }
+
+    /// Opens a WebSocket to a streaming subresource such as `pods/<name>/portforward`.
+    pub fn websocket(
+        &self,
+        path: &str,
+        protocols: &[&str],
+    ) -> Result<WebSocket<KubeStream>, String> {
+        let session = self.session()?;
+        let server = &self.config.server;
+        let (secure, rest) = match server.split_once("://") {
+            Some(("https", rest)) => (true, rest),
+            Some((_, rest)) => (false, rest),
+            None => (false, server.as_str()),
+        };
+        let authority = rest.split('/').next().unwrap_or(rest);
+        let (host, port) = split_host_port(authority, if secure { 443 } else { 80 });
+
+        let address = (host.as_str(), port)
+            .to_socket_addrs()
+            .map_err(|e| format!("Cannot resolve {}: {}", host, e))?
+            .next()
+            .ok_or_else(|| format!("Cannot resolve {}", host))?;
+        let tcp = TcpStream::connect_timeout(&address, Duration::from_secs(5))
+            .map_err(|e| format!("Cannot reach {}: {}", server, e))?;
+        tcp.set_nodelay(true).ok();
+
+        let stream = match (secure, session.tls) {
+            (true, Some(tls)) => {
+                let name = ServerName::try_from(host.clone())
+                    .map_err(|e| format!("Invalid server name '{}': {}", host, e))?;
+                let connection = ClientConnection::new(tls, name)
+                    .map_err(|e| format!("TLS setup failed: {}", e))?;
+                KubeStream::new(tcp, Some(connection))
+            }
+            _ => KubeStream::new(tcp, None),
+        };
+
+        let url = format!(
+            "{}{}",
+            server
+                .replacen("https://", "wss://", 1)
+                .replacen("http://", "ws://", 1),
+            path
+        );
+        let mut request = url
+            .into_client_request()
+            .map_err(|e| format!("Invalid WebSocket URL: {}", e))?;
+        let headers = request.headers_mut();
+        if let Ok(value) = HeaderValue::from_str(&protocols.join(", ")) {
+            headers.insert("Sec-WebSocket-Protocol", value);
+        }
+        if let Some(value) = session
+            .authorization
+            .and_then(|a| HeaderValue::from_str(&a).ok())
+        {
+            headers.insert("Authorization", value);
+        }
+
+        tungstenite::client(request, stream)
+            .map(|(socket, _)| socket)
+            .map_err(|e| match e {
+                tungstenite::HandshakeError::Failure(tungstenite::Error::Http(response)) => {
+                    let body = response
+                        .body()
+                        .as_ref()
+                        .map(|b| String::from_utf8_lossy(b).to_string())
+                        .unwrap_or_default();
+                    let detail = serde_json::from_str::<ApiStatus>(&body)
+                        .ok()
+                        .and_then(|s| s.message.or(s.reason))
+                        .unwrap_or(body);
+                    format!(
+                        "API error {} (upgrade {}): {}",
+                        response.status(),
+                        path,
+                        detail
+                    )
+                }
+                e => format!("WebSocket to {} failed: {}", path, e),
+            })
+    }
+
+    fn call(
+        &self,
+        method: &str,
+        path: &str,
+        body: Option<(&str, &str)>,
+        timeout: Option<Duration>,
+    ) -> Result<ureq::Response, String> {
+        let session = self.session()?;
+        let url = format!("{}{}", self.config.server, path);
+
+        let mut request = session
+            .agent
+            .request(method, &url)
+            .set("Accept", "application/json");
+        if let Some(authorization) = &session.authorization {
+            request = request.set("Authorization", authorization);
+        }
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+
+        let result = match body {
+            Some((content_type, body)) => {
+                request.set("Content-Type", content_type).send_string(body)
+            }
+            None => request.call(),
+        };
+
+        result.map_err(|e| match e {
+            ureq::Error::Status(code, response) => {
+                let status: Option<ApiStatus> = response.into_json().ok();
+                let detail = status
+                    .and_then(|s| s.message.or(s.reason))
+                    .unwrap_or_else(|| "request failed".to_string());
+                format!("API error {} ({} {}): {}", code, method, path, detail)
+            }
+            ureq::Error::Transport(t) => format!("Cannot reach {}: {}", self.config.server, t),
+        })
+    }
+
+    /// Returns the agent and credentials to use, re-running the exec plugin when its
+    /// credential has expired.
+    fn session(&self) -> Result<Session, String> {
+        let mut state = self.state.lock().map_err(|_| "Client state poisoned")?;
+
+        if let Some(exec) = &self.config.exec {
+            let expired = state
+                .exec_credential
+                .as_ref()
+                .and_then(|c| c.expires)
+                .map(|expires| expires <= Utc::now() + chrono::Duration::seconds(10))
+                .unwrap_or(false);
+            if expired {
+                let credential = run_exec_plugin(exec, &self.config)?;
+                if credential.cert_pem.is_some() {
+                    (state.agent, state.tls) = build_agent(&self.config, Some(&credential))?;
+                }
+                state.exec_credential = Some(credential);
+            }
+        }
+
+        let token = match state.exec_credential.as_ref().and_then(|c| c.token.clone()) {
+            Some(token) => Some(token),
+            None => match &self.config.token_file {
+                // Re-read on every request so rotated service account tokens are picked up.
+                Some(path) => Some(
+                    fs::read_to_string(path)
+                        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+                        .trim()
+                        .to_string(),
+                ),
+                None => self.config.token.clone(),
+            },
+        };
+
+        let authorization = match token {
+            Some(token) => Some(format!("Bearer {}", token)),
+            None => self.config.basic_auth.as_ref().map(|(user, password)| {
+                let encoded = base64::engine::general_purpose::STANDARD
+                    .encode(format!("{}:{}", user, password));
+                format!("Basic {}", encoded)
+            }),
+        };
+
+        Ok(Session {
+            agent: state.agent.clone(),
+            tls: state.tls.clone(),
+            authorization,
+        })
+    }
+}
+
+fn split_host_port(authority: &str, default_port: u16) -> (String, u16) {
+    if let Some(rest) = authority.strip_prefix('[') {
+        // IPv6 literal, e.g. [::1]:6443
+        let (host, port) = rest.split_once(']').unwrap_or((rest, ""));
+        let port = port.trim_start_matches(':').parse().unwrap_or(default_port);
+        return (host.to_string(), port);
+    }
+    match authority.rsplit_once(':') {
+        Some((host, port)) => (host.to_string(), port.parse().unwrap_or(default_port)),
+        None => (authority.to_string(), default_port),
+    }
+}
+
+fn run_exec_plugin(exec: &ExecConfig, config: &KubeConfig) -> Result<ExecCredential, String> {
+    let api_version = exec
+        .api_version
+        .clone()
+        .unwrap_or_else(|| "client.authentication.k8s.io/v1".to_string());
+    let mut spec = serde_json::json!({ "interactive": false });
+    if exec.provide_cluster_info {
+        spec["cluster"] = serde_json::json!({
+            "server": config.server,
+            "insecure-skip-tls-verify": config.insecure_skip_tls_verify,
+            "certificate-authority-data": config
+                .ca_pem
+                .as_ref()
+                .map(|ca| base64::engine::general_purpose::STANDARD.encode(ca)),
+        });
+    }
+    let exec_info = serde_json::json!({
+        "apiVersion": api_version,
+        "kind": "ExecCredential",
+        "spec": spec,
+    });
+
+    let output = Command::new(&exec.command)
+        .args(&exec.args)
+        .envs(exec.env.iter().map(|e| (&e.name, &e.value)))
+        .env("KUBERNETES_EXEC_INFO", exec_info.to_string())
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| {
+            let hint = exec.install_hint.as_deref().unwrap_or("");
+            format!(
+                "Failed to run credential plugin '{}': {} {}",
+                exec.command, e, hint
+            )
+        })?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Credential plugin '{}' failed: {}",
+            exec.command,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    let response: ExecCredentialResponse = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("Invalid ExecCredential from '{}': {}", exec.command, e))?;
+    let status = response
+        .status
+        .ok_or_else(|| format!("ExecCredential from '{}' has no status", exec.command))?;
+
+    Ok(ExecCredential {
+        token: status.token,
+        expires: status.expiration_timestamp,
+        cert_pem: status.client_certificate_data.map(String::into_bytes),
+        key_pem: status.client_key_data.map(String::into_bytes),
+    })
+}
+
+fn build_agent(
+    config: &KubeConfig,
+    exec_credential: Option<&ExecCredential>,
+) -> Result<(ureq::Agent, Option<Arc<ClientConfig>>), String> {
+    let mut builder = ureq::AgentBuilder::new().timeout_connect(Duration::from_secs(5));
+    let mut tls = None;
+
+    if config.server.starts_with("https://") {
+        let tls_config = Arc::new(build_tls_config(config, exec_credential)?);
+        builder = builder.tls_config(tls_config.clone());
+        tls = Some(tls_config);
+    }
+
+    Ok((builder.build(), tls))
+}
+
+fn build_tls_config(
+    config: &KubeConfig,
+    exec_credential: Option<&ExecCredential>,
+) -> Result<ClientConfig, String> {
+    let provider = Arc::new(ring::default_provider());
+    let builder = ClientConfig::builder_with_provider(provider.clone())
+        .with_safe_default_protocol_versions()
+        .map_err(|e| format!("TLS setup failed: {}", e))?;
+
+    let builder = if config.insecure_skip_tls_verify {
+        builder
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(InsecureVerifier(provider)))
+    } else {
+        let mut roots = RootCertStore::empty();
+        match &config.ca_pem {
+            Some(ca_pem) => {
+                for cert in rustls_pemfile::certs(&mut ca_pem.as_slice()) {
+                    let cert = cert.map_err(|e| format!("Invalid CA certificate: {}", e))?;
+                    roots
+                        .add(cert)
+                        .map_err(|e| format!("Invalid CA certificate: {}", e))?;
+                }
+            }
+            None => roots.extend(webpki_roots::TLS_SERVER_ROOTS.iter().cloned()),
+        }
+        let verifier = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider)
+            .build()
+            .map_err(|e| format!("TLS setup failed: {}", e))?;
+
+        match &config.tls_server_name {
+            Some(name) => {
+                let name = ServerName::try_from(name.clone())
+                    .map_err(|e| format!("Invalid tls-server-name '{}': {}", name, e))?;
+                builder
+                    .dangerous()
+                    .with_custom_certificate_verifier(Arc::new(ServerNameOverride {
+                        inner: verifier,
+                        name,
+                    }))
+            }
+            None => builder.with_webpki_verifier(verifier),
+        }
+    };
+
+    let cert_pem = exec_credential
+        .and_then(|c| c.cert_pem.as_ref())
+        .or(config.client_cert_pem.as_ref());
+    let key_pem = exec_credential
+        .and_then(|c| c.key_pem.as_ref())
+        .or(config.client_key_pem.as_ref());
+
+    match (cert_pem, key_pem) {
+        (Some(cert_pem), Some(key_pem)) => {
+            let certs = rustls_pemfile::certs(&mut cert_pem.as_slice())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("Invalid client certificate: {}", e))?;
+            let key = rustls_pemfile::private_key(&mut key_pem.as_slice())
+                .map_err(|e| format!("Invalid client key: {}", e))?
+                .ok_or_else(|| "No private key found in client key data".to_string())?;
+            builder
+                .with_client_auth_cert(certs, key)
+                .map_err(|e| format!("Invalid client certificate: {}", e))
+        }
+        _ => Ok(builder.with_no_client_auth()),
+    }
+}
+
+/// Accepts any server certificate, for clusters with `insecure-skip-tls-verify: true`
+/// and Docker hosts without TLS verification.
+#[derive(Debug)]
+pub(crate) struct InsecureVerifier(pub(crate) Arc<CryptoProvider>);
+
+impl ServerCertVerifier for InsecureVerifier {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// Verifies the server certificate against `tls-server-name` instead of the URL host.
+#[derive(Debug)]
+struct ServerNameOverride {
+    inner: Arc<WebPkiServerVerifier>,
+    name: ServerName<'static>,
+}
+
+impl ServerCertVerifier for ServerNameOverride {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        self.inner
+            .verify_server_cert(end_entity, intermediates, &self.name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::k8s::config::{ExecConfig, ExecEnvVar};
+    use crate::k8s::mock::{temp_dir, MockServer, Response};
+    use std::{os::unix::fs::PermissionsExt, path::Path};
+
+    fn namespaces() -> Response {
+        Response::json(serde_json::json!({ "items": [] }))
+    }
+
+    fn authorizations(server: &MockServer) -> Vec<String> {
+        server
+            .requests()
+            .iter()
+            .map(|r| r.header("Authorization").unwrap_or_default().to_string())
+            .collect()
+    }
+
+    /// A credential plugin that counts its runs and hands out `token-<run>`.
+    fn exec_plugin(dir: &Path, expiration: &str) -> ExecConfig {
+        let script = dir.join("plugin.sh");
+        fs::write(
+            &script,
+            format!(
+                r#"#!/bin/sh
+n=$(cat "{dir}/runs" 2>/dev/null || echo 0)
+n=$((n + 1))
+echo $n > "{dir}/runs"
+echo "$KUBERNETES_EXEC_INFO" > "{dir}/info"
+echo '{{"apiVersion":"client.authentication.k8s.io/v1","kind":"ExecCredential","status":{{"token":"'$PREFIX$n'"{expiration}}}}}'
+"#,
+                dir = dir.display(),
+                expiration = expiration,
+            ),
+        )
+        .unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        ExecConfig {
+            api_version: None,
+            command: script.display().to_string(),
+            args: Vec::new(),
+            env: vec![ExecEnvVar {
+                name: "PREFIX".to_string(),
+                value: "token-".to_string(),
+            }],
+            install_hint: None,
+            provide_cluster_info: true,
+        }
+    }
+
+    #[test]
+    fn sends_bearer_token() {
+        let server = MockServer::start(|_| namespaces());
+        let mut config = server.config();
+        config.token = Some("secret".to_string());
+        let client = KubeClient::new(config).unwrap();
+
+        client
+            .get_json::<serde_json::Value>("/api/v1/namespaces")
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/api/v1/namespaces");
+        assert_eq!(authorizations(&server), ["Bearer secret"]);
+    }
+
+    #[test]
+    fn rereads_token_file_on_every_request() {
+        let server = MockServer::start(|_| namespaces());
+        let dir = temp_dir("token-file");
+        let token_file = dir.join("token");
+        fs::write(&token_file, "first\n").unwrap();
+        let mut config = server.config();
+        config.token_file = Some(token_file.clone());
+        // A token file takes precedence over an inline token, as in kubectl.
+        config.token = Some("inline".to_string());
+        let client = KubeClient::new(config).unwrap();
+
+        client.get_json::<serde_json::Value>("/api").unwrap();
+        fs::write(&token_file, "rotated").unwrap();
+        client.get_json::<serde_json::Value>("/api").unwrap();
+
+        assert_eq!(authorizations(&server), ["Bearer first", "Bearer rotated"]);
+    }
+
+    #[test]
+    fn sends_basic_auth() {
+        let server = MockServer::start(|_| namespaces());
+        let mut config = server.config();
+        config.basic_auth = Some(("admin".to_string(), "hunter2".to_string()));
+        let client = KubeClient::new(config).unwrap();
+
+        client.get_json::<serde_json::Value>("/api").unwrap();
+
+        assert_eq!(authorizations(&server), ["Basic YWRtaW46aHVudGVyMg=="]);
+    }
+
+    #[test]
+    fn exec_plugin_token_is_cached_until_it_expires() {
+        let server = MockServer::start(|_| namespaces());
+        let dir = temp_dir("exec-cached");
+        let mut config = server.config();
+        config.exec = Some(exec_plugin(&dir, ""));
+        let client = KubeClient::new(config).unwrap();
+
+        client.get_json::<serde_json::Value>("/api").unwrap();
+        client.get_json::<serde_json::Value>("/api").unwrap();
+
+        assert_eq!(
+            authorizations(&server),
+            ["Bearer token-1", "Bearer token-1"]
+        );
+        let info: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(dir.join("info")).unwrap()).unwrap();
+        assert_eq!(info["kind"], "ExecCredential");
+        assert_eq!(info["spec"]["interactive"], false);
+        assert_eq!(info["spec"]["cluster"]["server"], server.url.as_str());
+    }
+
+    #[test]
+    fn exec_plugin_reruns_when_credential_expired() {
+        let server = MockServer::start(|_| namespaces());
+        let dir = temp_dir("exec-expired");
+        let mut config = server.config();
+        config.exec = Some(exec_plugin(
+            &dir,
+            r#","expirationTimestamp":"2000-01-01T00:00:00Z""#,
+        ));
+        let client = KubeClient::new(config).unwrap();
+
+        client.get_json::<serde_json::Value>("/api").unwrap();
+        client.get_json::<serde_json::Value>("/api").unwrap();
+
+        // The first run happens in `new`; every request finds it expired.
+        assert_eq!(
+            authorizations(&server),
+            ["Bearer token-2", "Bearer token-3"]
+        );
+    }
+
+    #[test]
+    fn exec_plugin_failure_is_reported() {
+        let dir = temp_dir("exec-failure");
+        let script = dir.join("failing.sh");
+        fs::write(&script, "#!/bin/sh\necho 'not logged in' >&2\nexit 1\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let server = MockServer::start(|_| namespaces());
+        let mut config = server.config();
+        config.exec = Some(ExecConfig {
+            command: script.display().to_string(),
+            ..exec_plugin(&dir, "")
+        });
+
+        let error = KubeClient::new(config).err().unwrap();
+        assert!(error.contains("not logged in"), "{}", error);
+    }
+
+    #[test]
+    fn api_errors_carry_the_status_message() {
+        let server = MockServer::start(|_| Response::status(404, "pods \"web\" not found"));
+        let client = KubeClient::new(server.config()).unwrap();
+
+        let error = client
+            .get_json::<serde_json::Value>("/api/v1/namespaces/default/pods/web")
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error,
+            "API error 404 (GET /api/v1/namespaces/default/pods/web): pods \"web\" not found"
+        );
+    }
+
+    #[test]
+    fn sends_json_bodies() {
+        let server = MockServer::start(|request| {
+            Response::json(serde_json::from_str(&request.body).unwrap())
+        });
+        let client = KubeClient::new(server.config()).unwrap();
+
+        let patch = serde_json::json!({ "spec": { "suspend": true } });
+        let echoed = client
+            .merge_patch("/apis/batch/v1/cronjobs/x", &patch)
+            .unwrap();
+
+        assert_eq!(echoed, patch);
+        let request = &server.requests()[0];
+        assert_eq!(request.method, "PATCH");
+        assert_eq!(
+            request.header("Content-Type"),
+            Some("application/merge-patch+json")
+        );
+    }
+
+    #[test]
+    fn splits_host_and_port() {
+        assert_eq!(
+            split_host_port("example.com:6443", 443),
+            ("example.com".to_string(), 6443)
+        );
+        assert_eq!(
+            split_host_port("example.com", 443),
+            ("example.com".to_string(), 443)
+        );
+        assert_eq!(
+            split_host_port("[::1]:8443", 443),
+            ("::1".to_string(), 8443)
+        );
+        assert_eq!(
+            split_host_port("[fd00::1]", 80),
+            ("fd00::1".to_string(), 80)
+        );
+    }
+}
@@ -0,0 +1,552 @@
+use base64::Engine;
+use serde::Deserialize;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+const SERVICE_ACCOUNT_DIR: &str = "/var/run/secrets/kubernetes.io/serviceaccount";
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+struct Kubeconfig {
+    #[serde(default)]
+    clusters: Vec<NamedCluster>,
+    #[serde(default)]
+    users: Vec<NamedUser>,
+    #[serde(default)]
+    contexts: Vec<NamedContext>,
+    current_context: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+struct NamedCluster {
+    name: String,
+    cluster: Cluster,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+struct Cluster {
+    server: String,
+    certificate_authority: Option<String>,
+    certificate_authority_data: Option<String>,
+    #[serde(default)]
+    insecure_skip_tls_verify: bool,
+    tls_server_name: Option<String>,
+    #[serde(skip)]
+    base_dir: PathBuf,
+}
+
+#[derive(Deserialize, Clone)]
+struct NamedUser {
+    name: String,
+    #[serde(default)]
+    user: User,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "kebab-case")]
+struct User {
+    token: Option<String>,
+    #[serde(rename = "tokenFile")]
+    token_file: Option<String>,
+    client_certificate: Option<String>,
+    client_certificate_data: Option<String>,
+    client_key: Option<String>,
+    client_key_data: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    exec: Option<ExecConfig>,
+    #[serde(skip)]
+    base_dir: PathBuf,
+}
+
+#[derive(Deserialize, Clone)]
+struct NamedContext {
+    name: String,
+    context: Context,
+}
+
+#[derive(Deserialize, Clone)]
+struct Context {
+    cluster: String,
+    user: Option<String>,
+    namespace: Option<String>,
+}
+
+/// Settings for a `client.authentication.k8s.io` credential plugin.
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecConfig {
+    pub api_version: Option<String>,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: Vec<ExecEnvVar>,
+    pub install_hint: Option<String>,
+    #[serde(default)]
+    pub provide_cluster_info: bool,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct ExecEnvVar {
+    pub name: String,
+    pub value: String,
+}
+
+/// Everything needed to reach one API server, resolved from the current context.
+#[derive(Clone)]
+pub struct KubeConfig {
+    pub context: String,
+    /// The context's namespace, `default` when it names none, as with kubectl.
+    pub namespace: String,
+    pub server: String,
+    pub ca_pem: Option<Vec<u8>>,
+    pub insecure_skip_tls_verify: bool,
+    pub tls_server_name: Option<String>,
+    pub token: Option<String>,
+    pub token_file: Option<PathBuf>,
+    pub basic_auth: Option<(String, String)>,
+    pub client_cert_pem: Option<Vec<u8>>,
+    pub client_key_pem: Option<Vec<u8>>,
+    pub exec: Option<ExecConfig>,
+}
+
+impl KubeConfig {
+    /// Loads the current context from `$KUBECONFIG` (or `~/.kube/config`),
+    /// falling back to the in-cluster service account.
+    pub fn load() -> Result<Self, String> {
+        Self::load_from(&kubeconfig_paths(), InCluster::from_env())
+    }
+
+    fn load_from(paths: &[PathBuf], in_cluster: Option<InCluster>) -> Result<Self, String> {
+        if paths.iter().any(|p| p.exists()) {
+            return Self::from_files(paths);
+        }
+
+        if let Some(in_cluster) = in_cluster {
+            return Ok(in_cluster.config());
+        }
+
+        Err("No kubeconfig found (set KUBECONFIG or create ~/.kube/config)".to_string())
+    }
+
+    fn from_files(paths: &[PathBuf]) -> Result<Self, String> {
+        let mut merged = Kubeconfig::default();
+
+        // Same rules as kubectl: the first file to define a name wins.
+        for path in paths.iter().filter(|p| p.exists()) {
+            let raw = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            if raw.trim().is_empty() {
+                continue;
+            }
+            let mut file: Kubeconfig = serde_yaml::from_str(&raw)
+                .map_err(|e| format!("Invalid kubeconfig {}: {}", path.display(), e))?;
+
+            let base_dir = path.parent().map(Path::to_path_buf).unwrap_or_default();
+            for named in &mut file.clusters {
+                named.cluster.base_dir = base_dir.clone();
+            }
+            for named in &mut file.users {
+                named.user.base_dir = base_dir.clone();
+            }
+
+            if merged.current_context.is_none() {
+                merged.current_context = file.current_context.filter(|c| !c.is_empty());
+            }
+            for cluster in file.clusters {
+                if !merged.clusters.iter().any(|c| c.name == cluster.name) {
+                    merged.clusters.push(cluster);
+                }
+            }
+            for user in file.users {
+                if !merged.users.iter().any(|u| u.name == user.name) {
+                    merged.users.push(user);
+                }
+            }
+            for context in file.contexts {
+                if !merged.contexts.iter().any(|c| c.name == context.name) {
+                    merged.contexts.push(context);
+                }
+            }
+        }
+
+        let context_name = merged
+            .current_context
+            .clone()
+            .ok_or_else(|| "Kubeconfig has no current-context".to_string())?;
+        let context = merged
+            .contexts
+            .iter()
+            .find(|c| c.name == context_name)
+            .ok_or_else(|| format!("Context '{}' not found in kubeconfig", context_name))?
+            .context
+            .clone();
+        let cluster = merged
+            .clusters
+            .iter()
+            .find(|c| c.name == context.cluster)
+            .ok_or_else(|| format!("Cluster '{}' not found in kubeconfig", context.cluster))?
+            .cluster
+            .clone();
+        let user = match &context.user {
+            Some(name) => merged
+                .users
+                .iter()
+                .find(|u| &u.name == name)
+                .map(|u| u.user.clone())
+                .ok_or_else(|| format!("User '{}' not found in kubeconfig", name))?,
+            None => User::default(),
+        };
+
+        let ca_pem = inline_or_file(
+            &cluster.certificate_authority_data,
+            &cluster.certificate_authority,
+            &cluster.base_dir,
+        )?;
+        let client_cert_pem = inline_or_file(
+            &user.client_certificate_data,
+            &user.client_certificate,
+            &user.base_dir,
+        )?;
+        let client_key_pem =
+            inline_or_file(&user.client_key_data, &user.client_key, &user.base_dir)?;
+
+        let mut exec = user.exec.clone();
+        if let Some(exec) = &mut exec {
+            // Relative plugin paths are relative to the kubeconfig that names them.
+            if exec.command.contains('/') && Path::new(&exec.command).is_relative() {
+                exec.command = user.base_dir.join(&exec.command).display().to_string();
+            }
+        }
+
+        Ok(KubeConfig {
+            context: context_name,
+            namespace: context
+                .namespace
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| "default".to_string()),
+            server: cluster.server.trim_end_matches('/').to_string(),
+            ca_pem,
+            insecure_skip_tls_verify: cluster.insecure_skip_tls_verify,
+            tls_server_name: cluster.tls_server_name,
+            token: user.token,
+            token_file: user.token_file.map(|f| resolve_path(&f, &user.base_dir)),
+            basic_auth: user.username.zip(user.password),
+            client_cert_pem,
+            client_key_pem,
+            exec,
+        })
+    }
+}
+
+/// The service account mounted into a pod, found through the service env vars.
+struct InCluster {
+    host: String,
+    port: String,
+    dir: PathBuf,
+}
+
+impl InCluster {
+    fn from_env() -> Option<Self> {
+        Some(InCluster {
+            host: env::var("KUBERNETES_SERVICE_HOST").ok()?,
+            port: env::var("KUBERNETES_SERVICE_PORT").unwrap_or_else(|_| "443".to_string()),
+            dir: PathBuf::from(SERVICE_ACCOUNT_DIR),
+        })
+    }
+
+    fn config(&self) -> KubeConfig {
+        let host = if self.host.contains(':') {
+            format!("[{}]", self.host)
+        } else {
+            self.host.clone()
+        };
+
+        KubeConfig {
+            context: "in-cluster".to_string(),
+            namespace: fs::read_to_string(self.dir.join("namespace"))
+                .map(|n| n.trim().to_string())
+                .ok()
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| "default".to_string()),
+            server: format!("https://{}:{}", host, self.port),
+            ca_pem: fs::read(self.dir.join("ca.crt")).ok(),
+            insecure_skip_tls_verify: false,
+            tls_server_name: None,
+            token: None,
+            token_file: Some(self.dir.join("token")),
+            basic_auth: None,
+            client_cert_pem: None,
+            client_key_pem: None,
+            exec: None,
+        }
+    }
+}
+
+fn kubeconfig_paths() -> Vec<PathBuf> {
+    match env::var_os("KUBECONFIG") {
+        Some(value) if !value.is_empty() => env::split_paths(&value).collect(),
+        _ => home_dir()
+            .map(|home| vec![home.join(".kube").join("config")])
+            .unwrap_or_default(),
+    }
+}
+
+pub fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn resolve_path(path: &str, base_dir: &Path) -> PathBuf {
+    let path = Path::new(path);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base_dir.join(path)
+    }
+}
+
+fn inline_or_file(
+    data: &Option<String>,
+    file: &Option<String>,
+    base_dir: &Path,
+) -> Result<Option<Vec<u8>>, String> {
+    if let Some(data) = data {
+        return base64::engine::general_purpose::STANDARD
+            .decode(data.trim())
+            .map(Some)
+            .map_err(|e| format!("Invalid base64 in kubeconfig: {}", e));
+    }
+    match file {
+        Some(file) => {
+            let path = resolve_path(file, base_dir);
+            fs::read(&path)
+                .map(Some)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+        }
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::k8s::mock::temp_dir;
+
+    fn write(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn first_file_to_define_a_name_wins() {
+        let dir = temp_dir("merge");
+        let first = write(
+            &dir,
+            "first",
+            r#"
+current-context: dev
+contexts:
+- name: dev
+  context: {cluster: shared, user: alice}
+clusters:
+- name: shared
+  cluster: {server: "https://first.example:6443/"}
+users:
+- name: alice
+  user: {token: from-first}
+"#,
+        );
+        let second = write(
+            &dir,
+            "second",
+            r#"
+current-context: prod
+contexts:
+- name: dev
+  context: {cluster: other, user: bob}
+clusters:
+- name: shared
+  cluster: {server: "https://second.example"}
+users:
+- name: alice
+  user: {token: from-second}
+"#,
+        );
+
+        let config = KubeConfig::load_from(&[first, second], None).unwrap();
+
+        assert_eq!(config.context, "dev");
+        assert_eq!(config.namespace, "default");
+        assert_eq!(config.server, "https://first.example:6443");
+        assert_eq!(config.token.as_deref(), Some("from-first"));
+    }
+
+    #[test]
+    fn entries_merge_across_files() {
+        let dir = temp_dir("merge-across");
+        // The first file only picks the context; the rest comes from the second.
+        let first = write(
+            &dir,
+            "first",
+            "current-context: staging
+",
+        );
+        let empty = write(
+            &dir, "empty", "
+",
+        );
+        let second = write(
+            &dir,
+            "second",
+            r#"
+contexts:
+- name: staging
+  context: {cluster: staging, user: ci}
+clusters:
+- name: staging
+  cluster:
+    server: https://staging.example
+    insecure-skip-tls-verify: true
+    tls-server-name: api.internal
+users:
+- name: ci
+  user: {username: ci, password: pw}
+"#,
+        );
+        let missing = dir.join("missing");
+
+        let config = KubeConfig::load_from(&[missing, first, empty, second], None).unwrap();
+
+        assert_eq!(config.context, "staging");
+        assert_eq!(config.server, "https://staging.example");
+        assert!(config.insecure_skip_tls_verify);
+        assert_eq!(config.tls_server_name.as_deref(), Some("api.internal"));
+        assert_eq!(
+            config.basic_auth,
+            Some(("ci".to_string(), "pw".to_string()))
+        );
+    }
+
+    #[test]
+    fn paths_are_relative_to_the_file_that_names_them() {
+        let dir = temp_dir("relative");
+        let sub = dir.join("sub");
+        fs::create_dir_all(&sub).unwrap();
+        write(&sub, "ca.crt", "CA");
+        write(&sub, "client.crt", "CERT");
+        let config_path = write(
+            &sub,
+            "config",
+            r#"
+current-context: c
+contexts:
+- name: c
+  context: {cluster: c, user: u, namespace: team-a}
+clusters:
+- name: c
+  cluster: {server: "https://c.example", certificate-authority: ca.crt}
+users:
+- name: u
+  user:
+    client-certificate: client.crt
+    client-key-data: S0VZ
+    tokenFile: token
+    exec: {command: ./bin/plugin, args: [get-token]}
+"#,
+        );
+
+        let config = KubeConfig::load_from(&[config_path], None).unwrap();
+
+        assert_eq!(config.namespace, "team-a");
+        assert_eq!(config.ca_pem.as_deref(), Some(&b"CA"[..]));
+        assert_eq!(config.client_cert_pem.as_deref(), Some(&b"CERT"[..]));
+        assert_eq!(config.client_key_pem.as_deref(), Some(&b"KEY"[..]));
+        assert_eq!(config.token_file, Some(sub.join("token")));
+        let exec = config.exec.unwrap();
+        assert_eq!(exec.command, sub.join("./bin/plugin").display().to_string());
+        assert_eq!(exec.args, ["get-token"]);
+    }
+
+    #[test]
+    fn reports_missing_context() {
+        let dir = temp_dir("missing-context");
+        let path = write(
+            &dir,
+            "config",
+            "current-context: nowhere
+",
+        );
+
+        let error = KubeConfig::load_from(&[path], None).err().unwrap();
+
+        assert_eq!(error, "Context 'nowhere' not found in kubeconfig");
+    }
+
+    #[test]
+    fn falls_back_to_in_cluster_service_account() {
+        let dir = temp_dir("in-cluster");
+        write(&dir, "ca.crt", "CA");
+        write(&dir, "namespace", "monitoring\n");
+        let in_cluster = InCluster {
+            host: "10.96.0.1".to_string(),
+            port: "443".to_string(),
+            dir: dir.clone(),
+        };
+
+        let config = KubeConfig::load_from(&[dir.join("no-config")], Some(in_cluster)).unwrap();
+
+        assert_eq!(config.context, "in-cluster");
+        assert_eq!(config.namespace, "monitoring");
+        assert_eq!(config.server, "https://10.96.0.1:443");
+        assert_eq!(config.ca_pem.as_deref(), Some(&b"CA"[..]));
+        assert_eq!(config.token_file, Some(dir.join("token")));
+    }
+
+    #[test]
+    fn in_cluster_brackets_ipv6_hosts() {
+        let in_cluster = InCluster {
+            host: "fd00::1".to_string(),
+            port: "6443".to_string(),
+            dir: temp_dir("in-cluster-ipv6"),
+        };
+
+        assert_eq!(in_cluster.config().server, "https://[fd00::1]:6443");
+    }
+
+    #[test]
+    fn kubeconfig_wins_over_in_cluster() {
+        let dir = temp_dir("precedence");
+        let path = write(
+            &dir,
+            "config",
+            r#"
+current-context: c
+contexts: [{name: c, context: {cluster: c}}]
+clusters: [{name: c, cluster: {server: "http://local:8080"}}]
+"#,
+        );
+        let in_cluster = InCluster {
+            host: "10.96.0.1".to_string(),
+            port: "443".to_string(),
+            dir: dir.clone(),
+        };
+
+        let config = KubeConfig::load_from(&[path], Some(in_cluster)).unwrap();
+
+        assert_eq!(config.server, "http://local:8080");
+        assert!(config.token.is_none());
+    }
+
+    #[test]
+    fn nothing_to_load() {
+        let dir = temp_dir("nothing");
+        assert!(KubeConfig::load_from(&[dir.join("config")], None).is_err());
+    }
+}
@@ -0,0 +1,177 @@
+use super::{format_age, ObjectMeta, Resource};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigMap {
+    pub metadata: ObjectMeta,
+    #[serde(default)]
+    pub data: BTreeMap<String, String>,
+    #[serde(default)]
+    pub binary_data: BTreeMap<String, String>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct Secret {
+    pub metadata: ObjectMeta,
+    #[serde(rename = "type")]
+    pub secret_type: Option<String>,
+    #[serde(default)]
+    pub data: BTreeMap<String, String>,
+}
+
+impl Resource for ConfigMap {
+    fn metadata(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+}
+
+impl Resource for Secret {
+    fn metadata(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+}
+
+/// A ConfigMap or Secret row with its decoded entries.
+#[derive(Clone)]
+pub struct K8sDataObject {
+    pub name: String,
+    pub namespace: String,
+    pub data_type: String,
+    pub entries: Vec<DataEntry>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+pub struct DataEntry {
+    pub key: String,
+    pub value: Vec<u8>,
+}
+
+impl DataEntry {
+    /// The value as text, or `None` when it is not valid UTF-8.
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.value).ok()
+    }
+}
+
+impl K8sDataObject {
+    pub fn from_config_map(config_map: &ConfigMap) -> Self {
+        let mut entries: Vec<DataEntry> = config_map
+            .data
+            .iter()
+            .map(|(key, value)| DataEntry {
+                key: key.clone(),
+                value: value.clone().into_bytes(),
+            })
+            .collect();
+        entries.extend(config_map.binary_data.iter().map(|(key, value)| DataEntry {
+            key: key.clone(),
+            value: decode_base64(value),
+        }));
+        entries.sort_by(|a, b| a.key.cmp(&b.key));
+
+        K8sDataObject {
+            name: config_map.metadata.name.clone(),
+            namespace: config_map.metadata.namespace.clone().unwrap_or_default(),
+            data_type: "ConfigMap".to_string(),
+            entries,
+            created: config_map.metadata.creation_timestamp,
+        }
+    }
+
+    pub fn from_secret(secret: &Secret) -> Self {
+        K8sDataObject {
+            name: secret.metadata.name.clone(),
+            namespace: secret.metadata.namespace.clone().unwrap_or_default(),
+            data_type: secret
+                .secret_type
+                .clone()
+                .unwrap_or_else(|| "Opaque".to_string()),
+            entries: secret
+                .data
+                .iter()
+                .map(|(key, value)| DataEntry {
+                    key: key.clone(),
+                    value: decode_base64(value),
+                })
+                .collect(),
+            created: secret.metadata.creation_timestamp,
+        }
+    }
+
+    pub fn total_size(&self) -> u64 {
+        self.entries.iter().map(|e| e.value.len() as u64).sum()
+    }
+
+    pub fn age(&self) -> String {
+        format_age(self.created)
+    }
+}
+
+/// A copy of a Secret manifest with every `data` and `stringData` value replaced by
+/// its size, or `None` for other kinds.
+pub fn mask_secret_values(manifest: &Value) -> Option<Value> {
+    if manifest.get("kind").and_then(|k| k.as_str()) != Some("Secret") {
+        return None;
+    }
+    let mut masked = manifest.clone();
+    for (field, encoded) in [("data", true), ("stringData", false)] {
+        let Some(values) = masked.get_mut(field).and_then(|d| d.as_object_mut()) else {
+            continue;
+        };
+        for value in values.values_mut() {
+            let text = value.as_str().unwrap_or_default();
+            let size = if encoded {
+                decode_base64(text).len()
+            } else {
+                text.len()
+            };
+            *value = Value::String(format!("<hidden, {} bytes>", size));
+        }
+    }
+    Some(masked)
+}
+
+fn decode_base64(value: &str) -> Vec<u8> {
+    base64::engine::general_purpose::STANDARD
+        .decode(value.trim())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn masks_secret_data_and_string_data() {
+        let secret = json!({
+            "kind": "Secret",
+            "metadata": {"name": "db"},
+            "type": "Opaque",
+            "data": {"password": "aHVudGVyMg=="},
+            "stringData": {"user": "admin"},
+        });
+
+        let masked = mask_secret_values(&secret).unwrap();
+
+        assert_eq!(masked["data"]["password"], "<hidden, 7 bytes>");
+        assert_eq!(masked["stringData"]["user"], "<hidden, 5 bytes>");
+        assert_eq!(masked["metadata"], secret["metadata"]);
+        assert_eq!(masked["type"], "Opaque");
+    }
+
+    #[test]
+    fn leaves_other_kinds_alone() {
+        let config_map = json!({"kind": "ConfigMap", "data": {"key": "value"}});
+        assert!(mask_secret_values(&config_map).is_none());
+
+        let empty = json!({"kind": "Secret", "metadata": {"name": "empty"}});
+        assert_eq!(mask_secret_values(&empty).unwrap(), empty);
+    }
+}
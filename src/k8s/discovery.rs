@@ -0,0 +1,426 @@
+use super::{client::KubeClient, format_age, ObjectMeta, Resource};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::{
+    mpsc::{self, Receiver},
+    Arc,
+};
+use std::thread;
+
+#[derive(Deserialize)]
+struct ApiVersions {
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct ApiGroupList {
+    #[serde(default)]
+    groups: Vec<ApiGroup>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApiGroup {
+    preferred_version: Option<GroupVersion>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GroupVersion {
+    group_version: String,
+}
+
+#[derive(Deserialize)]
+struct ApiResourceList {
+    #[serde(default)]
+    resources: Vec<RawApiResource>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawApiResource {
+    name: String,
+    kind: String,
+    #[serde(default)]
+    namespaced: bool,
+    #[serde(default)]
+    verbs: Vec<String>,
+    #[serde(default)]
+    short_names: Vec<String>,
+}
+
+/// One listable kind as reported by API discovery, e.g. `deployments` in `apps/v1`.
+#[derive(Clone)]
+pub struct ApiResource {
+    pub group_version: String,
+    pub plural: String,
+    pub kind: String,
+    pub namespaced: bool,
+    pub short_names: Vec<String>,
+}
+
+impl ApiResource {
+    pub fn group(&self) -> &str {
+        self.group_version
+            .split_once('/')
+            .map(|(group, _)| group)
+            .unwrap_or("")
+    }
+
+    /// Collection path across all namespaces, e.g. `/apis/apps/v1/deployments`.
+    pub fn list_path(&self) -> String {
+        if self.group().is_empty() {
+            format!("/api/{}/{}", self.group_version, self.plural)
+        } else {
+            format!("/apis/{}/{}", self.group_version, self.plural)
+        }
+    }
+
+    /// `plural.group` as kubectl accepts it, e.g. `certificates.cert-manager.io`.
+    pub fn full_name(&self) -> String {
+        if self.group().is_empty() {
+            self.plural.clone()
+        } else {
+            format!("{}.{}", self.plural, self.group())
+        }
+    }
+}
+
+/// Runs API discovery in the background; it takes one request per API group.
+pub struct DiscoveryTask {
+    rx: Receiver<Result<Vec<ApiResource>, String>>,
+}
+
+impl DiscoveryTask {
+    pub fn spawn(client: Arc<KubeClient>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(discover_resources(&client));
+        });
+        DiscoveryTask { rx }
+    }
+
+    pub fn poll(&mut self) -> Option<Result<Vec<ApiResource>, String>> {
+        self.rx.try_recv().ok()
+    }
+}
+
+/// Lists every kind that can be listed and watched, using each group's preferred version.
+fn discover_resources(client: &KubeClient) -> Result<Vec<ApiResource>, String> {
+    let mut group_versions = Vec::new();
+    let core: ApiVersions = client.get_json("/api")?;
+    group_versions.extend(
+        core.versions
+            .into_iter()
+            .map(|v| (v.clone(), format!("/api/{}", v))),
+    );
+
+    let groups: ApiGroupList = client.get_json("/apis")?;
+    for group in groups.groups {
+        if let Some(preferred) = group.preferred_version {
+            let path = format!("/apis/{}", preferred.group_version);
+            group_versions.push((preferred.group_version, path));
+        }
+    }
+
+    let mut resources = Vec::new();
+    for (group_version, path) in group_versions {
+        // Aggregated APIs that are down should not hide everything else.
+        let Ok(list) = client.get_json::<ApiResourceList>(&path) else {
+            continue;
+        };
+        for resource in list.resources {
+            let listable = resource.verbs.iter().any(|v| v == "list")
+                && resource.verbs.iter().any(|v| v == "watch");
+            if resource.name.contains('/') || !listable {
+                continue;
+            }
+            resources.push(ApiResource {
+                group_version: group_version.clone(),
+                plural: resource.name,
+                kind: resource.kind,
+                namespaced: resource.namespaced,
+                short_names: resource.short_names,
+            });
+        }
+    }
+
+    resources.sort_by(|a, b| a.kind.cmp(&b.kind).then(a.group().cmp(b.group())));
+    Ok(resources)
+}
+
+/// Looks up the resource name of `kind` in `api_version`, e.g. `ingresses` for
+/// `networking.k8s.io/v1` Ingress, as discovery reports it rather than guessing.
+pub fn resource_plural(
+    client: &KubeClient,
+    api_version: &str,
+    kind: &str,
+) -> Result<String, String> {
+    let path = if api_version.contains('/') {
+        format!("/apis/{}", api_version)
+    } else {
+        format!("/api/{}", api_version)
+    };
+    let list: ApiResourceList = client.get_json(&path)?;
+    list.resources
+        .into_iter()
+        .find(|r| r.kind == kind && !r.name.contains('/'))
+        .map(|r| r.name)
+        .ok_or_else(|| format!("{} is not served by {}", kind, api_version))
+}
+
+#[derive(Deserialize)]
+struct CustomResourceDefinition {
+    spec: CrdSpec,
+}
+
+#[derive(Deserialize)]
+struct CrdSpec {
+    #[serde(default)]
+    versions: Vec<CrdVersion>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CrdVersion {
+    name: String,
+    #[serde(default)]
+    additional_printer_columns: Vec<PrinterColumn>,
+}
+
+/// A column from a CRD's `additionalPrinterColumns`.
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrinterColumn {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub column_type: String,
+    pub json_path: String,
+    #[serde(default)]
+    pub priority: i32,
+}
+
+/// Fetches the printer columns of a custom resource. Built-in kinds have no CRD,
+/// so any lookup failure just means "no extra columns".
+pub fn printer_columns(client: &KubeClient, resource: &ApiResource) -> Vec<PrinterColumn> {
+    if resource.group().is_empty() {
+        return Vec::new();
+    }
+    let version = resource
+        .group_version
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    let path = format!(
+        "/apis/apiextensions.k8s.io/v1/customresourcedefinitions/{}",
+        resource.full_name()
+    );
+
+    client
+        .get_json::<CustomResourceDefinition>(&path)
+        .ok()
+        .and_then(|crd| crd.spec.versions.into_iter().find(|v| v.name == version))
+        .map(|v| {
+            v.additional_printer_columns
+                .into_iter()
+                // Priority > 0 columns only show with `-o wide`.
+                .filter(|c| c.priority == 0)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Any object, kept as raw JSON next to its parsed metadata.
+#[derive(Clone)]
+pub struct DynamicObject {
+    pub metadata: ObjectMeta,
+    pub raw: Value,
+}
+
+impl<'de> Deserialize<'de> for DynamicObject {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = Value::deserialize(deserializer)?;
+        let metadata = raw
+            .get("metadata")
+            .map(|m| serde_json::from_value(m.clone()))
+            .transpose()
+            .map_err(serde::de::Error::custom)?
+            .unwrap_or_default();
+        Ok(DynamicObject { metadata, raw })
+    }
+}
+
+impl Resource for DynamicObject {
+    fn metadata(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+}
+
+/// A row of the generic browser: name, namespace and the printer column values.
+#[derive(Clone)]
+pub struct GenericRow {
+    pub name: String,
+    pub namespace: String,
+    pub cells: Vec<String>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl GenericRow {
+    pub fn from_object(object: &DynamicObject, columns: &[PrinterColumn]) -> Self {
+        GenericRow {
+            name: object.metadata.name.clone(),
+            namespace: object.metadata.namespace.clone().unwrap_or_default(),
+            cells: columns
+                .iter()
+                .map(|column| format_cell(&object.raw, column))
+                .collect(),
+            created: object.metadata.creation_timestamp,
+        }
+    }
+
+    pub fn age(&self) -> String {
+        format_age(self.created)
+    }
+}
+
+fn format_cell(object: &Value, column: &PrinterColumn) -> String {
+    let values = json_path(object, &column.json_path);
+    let text: Vec<String> = values
+        .iter()
+        .map(|value| match (column.column_type.as_str(), value) {
+            ("date", Value::String(s)) => s
+                .parse::<DateTime<Utc>>()
+                .map(|t| format_age(Some(t)))
+                .unwrap_or_else(|_| s.clone()),
+            (_, Value::String(s)) => s.clone(),
+            (_, Value::Null) => String::new(),
+            (_, other) => other.to_string(),
+        })
+        .collect();
+    if text.is_empty() {
+        "<none>".to_string()
+    } else {
+        text.join(",")
+    }
+}
+
+enum PathSegment {
+    Field(String),
+    Index(usize),
+    All,
+    /// `[?(@.a.b=="value")]`
+    Filter(Vec<PathSegment>, String),
+}
+
+/// Evaluates the subset of JSONPath used by printer columns: `.a.b`, `.a[0]`,
+/// `.a[*].b`, `['a.b']`, escaped dots (`a\.b`) and `[?(@.type=="Ready")]` filters.
+fn json_path<'a>(object: &'a Value, path: &str) -> Vec<&'a Value> {
+    let path = path.trim().trim_start_matches('{').trim_end_matches('}');
+    let segments = parse_path(path.trim_start_matches('$'));
+    let mut current = vec![object];
+    for segment in &segments {
+        current = current
+            .into_iter()
+            .flat_map(|value| apply_segment(value, segment))
+            .collect();
+    }
+    current
+}
+
+fn apply_segment<'a>(value: &'a Value, segment: &PathSegment) -> Vec<&'a Value> {
+    match segment {
+        PathSegment::Field(name) => value.get(name).into_iter().collect(),
+        PathSegment::Index(i) => value.get(i).into_iter().collect(),
+        PathSegment::All => match value {
+            Value::Array(items) => items.iter().collect(),
+            Value::Object(map) => map.values().collect(),
+            _ => Vec::new(),
+        },
+        PathSegment::Filter(path, expected) => value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter(|item| {
+                let mut found = vec![*item];
+                for segment in path {
+                    found = found
+                        .into_iter()
+                        .flat_map(|v| apply_segment(v, segment))
+                        .collect();
+                }
+                found.iter().any(|v| match v {
+                    Value::String(s) => s == expected,
+                    other => other.to_string() == *expected,
+                })
+            })
+            .collect(),
+    }
+}
+
+fn parse_path(path: &str) -> Vec<PathSegment> {
+    let chars: Vec<char> = path.chars().collect();
+    let mut segments = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        match chars[i] {
+            '.' | '@' => i += 1,
+            '[' => {
+                let end = matching_bracket(&chars, i);
+                let inner: String = chars[i + 1..end].iter().collect();
+                let inner = inner.trim();
+                if inner == "*" {
+                    segments.push(PathSegment::All);
+                } else if let Ok(index) = inner.parse() {
+                    segments.push(PathSegment::Index(index));
+                } else if let Some(filter) =
+                    inner.strip_prefix("?(").and_then(|f| f.strip_suffix(')'))
+                {
+                    if let Some((left, right)) = filter.split_once("==") {
+                        let expected = right.trim().trim_matches(|c| c == '"' || c == '\'');
+                        segments.push(PathSegment::Filter(
+                            parse_path(left.trim()),
+                            expected.to_string(),
+                        ));
+                    }
+                } else {
+                    segments.push(PathSegment::Field(
+                        inner.trim_matches(|c| c == '"' || c == '\'').to_string(),
+                    ));
+                }
+                i = end + 1;
+            }
+            _ => {
+                let mut name = String::new();
+                while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        i += 1;
+                    }
+                    name.push(chars[i]);
+                    i += 1;
+                }
+                segments.push(PathSegment::Field(name));
+            }
+        }
+    }
+    segments
+}
+
+fn matching_bracket(chars: &[char], open: usize) -> usize {
+    let mut depth = 0;
+    for (i, c) in chars.iter().enumerate().skip(open) {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    chars.len()
+}
@@ -0,0 +1,279 @@
+use super::{client::KubeClient, format_age, human_duration, ObjectMeta, Resource};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+const JOB_NAME_LABEL: &str = "job-name";
+
+#[derive(Deserialize, Clone)]
+pub struct Job {
+    pub metadata: ObjectMeta,
+    #[serde(default)]
+    pub spec: JobSpec,
+    #[serde(default)]
+    pub status: JobStatus,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct JobSpec {
+    pub completions: Option<u32>,
+    pub suspend: Option<bool>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JobStatus {
+    #[serde(default)]
+    pub active: u32,
+    #[serde(default)]
+    pub succeeded: u32,
+    #[serde(default)]
+    pub failed: u32,
+    pub start_time: Option<DateTime<Utc>>,
+    pub completion_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub conditions: Vec<JobCondition>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct JobCondition {
+    #[serde(rename = "type")]
+    pub condition_type: String,
+    pub status: String,
+}
+
+impl Resource for Job {
+    fn metadata(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct CronJob {
+    pub metadata: ObjectMeta,
+    #[serde(default)]
+    pub spec: CronJobSpec,
+    #[serde(default)]
+    pub status: CronJobStatus,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CronJobSpec {
+    #[serde(default)]
+    pub schedule: String,
+    pub time_zone: Option<String>,
+    pub suspend: Option<bool>,
+    #[serde(default)]
+    pub job_template: Value,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CronJobStatus {
+    #[serde(default)]
+    pub active: Vec<Value>,
+    pub last_schedule_time: Option<DateTime<Utc>>,
+    pub last_successful_time: Option<DateTime<Utc>>,
+}
+
+impl Resource for CronJob {
+    fn metadata(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+}
+
+/// A Job row, with columns computed like `kubectl get jobs`.
+#[derive(Clone)]
+pub struct K8sJob {
+    pub name: String,
+    pub namespace: String,
+    pub status: String,
+    pub completions: String,
+    pub active: u32,
+    pub succeeded: u32,
+    pub failed: u32,
+    pub start_time: Option<DateTime<Utc>>,
+    pub completion_time: Option<DateTime<Utc>>,
+    pub cron_job: Option<String>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl K8sJob {
+    pub fn from_job(job: &Job) -> Self {
+        let status = &job.status;
+        let condition = |kind: &str| {
+            status
+                .conditions
+                .iter()
+                .any(|c| c.condition_type == kind && c.status == "True")
+        };
+
+        let state = if condition("Failed") {
+            "Failed".to_string()
+        } else if condition("Complete") {
+            "Complete".to_string()
+        } else if job.spec.suspend == Some(true) || condition("Suspended") {
+            "Suspended".to_string()
+        } else if condition("FailureTarget") {
+            "FailureTarget".to_string()
+        } else if condition("SuccessCriteriaMet") {
+            "SuccessCriteriaMet".to_string()
+        } else {
+            "Running".to_string()
+        };
+
+        let completions = match job.spec.completions {
+            Some(completions) => format!("{}/{}", status.succeeded, completions),
+            // Work-queue jobs without a completion count, like kubectl's "1/1 of 3".
+            None => format!("{}/1 of {}", status.succeeded, status.active.max(1)),
+        };
+
+        K8sJob {
+            name: job.metadata.name.clone(),
+            namespace: job.metadata.namespace.clone().unwrap_or_default(),
+            status: state,
+            completions,
+            active: status.active,
+            succeeded: status.succeeded,
+            failed: status.failed,
+            start_time: status.start_time,
+            completion_time: status.completion_time,
+            cron_job: job
+                .metadata
+                .owner_references
+                .iter()
+                .find(|o| o.controller && o.kind == "CronJob")
+                .map(|o| o.name.clone()),
+            created: job.metadata.creation_timestamp,
+        }
+    }
+
+    /// Time from start to completion, or until now while the job is still running.
+    pub fn duration(&self) -> String {
+        match (self.start_time, self.completion_time) {
+            (Some(start), Some(end)) => human_duration(end - start),
+            (Some(start), None) => human_duration(Utc::now() - start),
+            _ => String::new(),
+        }
+    }
+
+    pub fn age(&self) -> String {
+        format_age(self.created)
+    }
+
+    /// Label that the Job controller puts on every pod it creates.
+    pub fn pod_selector(&self) -> (&'static str, &str) {
+        (JOB_NAME_LABEL, &self.name)
+    }
+}
+
+/// A CronJob row, with columns computed like `kubectl get cronjobs`.
+#[derive(Clone)]
+pub struct K8sCronJob {
+    pub name: String,
+    pub namespace: String,
+    pub uid: String,
+    pub schedule: String,
+    pub time_zone: String,
+    pub suspend: bool,
+    pub active: usize,
+    pub last_schedule: Option<DateTime<Utc>>,
+    pub last_successful: Option<DateTime<Utc>>,
+    pub job_template: Value,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl K8sCronJob {
+    pub fn from_cron_job(cron_job: &CronJob) -> Self {
+        K8sCronJob {
+            name: cron_job.metadata.name.clone(),
+            namespace: cron_job.metadata.namespace.clone().unwrap_or_default(),
+            uid: cron_job.metadata.uid.clone().unwrap_or_default(),
+            schedule: cron_job.spec.schedule.clone(),
+            time_zone: cron_job
+                .spec
+                .time_zone
+                .clone()
+                .unwrap_or_else(|| "<none>".to_string()),
+            suspend: cron_job.spec.suspend.unwrap_or(false),
+            active: cron_job.status.active.len(),
+            last_schedule: cron_job.status.last_schedule_time,
+            last_successful: cron_job.status.last_successful_time,
+            job_template: cron_job.spec.job_template.clone(),
+            created: cron_job.metadata.creation_timestamp,
+        }
+    }
+
+    pub fn last_schedule_display(&self) -> String {
+        match self.last_schedule {
+            Some(_) => format!("{} ago", format_age(self.last_schedule)),
+            None => "<none>".to_string(),
+        }
+    }
+
+    pub fn last_successful_display(&self) -> String {
+        match self.last_successful {
+            Some(_) => format!("{} ago", format_age(self.last_successful)),
+            None => "<none>".to_string(),
+        }
+    }
+
+    pub fn age(&self) -> String {
+        format_age(self.created)
+    }
+}
+
+/// Creates a Job from the CronJob's template, like `kubectl create job --from=cronjob/NAME`.
+/// Returns the name of the new Job.
+pub fn trigger_cron_job(client: &KubeClient, cron_job: &K8sCronJob) -> Result<String, String> {
+    let suffix = Utc::now().timestamp() % 100_000;
+    let base: String = cron_job.name.chars().take(45).collect();
+    let name = format!("{}-manual-{}", base, suffix);
+
+    let template = &cron_job.job_template;
+    let job = json!({
+        "apiVersion": "batch/v1",
+        "kind": "Job",
+        "metadata": {
+            "name": name,
+            "namespace": cron_job.namespace,
+            "labels": template.pointer("/metadata/labels").cloned().unwrap_or(json!({})),
+            "annotations": {
+                "cronjob.kubernetes.io/instantiate": "manual",
+            },
+            "ownerReferences": [{
+                "apiVersion": "batch/v1",
+                "kind": "CronJob",
+                "name": cron_job.name,
+                "uid": cron_job.uid,
+                "controller": true,
+                "blockOwnerDeletion": true,
+            }],
+        },
+        "spec": template.get("spec").cloned().unwrap_or(json!({})),
+    });
+
+    client.post_json(
+        &format!("/apis/batch/v1/namespaces/{}/jobs", cron_job.namespace),
+        &job,
+    )?;
+    Ok(name)
+}
+
+/// Suspends or resumes a CronJob by patching `spec.suspend`.
+pub fn set_cron_job_suspended(
+    client: &KubeClient,
+    cron_job: &K8sCronJob,
+    suspend: bool,
+) -> Result<(), String> {
+    client
+        .merge_patch(
+            &format!(
+                "/apis/batch/v1/namespaces/{}/cronjobs/{}",
+                cron_job.namespace, cron_job.name
+            ),
+            &json!({ "spec": { "suspend": suspend } }),
+        )
+        .map(|_| ())
+}
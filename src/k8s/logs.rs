@@ -0,0 +1,77 @@
+use super::client::KubeClient;
+use std::{
+    io::BufRead,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::{self, Receiver},
+        Arc,
+    },
+    thread,
+};
+
+const TAIL_LINES: u32 = 500;
+
+/// Follows the logs of one or more containers in the background, like
+/// `kubectl logs -f`. Lines from several containers are prefixed with their source.
+pub struct LogStream {
+    rx: Receiver<String>,
+    stop: Arc<AtomicBool>,
+}
+
+impl LogStream {
+    /// `targets` are `(pod, container)` pairs in `namespace`.
+    pub fn spawn(client: Arc<KubeClient>, namespace: &str, targets: Vec<(String, String)>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let prefixed = targets.len() > 1;
+
+        for (pod, container) in targets {
+            let client = client.clone();
+            let tx = tx.clone();
+            let stop = stop.clone();
+            let path = format!(
+                "/api/v1/namespaces/{}/pods/{}/log?follow=true&tailLines={}&container={}",
+                namespace, pod, TAIL_LINES, container
+            );
+            let prefix = if prefixed {
+                format!("[{}/{}] ", pod, container)
+            } else {
+                String::new()
+            };
+
+            thread::spawn(move || {
+                let reader = match client.stream(&path) {
+                    Ok(reader) => reader,
+                    Err(e) => {
+                        let _ = tx.send(format!("{}<error: {}>", prefix, e));
+                        return;
+                    }
+                };
+                for line in reader.lines() {
+                    let Ok(line) = line else { break };
+                    if stop.load(Ordering::Relaxed)
+                        || tx.send(format!("{}{}", prefix, line)).is_err()
+                    {
+                        return;
+                    }
+                }
+                if !stop.load(Ordering::Relaxed) {
+                    let _ = tx.send(format!("{}<log stream ended>", prefix));
+                }
+            });
+        }
+
+        LogStream { rx, stop }
+    }
+
+    /// Returns the lines received since the last call.
+    pub fn poll(&mut self) -> Vec<String> {
+        self.rx.try_iter().collect()
+    }
+}
+
+impl Drop for LogStream {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,307 @@
+use super::{client::KubeClient, ObjectMeta};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    sync::{
+        mpsc::{self, Receiver},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+const METRICS_PATH: &str = "/apis/metrics.k8s.io/v1beta1/pods";
+const POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Deserialize, Clone, Default)]
+pub struct ResourceList {
+    pub cpu: Option<String>,
+    pub memory: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PodMetricsList {
+    #[serde(default)]
+    items: Vec<PodMetrics>,
+}
+
+#[derive(Deserialize)]
+struct PodMetrics {
+    metadata: ObjectMeta,
+    #[serde(default)]
+    containers: Vec<ContainerMetrics>,
+}
+
+#[derive(Deserialize)]
+struct ContainerMetrics {
+    name: String,
+    #[serde(default)]
+    usage: ResourceList,
+}
+
+/// Usage per pod, keyed by `(namespace, name)`.
+pub type PodMetricsMap = HashMap<(String, String), PodUsage>;
+
+/// Current usage of one pod, summed over its containers.
+#[derive(Clone, Default)]
+pub struct PodUsage {
+    pub cpu_millis: f64,
+    pub memory_bytes: f64,
+    pub containers: Vec<ContainerUsage>,
+}
+
+#[derive(Clone)]
+pub struct ContainerUsage {
+    pub name: String,
+    pub cpu_millis: f64,
+    pub memory_bytes: f64,
+}
+
+/// Polls `metrics.k8s.io` in the background; the metrics API cannot be watched.
+pub struct MetricsPoller {
+    rx: Receiver<Result<PodMetricsMap, String>>,
+}
+
+impl MetricsPoller {
+    pub fn spawn(client: Arc<KubeClient>) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || loop {
+            let result = client
+                .get_json::<PodMetricsList>(METRICS_PATH)
+                .map(|list| {
+                    list.items
+                        .into_iter()
+                        .map(|pod| {
+                            let key = (
+                                pod.metadata.namespace.unwrap_or_default(),
+                                pod.metadata.name,
+                            );
+                            (key, PodUsage::from_containers(pod.containers))
+                        })
+                        .collect()
+                })
+                .map_err(|e| format!("Metrics API unavailable: {}", e));
+            if tx.send(result).is_err() {
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        });
+
+        MetricsPoller { rx }
+    }
+
+    /// Returns the newest result received since the last call, if any.
+    pub fn poll(&mut self) -> Option<Result<PodMetricsMap, String>> {
+        self.rx.try_iter().last()
+    }
+}
+
+impl PodUsage {
+    fn from_containers(containers: Vec<ContainerMetrics>) -> Self {
+        let containers: Vec<ContainerUsage> = containers
+            .into_iter()
+            .map(|c| ContainerUsage {
+                name: c.name,
+                cpu_millis: c.usage.cpu.as_deref().and_then(parse_cpu).unwrap_or(0.0),
+                memory_bytes: c
+                    .usage
+                    .memory
+                    .as_deref()
+                    .and_then(parse_quantity)
+                    .unwrap_or(0.0),
+            })
+            .collect();
+
+        PodUsage {
+            cpu_millis: containers.iter().map(|c| c.cpu_millis).sum(),
+            memory_bytes: containers.iter().map(|c| c.memory_bytes).sum(),
+            containers,
+        }
+    }
+}
+
+/// Parses a Kubernetes quantity (`250m`, `1.5`, `128Mi`, `1e3`, `12345n`) into base units.
+pub fn parse_quantity(quantity: &str) -> Option<f64> {
+    let quantity = quantity.trim();
+    let split = quantity
+        .find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-' || c == '+'))
+        .unwrap_or(quantity.len());
+    let (number, suffix) = quantity.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier = match suffix {
+        "" => 1.0,
+        "n" => 1e-9,
+        "u" => 1e-6,
+        "m" => 1e-3,
+        "k" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        "P" => 1e15,
+        "E" => 1e18,
+        "Ki" => 1024.0,
+        "Mi" => 1024.0_f64.powi(2),
+        "Gi" => 1024.0_f64.powi(3),
+        "Ti" => 1024.0_f64.powi(4),
+        "Pi" => 1024.0_f64.powi(5),
+        "Ei" => 1024.0_f64.powi(6),
+        s if s.starts_with('e') || s.starts_with('E') => 10f64.powi(s[1..].parse().ok()?),
+        _ => return None,
+    };
+
+    Some(number * multiplier)
+}
+
+pub fn parse_cpu(quantity: &str) -> Option<f64> {
+    parse_quantity(quantity).map(|cores| cores * 1000.0)
+}
+
+/// Formats millicores like `kubectl top` (`250m`, `1500m`).
+pub fn format_cpu(millis: f64) -> String {
+    format!("{:.0}m", millis)
+}
+
+/// Formats bytes with binary suffixes like `kubectl top` (`512Ki`, `128Mi`, `2Gi`).
+pub fn format_memory(bytes: f64) -> String {
+    const UNITS: [&str; 4] = ["Ki", "Mi", "Gi", "Ti"];
+    let mut value = bytes / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if value >= 100.0 || unit < 2 || value.fract() == 0.0 {
+        format!("{:.0}{}", value, UNITS[unit])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(actual: Option<f64>, expected: f64) -> bool {
+        actual.is_some_and(|actual| (actual - expected).abs() <= expected.abs() * 1e-9)
+    }
+
+    #[test]
+    fn parses_quantities() {
+        let cases = [
+            ("0", 0.0),
+            ("2", 2.0),
+            ("1.5", 1.5),
+            (" 3 ", 3.0),
+            ("+4", 4.0),
+            ("12345n", 12345e-9),
+            ("250u", 250e-6),
+            ("250m", 0.25),
+            ("1k", 1e3),
+            ("5M", 5e6),
+            ("2G", 2e9),
+            ("1T", 1e12),
+            ("1P", 1e15),
+            ("1E", 1e18),
+            ("512Ki", 512.0 * 1024.0),
+            ("128Mi", 128.0 * 1024.0 * 1024.0),
+            ("1.5Gi", 1.5 * 1024.0 * 1024.0 * 1024.0),
+            ("2Ti", 2.0 * 1024f64.powi(4)),
+            ("1Pi", 1024f64.powi(5)),
+            ("1Ei", 1024f64.powi(6)),
+            ("1e3", 1e3),
+            ("1E3", 1e3),
+            ("5e-3", 5e-3),
+            ("2.5e+2", 250.0),
+        ];
+        for (quantity, expected) in cases {
+            assert!(
+                close(parse_quantity(quantity), expected),
+                "{} parsed as {:?}, expected {}",
+                quantity,
+                parse_quantity(quantity),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn milli_is_not_mebi() {
+        assert!(close(parse_quantity("100m"), 0.1));
+        assert!(close(parse_quantity("100Mi"), 100.0 * 1024.0 * 1024.0));
+        assert!(close(parse_quantity("100M"), 100e6));
+    }
+
+    #[test]
+    fn rejects_invalid_quantities() {
+        for quantity in ["", "Mi", "abc", "12KB", "1mi", "1eX", "1.2.3"] {
+            assert_eq!(parse_quantity(quantity), None, "{}", quantity);
+        }
+    }
+
+    #[test]
+    fn parses_cpu_to_millicores() {
+        let cases = [
+            ("1", 1000.0),
+            ("250m", 250.0),
+            ("0.1", 100.0),
+            ("1500000u", 1500.0),
+            ("12345678n", 12.345678),
+        ];
+        for (quantity, expected) in cases {
+            assert!(close(parse_cpu(quantity), expected), "{}", quantity);
+        }
+    }
+
+    #[test]
+    fn formats_cpu() {
+        assert_eq!(format_cpu(250.0), "250m");
+        assert_eq!(format_cpu(12.6), "13m");
+        assert_eq!(format_cpu(1500.0), "1500m");
+    }
+
+    #[test]
+    fn formats_memory() {
+        let mi = 1024.0 * 1024.0;
+        let cases = [
+            (0.0, "0Ki"),
+            (512.0 * 1024.0, "512Ki"),
+            (1023.0 * 1024.0, "1023Ki"),
+            (mi, "1Mi"),
+            (1.5 * mi, "2Mi"),
+            (128.0 * mi, "128Mi"),
+            (1024.0 * mi, "1Gi"),
+            (1.5 * 1024.0 * mi, "1.5Gi"),
+            (150.0 * 1024.0 * mi, "150Gi"),
+            (2.25 * 1024.0 * 1024.0 * mi, "2.2Ti"),
+            (4096.0 * 1024.0 * 1024.0 * mi, "4096Ti"),
+        ];
+        for (bytes, expected) in cases {
+            assert_eq!(format_memory(bytes), expected, "{} bytes", bytes);
+        }
+    }
+
+    #[test]
+    fn sums_container_usage() {
+        let usage = PodUsage::from_containers(vec![
+            ContainerMetrics {
+                name: "app".to_string(),
+                usage: ResourceList {
+                    cpu: Some("150000000n".to_string()),
+                    memory: Some("64Mi".to_string()),
+                },
+            },
+            ContainerMetrics {
+                name: "proxy".to_string(),
+                usage: ResourceList {
+                    cpu: Some("5m".to_string()),
+                    memory: None,
+                },
+            },
+        ]);
+
+        assert!((usage.cpu_millis - 155.0).abs() < 1e-9);
+        assert_eq!(usage.memory_bytes, 64.0 * 1024.0 * 1024.0);
+        assert_eq!(usage.containers.len(), 2);
+    }
+}
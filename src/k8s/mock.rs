@@ -0,0 +1,208 @@
+//! A throwaway HTTP server standing in for the API server in tests.
+
+use super::config::KubeConfig;
+use std::{
+    env, fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    path::PathBuf,
+    process,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+#[derive(Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+pub struct Response {
+    status: u16,
+    body: String,
+    /// Keeps the connection open after the body, like a watch with nothing to report.
+    hold: Duration,
+}
+
+impl Response {
+    pub fn json(body: serde_json::Value) -> Self {
+        Response {
+            status: 200,
+            body: body.to_string(),
+            hold: Duration::ZERO,
+        }
+    }
+
+    /// A watch stream: one JSON event per line.
+    pub fn events(events: &[serde_json::Value]) -> Self {
+        Response {
+            status: 200,
+            body: events.iter().map(|event| format!("{}\n", event)).collect(),
+            hold: Duration::ZERO,
+        }
+    }
+
+    /// A `Status` object, as the API server sends with errors.
+    pub fn status(status: u16, message: &str) -> Self {
+        Response {
+            status,
+            body: serde_json::json!({
+                "kind": "Status",
+                "status": "Failure",
+                "message": message,
+                "code": status,
+            })
+            .to_string(),
+            hold: Duration::ZERO,
+        }
+    }
+
+    pub fn hold(mut self, duration: Duration) -> Self {
+        self.hold = duration;
+        self
+    }
+}
+
+pub struct MockServer {
+    pub url: String,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl MockServer {
+    /// Serves every request with `handler` until the test process exits.
+    pub fn start(handler: impl Fn(&Request) -> Response + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler = Arc::new(handler);
+
+        let recorded = requests.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let handler = handler.clone();
+                let recorded = recorded.clone();
+                thread::spawn(move || {
+                    if let Some(request) = read_request(&stream) {
+                        recorded.lock().unwrap().push(request.clone());
+                        write_response(stream, handler(&request));
+                    }
+                });
+            }
+        });
+
+        MockServer { url, requests }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// A config that talks to this server without credentials.
+    pub fn config(&self) -> KubeConfig {
+        Self::config_for(&self.url)
+    }
+
+    pub fn config_for(url: &str) -> KubeConfig {
+        KubeConfig {
+            context: "mock".to_string(),
+            namespace: "default".to_string(),
+            server: url.to_string(),
+            ca_pem: None,
+            insecure_skip_tls_verify: false,
+            tls_server_name: None,
+            token: None,
+            token_file: None,
+            basic_auth: None,
+            client_cert_pem: None,
+            client_key_pem: None,
+            exec: None,
+        }
+    }
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            headers.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let length = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body).ok()?;
+
+    Some(Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).to_string(),
+    })
+}
+
+fn write_response(mut stream: TcpStream, response: Response) {
+    // No Content-Length: the body ends when the connection closes, like a watch.
+    let head = format!(
+        "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n",
+        response.status
+    );
+    let _ = stream.write_all(head.as_bytes());
+    let _ = stream.write_all(response.body.as_bytes());
+    let _ = stream.flush();
+    thread::sleep(response.hold);
+}
+
+/// A fresh, empty directory under the system temp dir.
+pub fn temp_dir(name: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let dir = env::temp_dir().join(format!(
+        "k8s-test-{}-{}-{}",
+        process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed),
+        name
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+/// Polls `condition` until it holds, failing the test after a few seconds.
+pub fn wait_until(mut condition: impl FnMut() -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while !condition() {
+        assert!(Instant::now() < deadline, "timed out waiting for condition");
+        thread::sleep(Duration::from_millis(10));
+    }
+}
@@ -0,0 +1,169 @@
+mod client;
+mod config;
+mod configs;
+mod discovery;
+mod jobs;
+mod logs;
+mod metrics;
+#[cfg(test)]
+mod mock;
+mod network;
+mod objects;
+mod pods;
+mod portforward;
+mod task;
+mod watch;
+
+pub(crate) use client::InsecureVerifier;
+pub use client::KubeClient;
+pub use configs::{mask_secret_values, ConfigMap, K8sDataObject, Secret};
+pub use discovery::{
+    printer_columns, ApiResource, DiscoveryTask, DynamicObject, GenericRow, PrinterColumn,
+};
+pub use jobs::{set_cron_job_suspended, trigger_cron_job, CronJob, Job, K8sCronJob, K8sJob};
+pub use logs::LogStream;
+pub use metrics::{format_cpu, format_memory, MetricsPoller, PodMetricsMap};
+pub use network::{
+    format_selector, service_endpoints, EndpointSlice, Ingress, K8sIngress, K8sService, Service,
+};
+pub use objects::{
+    api_path, get_manifest, manifest_to_yaml, owning_workload, strip_managed_fields,
+};
+pub use pods::{ContainerResources, K8sPod, Pod};
+pub use portforward::{ForwardTarget, PortForward};
+pub use task::KubeTask;
+pub use watch::{Resource, Watcher};
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ObjectMeta {
+    #[serde(default)]
+    pub name: String,
+    pub namespace: Option<String>,
+    pub uid: Option<String>,
+    pub creation_timestamp: Option<DateTime<Utc>>,
+    pub deletion_timestamp: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    #[serde(default)]
+    pub owner_references: Vec<OwnerReference>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OwnerReference {
+    pub api_version: String,
+    pub kind: String,
+    pub name: String,
+    #[serde(default)]
+    pub controller: bool,
+}
+
+/// Formats the time since `timestamp` the way kubectl prints ages (`45s`, `5m10s`, `3h`, `12d`).
+pub fn format_age(timestamp: Option<DateTime<Utc>>) -> String {
+    match timestamp {
+        Some(timestamp) => human_duration(Utc::now() - timestamp),
+        None => "<unknown>".to_string(),
+    }
+}
+
+fn human_duration(duration: chrono::Duration) -> String {
+    let seconds = duration.num_seconds();
+    if seconds < -1 {
+        return "<invalid>".to_string();
+    } else if seconds < 0 {
+        return "0s".to_string();
+    } else if seconds < 60 * 2 {
+        return format!("{}s", seconds);
+    }
+
+    let minutes = seconds / 60;
+    if minutes < 10 {
+        let s = seconds % 60;
+        return if s == 0 {
+            format!("{}m", minutes)
+        } else {
+            format!("{}m{}s", minutes, s)
+        };
+    } else if minutes < 60 * 3 {
+        return format!("{}m", minutes);
+    }
+
+    let hours = minutes / 60;
+    if hours < 8 {
+        let m = minutes % 60;
+        if m == 0 {
+            format!("{}h", hours)
+        } else {
+            format!("{}h{}m", hours, m)
+        }
+    } else if hours < 48 {
+        format!("{}h", hours)
+    } else if hours < 24 * 8 {
+        let h = hours % 24;
+        if h == 0 {
+            format!("{}d", hours / 24)
+        } else {
+            format!("{}d{}h", hours / 24, h)
+        }
+    } else if hours < 24 * 365 * 2 {
+        format!("{}d", hours / 24)
+    } else if hours < 24 * 365 * 8 {
+        let days = (hours / 24) % 365;
+        if days == 0 {
+            format!("{}y", hours / 24 / 365)
+        } else {
+            format!("{}y{}d", hours / 24 / 365, days)
+        }
+    } else {
+        format!("{}y", hours / 24 / 365)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_durations_like_kubectl() {
+        let cases = [
+            (-5, "<invalid>"),
+            (-1, "0s"),
+            (0, "0s"),
+            (45, "45s"),
+            (119, "119s"),
+            (120, "2m"),
+            (310, "5m10s"),
+            (600, "10m"),
+            (179 * 60, "179m"),
+            (3 * 3600, "3h"),
+            (3 * 3600 + 25 * 60, "3h25m"),
+            (8 * 3600, "8h"),
+            (47 * 3600, "47h"),
+            (48 * 3600, "2d"),
+            (3 * 86400 + 5 * 3600, "3d5h"),
+            (8 * 86400, "8d"),
+            (729 * 86400, "729d"),
+            (730 * 86400, "2y"),
+            (3 * 365 * 86400 + 10 * 86400, "3y10d"),
+            (9 * 365 * 86400 + 10 * 86400, "9y"),
+        ];
+        for (seconds, expected) in cases {
+            assert_eq!(
+                human_duration(chrono::Duration::seconds(seconds)),
+                expected,
+                "{}s",
+                seconds
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_age() {
+        assert_eq!(format_age(None), "<unknown>");
+    }
+}
@@ -0,0 +1,462 @@
+use super::{format_age, ObjectMeta, Resource};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+const SERVICE_NAME_LABEL: &str = "kubernetes.io/service-name";
+
+#[derive(Deserialize, Clone)]
+pub struct Service {
+    pub metadata: ObjectMeta,
+    #[serde(default)]
+    pub spec: ServiceSpec,
+    #[serde(default)]
+    pub status: LoadBalancerHolder,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceSpec {
+    #[serde(rename = "type")]
+    pub service_type: Option<String>,
+    #[serde(rename = "clusterIP")]
+    pub cluster_ip: Option<String>,
+    #[serde(default, rename = "externalIPs")]
+    pub external_ips: Vec<String>,
+    pub external_name: Option<String>,
+    #[serde(default)]
+    pub selector: BTreeMap<String, String>,
+    #[serde(default)]
+    pub ports: Vec<ServicePort>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ServicePort {
+    pub port: u16,
+    pub target_port: Option<serde_json::Value>,
+    pub node_port: Option<u16>,
+    pub protocol: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadBalancerHolder {
+    #[serde(default)]
+    pub load_balancer: LoadBalancerStatus,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct LoadBalancerStatus {
+    #[serde(default)]
+    pub ingress: Vec<LoadBalancerIngress>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct LoadBalancerIngress {
+    pub ip: Option<String>,
+    pub hostname: Option<String>,
+}
+
+impl LoadBalancerStatus {
+    fn addresses(&self) -> Vec<String> {
+        self.ingress
+            .iter()
+            .filter_map(|i| i.ip.clone().or_else(|| i.hostname.clone()))
+            .collect()
+    }
+}
+
+impl Resource for Service {
+    fn metadata(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct EndpointSlice {
+    pub metadata: ObjectMeta,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub endpoints: Vec<Endpoint>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub ports: Vec<EndpointPort>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Endpoint {
+    #[serde(default)]
+    pub addresses: Vec<String>,
+    #[serde(default)]
+    pub conditions: EndpointConditions,
+    pub target_ref: Option<TargetRef>,
+    pub node_name: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct EndpointConditions {
+    pub ready: Option<bool>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct TargetRef {
+    pub kind: Option<String>,
+    pub name: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct EndpointPort {
+    pub name: Option<String>,
+    pub port: Option<u16>,
+    pub protocol: Option<String>,
+}
+
+impl Resource for EndpointSlice {
+    fn metadata(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+}
+
+#[derive(Deserialize, Clone)]
+pub struct Ingress {
+    pub metadata: ObjectMeta,
+    #[serde(default)]
+    pub spec: IngressSpec,
+    #[serde(default)]
+    pub status: LoadBalancerHolder,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IngressSpec {
+    pub ingress_class_name: Option<String>,
+    pub default_backend: Option<IngressBackend>,
+    #[serde(default)]
+    pub rules: Vec<IngressRule>,
+    #[serde(default)]
+    pub tls: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct IngressRule {
+    pub host: Option<String>,
+    pub http: Option<HttpIngressRuleValue>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct HttpIngressRuleValue {
+    #[serde(default)]
+    pub paths: Vec<HttpIngressPath>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HttpIngressPath {
+    pub path: Option<String>,
+    pub path_type: Option<String>,
+    pub backend: IngressBackend,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct IngressBackend {
+    pub service: Option<IngressServiceBackend>,
+    pub resource: Option<TargetRef>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct IngressServiceBackend {
+    pub name: String,
+    #[serde(default)]
+    pub port: ServiceBackendPort,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct ServiceBackendPort {
+    pub name: Option<String>,
+    pub number: Option<u16>,
+}
+
+impl Resource for Ingress {
+    fn metadata(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+}
+
+impl IngressBackend {
+    fn describe(&self) -> String {
+        match (&self.service, &self.resource) {
+            (Some(service), _) => {
+                let port = service
+                    .port
+                    .number
+                    .map(|n| n.to_string())
+                    .or_else(|| service.port.name.clone())
+                    .unwrap_or_default();
+                format!("{}:{}", service.name, port)
+            }
+            (None, Some(resource)) => format!(
+                "{}/{}",
+                resource.kind.clone().unwrap_or_default(),
+                resource.name.clone().unwrap_or_default()
+            ),
+            (None, None) => "<none>".to_string(),
+        }
+    }
+}
+
+/// A Service row, with columns computed like `kubectl get services -o wide`.
+#[derive(Clone)]
+pub struct K8sService {
+    pub name: String,
+    pub namespace: String,
+    pub service_type: String,
+    pub cluster_ip: String,
+    pub external_ip: String,
+    pub ports: String,
+    pub selector: BTreeMap<String, String>,
+    pub service_ports: Vec<u16>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+impl K8sService {
+    pub fn from_service(service: &Service) -> Self {
+        let spec = &service.spec;
+        let service_type = spec
+            .service_type
+            .clone()
+            .unwrap_or_else(|| "ClusterIP".to_string());
+
+        let mut external = spec.external_ips.clone();
+        let external_ip = match service_type.as_str() {
+            "ExternalName" => spec.external_name.clone().unwrap_or_default(),
+            "LoadBalancer" => {
+                let mut addresses = service.status.load_balancer.addresses();
+                addresses.append(&mut external);
+                if addresses.is_empty() {
+                    "<pending>".to_string()
+                } else {
+                    addresses.join(",")
+                }
+            }
+            _ if !external.is_empty() => external.join(","),
+            _ => "<none>".to_string(),
+        };
+
+        let ports = spec
+            .ports
+            .iter()
+            .map(|p| {
+                let protocol = p.protocol.as_deref().unwrap_or("TCP");
+                match p.node_port {
+                    Some(node_port) if node_port > 0 => {
+                        format!("{}:{}/{}", p.port, node_port, protocol)
+                    }
+                    _ => format!("{}/{}", p.port, protocol),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        K8sService {
+            name: service.metadata.name.clone(),
+            namespace: service.metadata.namespace.clone().unwrap_or_default(),
+            service_type,
+            cluster_ip: spec
+                .cluster_ip
+                .clone()
+                .filter(|ip| !ip.is_empty())
+                .unwrap_or_else(|| "<none>".to_string()),
+            external_ip,
+            ports: if ports.is_empty() {
+                "<none>".to_string()
+            } else {
+                ports
+            },
+            selector: spec.selector.clone(),
+            service_ports: spec.ports.iter().map(|p| p.port).collect(),
+            created: service.metadata.creation_timestamp,
+        }
+    }
+
+    pub fn selector_display(&self) -> String {
+        format_selector(&self.selector)
+    }
+
+    pub fn age(&self) -> String {
+        format_age(self.created)
+    }
+}
+
+/// One backing address of a Service, taken from its EndpointSlices.
+#[derive(Clone)]
+pub struct ServiceEndpoint {
+    pub address: String,
+    pub ports: String,
+    pub ready: bool,
+    pub target: String,
+    pub node: String,
+}
+
+/// Collects the endpoints of `namespace/service` from all of its EndpointSlices.
+pub fn service_endpoints<'a>(
+    slices: impl Iterator<Item = &'a EndpointSlice>,
+    namespace: &str,
+    service: &str,
+) -> Vec<ServiceEndpoint> {
+    let mut endpoints = Vec::new();
+    for slice in slices.filter(|s| {
+        s.metadata.namespace.as_deref() == Some(namespace)
+            && s.metadata
+                .labels
+                .get(SERVICE_NAME_LABEL)
+                .map(String::as_str)
+                == Some(service)
+    }) {
+        let ports = slice
+            .ports
+            .iter()
+            .map(|p| {
+                let port = p.port.map(|n| n.to_string()).unwrap_or_default();
+                let protocol = p.protocol.as_deref().unwrap_or("TCP");
+                match p.name.as_deref().filter(|n| !n.is_empty()) {
+                    Some(name) => format!("{} {}/{}", name, port, protocol),
+                    None => format!("{}/{}", port, protocol),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(",");
+
+        for endpoint in &slice.endpoints {
+            let target = endpoint
+                .target_ref
+                .as_ref()
+                .map(|t| {
+                    format!(
+                        "{}/{}",
+                        t.kind.clone().unwrap_or_default().to_lowercase(),
+                        t.name.clone().unwrap_or_default()
+                    )
+                })
+                .unwrap_or_else(|| "<none>".to_string());
+            for address in &endpoint.addresses {
+                endpoints.push(ServiceEndpoint {
+                    address: address.clone(),
+                    ports: ports.clone(),
+                    // A missing condition means ready, per the EndpointSlice API.
+                    ready: endpoint.conditions.ready.unwrap_or(true),
+                    target: target.clone(),
+                    node: endpoint
+                        .node_name
+                        .clone()
+                        .unwrap_or_else(|| "<none>".to_string()),
+                });
+            }
+        }
+    }
+    endpoints.sort_by(|a, b| b.ready.cmp(&a.ready).then(a.address.cmp(&b.address)));
+    endpoints
+}
+
+/// An Ingress row, with columns computed like `kubectl get ingress`.
+#[derive(Clone)]
+pub struct K8sIngress {
+    pub name: String,
+    pub namespace: String,
+    pub class: String,
+    pub hosts: String,
+    pub address: String,
+    pub ports: String,
+    pub rules: Vec<IngressPath>,
+    pub created: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+pub struct IngressPath {
+    pub host: String,
+    pub path: String,
+    pub path_type: String,
+    pub backend: String,
+}
+
+impl K8sIngress {
+    pub fn from_ingress(ingress: &Ingress) -> Self {
+        let spec = &ingress.spec;
+        let hosts: Vec<String> = spec
+            .rules
+            .iter()
+            .filter_map(|r| r.host.clone())
+            .filter(|h| !h.is_empty())
+            .collect();
+
+        let mut rules = Vec::new();
+        if let Some(backend) = &spec.default_backend {
+            rules.push(IngressPath {
+                host: "*".to_string(),
+                path: "(default)".to_string(),
+                path_type: String::new(),
+                backend: backend.describe(),
+            });
+        }
+        for rule in &spec.rules {
+            let host = rule
+                .host
+                .clone()
+                .filter(|h| !h.is_empty())
+                .unwrap_or_else(|| "*".to_string());
+            for path in rule.http.iter().flat_map(|http| &http.paths) {
+                rules.push(IngressPath {
+                    host: host.clone(),
+                    path: path.path.clone().unwrap_or_else(|| "/".to_string()),
+                    path_type: path.path_type.clone().unwrap_or_default(),
+                    backend: path.backend.describe(),
+                });
+            }
+        }
+
+        K8sIngress {
+            name: ingress.metadata.name.clone(),
+            namespace: ingress.metadata.namespace.clone().unwrap_or_default(),
+            class: spec
+                .ingress_class_name
+                .clone()
+                .unwrap_or_else(|| "<none>".to_string()),
+            hosts: if hosts.is_empty() {
+                "*".to_string()
+            } else {
+                hosts.join(",")
+            },
+            address: ingress.status.load_balancer.addresses().join(","),
+            ports: if spec.tls.is_empty() {
+                "80".to_string()
+            } else {
+                "80, 443".to_string()
+            },
+            rules,
+            created: ingress.metadata.creation_timestamp,
+        }
+    }
+
+    pub fn age(&self) -> String {
+        format_age(self.created)
+    }
+}
+
+/// Formats a label selector as `key=value,key=value`, or `<none>`.
+pub fn format_selector(selector: &BTreeMap<String, String>) -> String {
+    if selector.is_empty() {
+        return "<none>".to_string();
+    }
+    selector
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn null_as_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
@@ -0,0 +1,143 @@
+use super::{client::KubeClient, discovery::resource_plural, OwnerReference};
+use serde_json::Value;
+
+/// Builds the REST path of a named object, e.g. `/apis/apps/v1/namespaces/web/deployments/api`.
+pub fn api_path(api_version: &str, plural: &str, namespace: Option<&str>, name: &str) -> String {
+    let group = if api_version.contains('/') {
+        format!("/apis/{}", api_version)
+    } else {
+        format!("/api/{}", api_version)
+    };
+    match namespace {
+        Some(namespace) => format!("{}/namespaces/{}/{}/{}", group, namespace, plural, name),
+        None => format!("{}/{}/{}", group, plural, name),
+    }
+}
+
+/// Fetches an object and drops `metadata.managedFields`, which kubectl hides too.
+pub fn get_manifest(client: &KubeClient, path: &str) -> Result<Value, String> {
+    let mut manifest: Value = client.get_json(path)?;
+    strip_managed_fields(&mut manifest);
+    Ok(manifest)
+}
+
+pub fn strip_managed_fields(manifest: &mut Value) {
+    if let Some(metadata) = manifest.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+        metadata.remove("managedFields");
+    }
+}
+
+pub fn manifest_to_yaml(manifest: &Value) -> String {
+    serde_yaml::to_string(manifest).unwrap_or_else(|e| format!("# failed to render YAML: {}", e))
+}
+
+/// Follows controller owner references from a pod's owner up to the top-level workload
+/// (ReplicaSet -> Deployment, Job -> CronJob). Returns the kind and API path.
+pub fn owning_workload(
+    client: &KubeClient,
+    namespace: &str,
+    owner: &OwnerReference,
+) -> Result<(String, String), String> {
+    let mut kind = owner.kind.clone();
+    let mut path = owner_path(client, namespace, owner)?;
+
+    for _ in 0..3 {
+        let object: Value = client.get_json(&path)?;
+        let parent = object
+            .pointer("/metadata/ownerReferences")
+            .and_then(|refs| refs.as_array())
+            .and_then(|refs| {
+                refs.iter()
+                    .find(|r| r.get("controller").and_then(|c| c.as_bool()) == Some(true))
+            })
+            .and_then(|r| serde_json::from_value::<OwnerReference>(r.clone()).ok());
+
+        match parent {
+            Some(parent) => {
+                kind = parent.kind.clone();
+                path = owner_path(client, namespace, &parent)?;
+            }
+            None => break,
+        }
+    }
+
+    Ok((kind, path))
+}
+
+fn owner_path(
+    client: &KubeClient,
+    namespace: &str,
+    owner: &OwnerReference,
+) -> Result<String, String> {
+    let plural = resource_plural(client, &owner.api_version, &owner.kind)?;
+    Ok(api_path(
+        &owner.api_version,
+        &plural,
+        Some(namespace),
+        &owner.name,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::k8s::mock::{MockServer, Response};
+    use serde_json::json;
+
+    fn controller(api_version: &str, kind: &str, name: &str) -> Value {
+        json!({"metadata": {"ownerReferences": [
+            {"apiVersion": "v1", "kind": "ConfigMap", "name": "unrelated"},
+            {"apiVersion": api_version, "kind": kind, "name": name, "controller": true},
+        ]}})
+    }
+
+    #[test]
+    fn owning_workload_follows_controllers_with_discovered_plurals() {
+        let server = MockServer::start(|request| match request.path.as_str() {
+            "/apis/apps/v1" => Response::json(json!({"resources": [
+                {"name": "replicasets", "kind": "ReplicaSet"},
+                {"name": "replicasets/scale", "kind": "Scale"},
+            ]})),
+            "/apis/example.com/v1" => Response::json(json!({"resources": [
+                {"name": "proxies/status", "kind": "Proxy"},
+                {"name": "proxies", "kind": "Proxy"},
+            ]})),
+            "/apis/apps/v1/namespaces/web/replicasets/edge-5d8f" => {
+                Response::json(controller("example.com/v1", "Proxy", "edge"))
+            }
+            "/apis/example.com/v1/namespaces/web/proxies/edge" => {
+                Response::json(json!({"metadata": {"name": "edge"}}))
+            }
+            _ => Response::status(404, "not found"),
+        });
+        let client = KubeClient::new(server.config()).unwrap();
+        let owner = OwnerReference {
+            api_version: "apps/v1".to_string(),
+            kind: "ReplicaSet".to_string(),
+            name: "edge-5d8f".to_string(),
+            ..Default::default()
+        };
+
+        let (kind, path) = owning_workload(&client, "web", &owner).unwrap();
+
+        assert_eq!(kind, "Proxy");
+        assert_eq!(path, "/apis/example.com/v1/namespaces/web/proxies/edge");
+    }
+
+    #[test]
+    fn owning_workload_reports_unknown_kinds() {
+        let server = MockServer::start(|_| Response::json(json!({"resources": []})));
+        let client = KubeClient::new(server.config()).unwrap();
+        let owner = OwnerReference {
+            api_version: "v1".to_string(),
+            kind: "Gizmo".to_string(),
+            name: "g".to_string(),
+            ..Default::default()
+        };
+
+        let error = owning_workload(&client, "web", &owner).err().unwrap();
+
+        assert_eq!(error, "Gizmo is not served by v1");
+        assert_eq!(server.requests()[0].path, "/api/v1");
+    }
+}
@@ -0,0 +1,745 @@
+use super::{
+    format_age,
+    metrics::{parse_cpu, parse_quantity, ResourceList},
+    ObjectMeta, OwnerReference, Resource,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+const NODE_UNREACHABLE_REASON: &str = "NodeLost";
+
+#[derive(Deserialize, Clone)]
+pub struct Pod {
+    pub metadata: ObjectMeta,
+    #[serde(default)]
+    pub spec: PodSpec,
+    #[serde(default)]
+    pub status: PodStatus,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PodSpec {
+    pub node_name: Option<String>,
+    #[serde(default)]
+    pub containers: Vec<Container>,
+    #[serde(default)]
+    pub init_containers: Vec<Container>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Container {
+    pub name: String,
+    pub restart_policy: Option<String>,
+    #[serde(default)]
+    pub resources: ResourceRequirements,
+    #[serde(default)]
+    pub ports: Vec<ContainerPort>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerPort {
+    pub name: Option<String>,
+    pub container_port: u16,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct ResourceRequirements {
+    #[serde(default)]
+    pub requests: ResourceList,
+    #[serde(default)]
+    pub limits: ResourceList,
+}
+
+impl Container {
+    /// Native sidecars are init containers with `restartPolicy: Always`.
+    fn is_sidecar(&self) -> bool {
+        self.restart_policy.as_deref() == Some("Always")
+    }
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct PodStatus {
+    pub phase: Option<String>,
+    pub reason: Option<String>,
+    #[serde(rename = "podIP")]
+    pub pod_ip: Option<String>,
+    #[serde(default)]
+    pub conditions: Vec<PodCondition>,
+    #[serde(default)]
+    pub container_statuses: Vec<ContainerStatus>,
+    #[serde(default)]
+    pub init_container_statuses: Vec<ContainerStatus>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct PodCondition {
+    #[serde(rename = "type")]
+    pub condition_type: String,
+    pub status: String,
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerStatus {
+    pub name: String,
+    #[serde(default)]
+    pub ready: bool,
+    pub started: Option<bool>,
+    #[serde(default)]
+    pub restart_count: u32,
+    #[serde(default)]
+    pub state: ContainerState,
+    #[serde(default)]
+    pub last_state: ContainerState,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct ContainerState {
+    pub waiting: Option<ContainerStateWaiting>,
+    pub running: Option<serde_json::Value>,
+    pub terminated: Option<ContainerStateTerminated>,
+}
+
+#[derive(Deserialize, Clone)]
+pub struct ContainerStateWaiting {
+    pub reason: Option<String>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ContainerStateTerminated {
+    #[serde(default)]
+    pub exit_code: i32,
+    #[serde(default)]
+    pub signal: i32,
+    pub reason: Option<String>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ContainerStateTerminated {
+    /// The reason, or `ExitCode:N` / `Signal:N` when the runtime gave none.
+    fn describe(&self, prefix: &str) -> String {
+        match self.reason.as_deref() {
+            Some(reason) if !reason.is_empty() => format!("{}{}", prefix, reason),
+            _ if self.signal != 0 => format!("{}Signal:{}", prefix, self.signal),
+            _ => format!("{}ExitCode:{}", prefix, self.exit_code),
+        }
+    }
+}
+
+impl Resource for Pod {
+    fn metadata(&self) -> &ObjectMeta {
+        &self.metadata
+    }
+}
+
+impl Pod {
+    fn condition_is_true(&self, condition_type: &str) -> bool {
+        self.status
+            .conditions
+            .iter()
+            .any(|c| c.condition_type == condition_type && c.status == "True")
+    }
+}
+
+#[derive(Clone)]
+pub struct K8sPod {
+    pub name: String,
+    pub namespace: String,
+    pub status: String,
+    pub ready: String,
+    pub restarts: u32,
+    pub last_restart: Option<DateTime<Utc>>,
+    pub last_termination: Option<String>,
+    pub node: String,
+    pub ip: String,
+    pub created: Option<DateTime<Utc>>,
+    pub containers: Vec<ContainerResources>,
+    pub ports: Vec<u16>,
+    pub owner: Option<OwnerReference>,
+    pub labels: BTreeMap<String, String>,
+}
+
+/// Requests and limits of one container, in millicores and bytes.
+#[derive(Clone)]
+pub struct ContainerResources {
+    pub name: String,
+    pub cpu_request: Option<f64>,
+    pub cpu_limit: Option<f64>,
+    pub memory_request: Option<f64>,
+    pub memory_limit: Option<f64>,
+}
+
+impl K8sPod {
+    /// Builds the row the same way `kubectl get pods` computes STATUS, READY and RESTARTS.
+    pub fn from_pod(pod: &Pod) -> Self {
+        let spec = &pod.spec;
+        let status = &pod.status;
+
+        let sidecars = spec
+            .init_containers
+            .iter()
+            .filter(|c| c.is_sidecar())
+            .count();
+        let total = spec.containers.len() + sidecars;
+        let mut ready = 0;
+
+        let mut reason = status
+            .reason
+            .clone()
+            .filter(|r| !r.is_empty())
+            .or_else(|| status.phase.clone())
+            .unwrap_or_else(|| "Unknown".to_string());
+        if status.conditions.iter().any(|c| {
+            c.condition_type == "PodScheduled" && c.reason.as_deref() == Some("SchedulingGated")
+        }) {
+            reason = "SchedulingGated".to_string();
+        }
+
+        let mut restarts = 0;
+        let mut last_restart = None;
+        let mut sidecar_restarts = 0;
+        let mut sidecar_last_restart = None;
+        let mut last_termination: Option<(Option<DateTime<Utc>>, String)> = None;
+
+        let mut initializing = false;
+        for (i, container) in status.init_container_statuses.iter().enumerate() {
+            let finished = last_finished(container);
+            restarts += container.restart_count;
+            last_restart = last_restart.max(finished);
+            track_termination(&mut last_termination, container);
+
+            let is_sidecar = spec
+                .init_containers
+                .iter()
+                .any(|c| c.name == container.name && c.is_sidecar());
+            if is_sidecar {
+                sidecar_restarts += container.restart_count;
+                sidecar_last_restart = sidecar_last_restart.max(finished);
+            }
+
+            match (&container.state.terminated, &container.state.waiting) {
+                (Some(terminated), _) if terminated.exit_code == 0 => continue,
+                _ if is_sidecar && container.started == Some(true) => {
+                    if container.ready {
+                        ready += 1;
+                    }
+                    continue;
+                }
+                (Some(terminated), _) => reason = terminated.describe("Init:"),
+                (None, Some(waiting))
+                    if waiting
+                        .reason
+                        .as_deref()
+                        .is_some_and(|r| !r.is_empty() && r != "PodInitializing") =>
+                {
+                    reason = format!("Init:{}", waiting.reason.clone().unwrap_or_default());
+                }
+                _ => reason = format!("Init:{}/{}", i, spec.init_containers.len()),
+            }
+            initializing = true;
+            break;
+        }
+
+        if !initializing || pod.condition_is_true("Initialized") {
+            restarts = sidecar_restarts;
+            last_restart = sidecar_last_restart;
+            let mut has_running = false;
+
+            for container in status.container_statuses.iter().rev() {
+                restarts += container.restart_count;
+                last_restart = last_restart.max(last_finished(container));
+                track_termination(&mut last_termination, container);
+
+                let state = &container.state;
+                if let Some(waiting_reason) = state
+                    .waiting
+                    .as_ref()
+                    .and_then(|w| w.reason.clone())
+                    .filter(|r| !r.is_empty())
+                {
+                    reason = waiting_reason;
+                } else if let Some(terminated) = &state.terminated {
+                    reason = terminated.describe("");
+                } else if container.ready && state.running.is_some() {
+                    has_running = true;
+                    ready += 1;
+                }
+            }
+
+            if reason == "Completed" && has_running {
+                reason = if pod.condition_is_true("Ready") {
+                    "Running".to_string()
+                } else {
+                    "NotReady".to_string()
+                };
+            }
+        }
+
+        let terminal = matches!(status.phase.as_deref(), Some("Succeeded") | Some("Failed"));
+        if pod.metadata.deletion_timestamp.is_some() {
+            if status.reason.as_deref() == Some(NODE_UNREACHABLE_REASON) {
+                reason = "Unknown".to_string();
+            } else if !terminal {
+                reason = "Terminating".to_string();
+            }
+        }
+
+        K8sPod {
+            name: pod.metadata.name.clone(),
+            namespace: pod.metadata.namespace.clone().unwrap_or_default(),
+            status: reason,
+            ready: format!("{}/{}", ready, total),
+            restarts,
+            last_restart,
+            last_termination: last_termination.map(|(_, reason)| reason),
+            node: spec
+                .node_name
+                .clone()
+                .unwrap_or_else(|| "<none>".to_string()),
+            ip: status
+                .pod_ip
+                .clone()
+                .unwrap_or_else(|| "<none>".to_string()),
+            created: pod.metadata.creation_timestamp,
+            containers: spec
+                .init_containers
+                .iter()
+                .filter(|c| c.is_sidecar())
+                .chain(&spec.containers)
+                .map(|c| ContainerResources {
+                    name: c.name.clone(),
+                    cpu_request: c.resources.requests.cpu.as_deref().and_then(parse_cpu),
+                    cpu_limit: c.resources.limits.cpu.as_deref().and_then(parse_cpu),
+                    memory_request: c
+                        .resources
+                        .requests
+                        .memory
+                        .as_deref()
+                        .and_then(parse_quantity),
+                    memory_limit: c
+                        .resources
+                        .limits
+                        .memory
+                        .as_deref()
+                        .and_then(parse_quantity),
+                })
+                .collect(),
+            ports: spec
+                .containers
+                .iter()
+                .flat_map(|c| c.ports.iter().map(|p| p.container_port))
+                .collect(),
+            owner: pod
+                .metadata
+                .owner_references
+                .iter()
+                .find(|o| o.controller)
+                .cloned(),
+            labels: pod.metadata.labels.clone(),
+        }
+    }
+
+    /// Pod-wide CPU limit, only when every container sets one.
+    pub fn cpu_limit(&self) -> Option<f64> {
+        sum_all(self.containers.iter().map(|c| c.cpu_limit))
+    }
+
+    /// Pod-wide memory limit, only when every container sets one.
+    pub fn memory_limit(&self) -> Option<f64> {
+        sum_all(self.containers.iter().map(|c| c.memory_limit))
+    }
+
+    pub fn age(&self) -> String {
+        format_age(self.created)
+    }
+
+    /// Whether the pod carries every label in `selector`, as a Service selector matches.
+    pub fn matches_selector(&self, selector: &BTreeMap<String, String>) -> bool {
+        selector
+            .iter()
+            .all(|(k, v)| self.labels.get(k).is_some_and(|value| value == v))
+    }
+
+    /// Restart count with the time of the last restart, e.g. `3 (5m ago)`.
+    pub fn restarts_display(&self) -> String {
+        match self.last_restart {
+            Some(_) if self.restarts > 0 => {
+                format!("{} ({} ago)", self.restarts, format_age(self.last_restart))
+            }
+            _ => self.restarts.to_string(),
+        }
+    }
+}
+
+fn last_finished(container: &ContainerStatus) -> Option<DateTime<Utc>> {
+    container
+        .last_state
+        .terminated
+        .as_ref()
+        .and_then(|t| t.finished_at)
+}
+
+/// Remembers the most recent previous termination across all containers.
+fn track_termination(
+    latest: &mut Option<(Option<DateTime<Utc>>, String)>,
+    container: &ContainerStatus,
+) {
+    if let Some(terminated) = &container.last_state.terminated {
+        let newer = match latest {
+            Some((finished, _)) => terminated.finished_at >= *finished,
+            None => true,
+        };
+        if newer {
+            *latest = Some((terminated.finished_at, terminated.describe("")));
+        }
+    }
+}
+
+fn sum_all(values: impl Iterator<Item = Option<f64>>) -> Option<f64> {
+    let mut total = 0.0;
+    let mut any = false;
+    for value in values {
+        total += value?;
+        any = true;
+    }
+    any.then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pod(value: serde_json::Value) -> K8sPod {
+        let mut value = value;
+        value["metadata"]["name"] = json!("web");
+        value["metadata"]["namespace"] = json!("default");
+        K8sPod::from_pod(&serde_json::from_value(value).unwrap())
+    }
+
+    fn running(name: &str, restarts: u32) -> serde_json::Value {
+        json!({ "name": name, "ready": true, "restartCount": restarts, "state": { "running": {} } })
+    }
+
+    fn two_containers() -> serde_json::Value {
+        json!([{ "name": "app" }, { "name": "proxy" }])
+    }
+
+    #[test]
+    fn running_pod() {
+        let row = pod(json!({
+            "spec": { "nodeName": "node-1", "containers": two_containers() },
+            "status": {
+                "phase": "Running",
+                "podIP": "10.0.0.7",
+                "containerStatuses": [running("app", 0), running("proxy", 0)],
+            },
+        }));
+
+        assert_eq!(row.status, "Running");
+        assert_eq!(row.ready, "2/2");
+        assert_eq!(row.restarts, 0);
+        assert_eq!(row.node, "node-1");
+        assert_eq!(row.ip, "10.0.0.7");
+    }
+
+    #[test]
+    fn unscheduled_pod() {
+        let row = pod(json!({
+            "spec": { "containers": [{ "name": "app" }] },
+            "status": { "phase": "Pending" },
+        }));
+
+        assert_eq!(row.status, "Pending");
+        assert_eq!(row.ready, "0/1");
+        assert_eq!(row.node, "<none>");
+        assert_eq!(row.ip, "<none>");
+    }
+
+    #[test]
+    fn scheduling_gated() {
+        let row = pod(json!({
+            "spec": { "containers": [{ "name": "app" }] },
+            "status": {
+                "phase": "Pending",
+                "conditions": [{ "type": "PodScheduled", "status": "False", "reason": "SchedulingGated" }],
+            },
+        }));
+
+        assert_eq!(row.status, "SchedulingGated");
+    }
+
+    #[test]
+    fn pod_reason_wins_over_phase() {
+        let row = pod(json!({
+            "spec": { "containers": [{ "name": "app" }] },
+            "status": { "phase": "Failed", "reason": "Evicted" },
+        }));
+
+        assert_eq!(row.status, "Evicted");
+    }
+
+    #[test]
+    fn waiting_reason_and_restarts() {
+        let last_finished = "2026-10-18T10:00:00Z";
+        let row = pod(json!({
+            "spec": { "containers": two_containers() },
+            "status": {
+                "phase": "Running",
+                "containerStatuses": [
+                    {
+                        "name": "app",
+                        "restartCount": 4,
+                        "state": { "waiting": { "reason": "CrashLoopBackOff" } },
+                        "lastState": { "terminated": { "exitCode": 1, "reason": "Error", "finishedAt": last_finished } },
+                    },
+                    running("proxy", 1),
+                ],
+            },
+        }));
+
+        assert_eq!(row.status, "CrashLoopBackOff");
+        assert_eq!(row.ready, "1/2");
+        assert_eq!(row.restarts, 5);
+        assert_eq!(row.last_restart, last_finished.parse().ok());
+        assert_eq!(row.last_termination.as_deref(), Some("Error"));
+    }
+
+    #[test]
+    fn first_container_decides_the_status() {
+        // kubectl walks the containers backwards, so the first one's reason wins.
+        let row = pod(json!({
+            "spec": { "containers": two_containers() },
+            "status": {
+                "phase": "Pending",
+                "containerStatuses": [
+                    { "name": "app", "state": { "waiting": { "reason": "ImagePullBackOff" } } },
+                    { "name": "proxy", "state": { "waiting": { "reason": "ContainerCreating" } } },
+                ],
+            },
+        }));
+
+        assert_eq!(row.status, "ImagePullBackOff");
+    }
+
+    #[test]
+    fn terminated_without_reason_shows_exit_code_or_signal() {
+        let exit_code = pod(json!({
+            "spec": { "containers": [{ "name": "app" }] },
+            "status": {
+                "phase": "Failed",
+                "containerStatuses": [{ "name": "app", "state": { "terminated": { "exitCode": 137 } } }],
+            },
+        }));
+        let signal = pod(json!({
+            "spec": { "containers": [{ "name": "app" }] },
+            "status": {
+                "phase": "Failed",
+                "containerStatuses": [{ "name": "app", "state": { "terminated": { "exitCode": 0, "signal": 9 } } }],
+            },
+        }));
+
+        assert_eq!(exit_code.status, "ExitCode:137");
+        assert_eq!(signal.status, "Signal:9");
+    }
+
+    #[test]
+    fn completed_with_running_container_is_not_ready_or_running() {
+        let status = |ready: &str| {
+            pod(json!({
+                "spec": { "containers": two_containers() },
+                "status": {
+                    "phase": "Running",
+                    "conditions": [{ "type": "Ready", "status": ready }],
+                    "containerStatuses": [
+                        running("app", 0),
+                        { "name": "proxy", "state": { "terminated": { "exitCode": 0, "reason": "Completed" } } },
+                    ],
+                },
+            }))
+            .status
+        };
+
+        assert_eq!(status("True"), "Running");
+        assert_eq!(status("False"), "NotReady");
+    }
+
+    #[test]
+    fn init_container_progress() {
+        let row = pod(json!({
+            "spec": {
+                "initContainers": [{ "name": "migrate" }, { "name": "seed" }],
+                "containers": [{ "name": "app" }],
+            },
+            "status": {
+                "phase": "Pending",
+                "initContainerStatuses": [
+                    { "name": "migrate", "state": { "terminated": { "exitCode": 0, "reason": "Completed" } } },
+                    { "name": "seed", "state": { "running": {} } },
+                ],
+                "containerStatuses": [
+                    { "name": "app", "state": { "waiting": { "reason": "PodInitializing" } } },
+                ],
+            },
+        }));
+
+        assert_eq!(row.status, "Init:1/2");
+        assert_eq!(row.ready, "0/1");
+    }
+
+    #[test]
+    fn init_container_waiting_and_failed() {
+        let waiting = pod(json!({
+            "spec": { "initContainers": [{ "name": "migrate" }], "containers": [{ "name": "app" }] },
+            "status": {
+                "phase": "Pending",
+                "initContainerStatuses": [
+                    { "name": "migrate", "restartCount": 2, "state": { "waiting": { "reason": "CrashLoopBackOff" } } },
+                ],
+            },
+        }));
+        let failed = pod(json!({
+            "spec": { "initContainers": [{ "name": "migrate" }], "containers": [{ "name": "app" }] },
+            "status": {
+                "phase": "Pending",
+                "initContainerStatuses": [
+                    { "name": "migrate", "state": { "terminated": { "exitCode": 3 } } },
+                ],
+            },
+        }));
+
+        assert_eq!(waiting.status, "Init:CrashLoopBackOff");
+        assert_eq!(waiting.restarts, 2);
+        assert_eq!(failed.status, "Init:ExitCode:3");
+    }
+
+    #[test]
+    fn native_sidecars_count_as_containers() {
+        let row = pod(json!({
+            "spec": {
+                "initContainers": [{ "name": "mesh", "restartPolicy": "Always" }],
+                "containers": [{ "name": "app" }],
+            },
+            "status": {
+                "phase": "Running",
+                "conditions": [{ "type": "Initialized", "status": "True" }],
+                "initContainerStatuses": [
+                    { "name": "mesh", "ready": true, "started": true, "restartCount": 2, "state": { "running": {} } },
+                ],
+                "containerStatuses": [running("app", 1)],
+            },
+        }));
+
+        assert_eq!(row.status, "Running");
+        assert_eq!(row.ready, "2/2");
+        assert_eq!(row.restarts, 3);
+        let names: Vec<&str> = row.containers.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["mesh", "app"]);
+    }
+
+    #[test]
+    fn deleted_pods_are_terminating() {
+        let row = pod(json!({
+            "metadata": { "deletionTimestamp": "2026-10-18T10:00:00Z" },
+            "spec": { "containers": [{ "name": "app" }] },
+            "status": { "phase": "Running", "containerStatuses": [running("app", 0)] },
+        }));
+
+        assert_eq!(row.status, "Terminating");
+    }
+
+    #[test]
+    fn finished_pods_keep_their_status_while_deleted() {
+        let row = pod(json!({
+            "metadata": { "deletionTimestamp": "2026-10-18T10:00:00Z" },
+            "spec": { "containers": [{ "name": "job" }] },
+            "status": {
+                "phase": "Succeeded",
+                "containerStatuses": [{ "name": "job", "state": { "terminated": { "exitCode": 0, "reason": "Completed" } } }],
+            },
+        }));
+
+        assert_eq!(row.status, "Completed");
+    }
+
+    #[test]
+    fn pods_on_lost_nodes_are_unknown() {
+        let row = pod(json!({
+            "metadata": { "deletionTimestamp": "2026-10-18T10:00:00Z" },
+            "spec": { "containers": [{ "name": "app" }] },
+            "status": { "phase": "Running", "reason": "NodeLost" },
+        }));
+
+        assert_eq!(row.status, "Unknown");
+    }
+
+    #[test]
+    fn latest_termination_across_containers() {
+        let row = pod(json!({
+            "spec": { "containers": two_containers() },
+            "status": {
+                "phase": "Running",
+                "containerStatuses": [
+                    {
+                        "name": "app", "ready": true, "restartCount": 1, "state": { "running": {} },
+                        "lastState": { "terminated": { "exitCode": 137, "reason": "OOMKilled", "finishedAt": "2026-10-18T09:00:00Z" } },
+                    },
+                    {
+                        "name": "proxy", "ready": true, "restartCount": 1, "state": { "running": {} },
+                        "lastState": { "terminated": { "exitCode": 1, "reason": "Error", "finishedAt": "2026-10-18T08:00:00Z" } },
+                    },
+                ],
+            },
+        }));
+
+        assert_eq!(row.last_termination.as_deref(), Some("OOMKilled"));
+        assert!(row.restarts_display().starts_with("2 ("));
+    }
+
+    #[test]
+    fn resources_and_pod_limits() {
+        let row = pod(json!({
+            "spec": {
+                "containers": [
+                    { "name": "app", "resources": { "requests": { "cpu": "250m", "memory": "64Mi" }, "limits": { "cpu": "1", "memory": "128Mi" } } },
+                    { "name": "proxy", "resources": { "limits": { "cpu": "500m" } } },
+                ],
+            },
+            "status": { "phase": "Running" },
+        }));
+
+        assert_eq!(row.containers[0].cpu_request, Some(250.0));
+        assert_eq!(
+            row.containers[0].memory_limit,
+            Some(128.0 * 1024.0 * 1024.0)
+        );
+        assert_eq!(row.cpu_limit(), Some(1500.0));
+        // Unbounded as soon as one container has no limit.
+        assert_eq!(row.memory_limit(), None);
+    }
+
+    #[test]
+    fn selector_matching() {
+        let row = pod(json!({
+            "metadata": { "labels": { "app": "web", "tier": "frontend" } },
+            "status": {},
+        }));
+        let selector = |pairs: &[(&str, &str)]| {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<BTreeMap<_, _>>()
+        };
+
+        assert!(row.matches_selector(&selector(&[("app", "web")])));
+        assert!(!row.matches_selector(&selector(&[("app", "web"), ("tier", "backend")])));
+        assert!(row.matches_selector(&selector(&[])));
+    }
+}
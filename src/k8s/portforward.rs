@@ -0,0 +1,565 @@
+use super::{
+    client::{KubeClient, KubeStream},
+    Pod, Service,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    io::{Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
+use tungstenite::{protocol::Role, Message, WebSocket};
+
+const PORT_FORWARD_PROTOCOLS: [&str; 2] = ["v4.channel.k8s.io", "portforward.k8s.io"];
+const DATA_CHANNEL: u8 = 0;
+const ERROR_CHANNEL: u8 = 1;
+
+#[derive(Clone)]
+pub enum ForwardTarget {
+    Pod { namespace: String, name: String },
+    Service { namespace: String, name: String },
+}
+
+impl ForwardTarget {
+    pub fn describe(&self) -> String {
+        match self {
+            ForwardTarget::Pod { namespace, name } => format!("pod/{}/{}", namespace, name),
+            ForwardTarget::Service { namespace, name } => format!("svc/{}/{}", namespace, name),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PodList {
+    #[serde(default)]
+    items: Vec<Pod>,
+}
+
+struct ForwardShared {
+    stop: AtomicBool,
+    bytes_in: AtomicU64,
+    bytes_out: AtomicU64,
+    connections: AtomicUsize,
+    /// State of the listener; a failing connection only sets `last_error`.
+    status: Mutex<String>,
+    last_error: Mutex<Option<(DateTime<Utc>, String)>>,
+    /// Both ends of every open tunnel, shut down on stop to wake their threads.
+    tunnels: Mutex<HashMap<u64, (TcpStream, KubeStream)>>,
+    next_tunnel: AtomicU64,
+}
+
+impl ForwardShared {
+    fn set_status(&self, status: &str) {
+        if let Ok(mut current) = self.status.lock() {
+            *current = status.to_string();
+        }
+    }
+
+    fn record_error(&self, error: String) {
+        if let Ok(mut last_error) = self.last_error.lock() {
+            *last_error = Some((Utc::now(), error));
+        }
+    }
+}
+
+/// A local TCP listener that tunnels every accepted connection to a pod port
+/// through the API server's `portforward` subresource.
+pub struct PortForward {
+    pub target: ForwardTarget,
+    pub pod: String,
+    pub local_port: u16,
+    pub remote_port: u16,
+    shared: Arc<ForwardShared>,
+}
+
+impl PortForward {
+    /// Binds `127.0.0.1:local_port` and starts forwarding. For services, the port is the
+    /// service port and is resolved to a ready backing pod and its target port.
+    pub fn start(
+        client: Arc<KubeClient>,
+        target: ForwardTarget,
+        local_port: u16,
+        remote_port: u16,
+    ) -> Result<Self, String> {
+        let (namespace, pod, pod_port) = match &target {
+            ForwardTarget::Pod { namespace, name } => {
+                (namespace.clone(), name.clone(), remote_port)
+            }
+            ForwardTarget::Service { namespace, name } => {
+                let (pod, port) = resolve_service(&client, namespace, name, remote_port)?;
+                (namespace.clone(), pod, port)
+            }
+        };
+
+        let listener = TcpListener::bind(("127.0.0.1", local_port))
+            .map_err(|e| format!("Cannot listen on 127.0.0.1:{}: {}", local_port, e))?;
+        let local_port = listener
+            .local_addr()
+            .map(|a| a.port())
+            .unwrap_or(local_port);
+
+        let shared = Arc::new(ForwardShared {
+            stop: AtomicBool::new(false),
+            bytes_in: AtomicU64::new(0),
+            bytes_out: AtomicU64::new(0),
+            connections: AtomicUsize::new(0),
+            status: Mutex::new("Listening".to_string()),
+            last_error: Mutex::new(None),
+            tunnels: Mutex::new(HashMap::new()),
+            next_tunnel: AtomicU64::new(0),
+        });
+
+        let thread_shared = shared.clone();
+        let thread_pod = pod.clone();
+        thread::spawn(move || {
+            accept_loop(
+                listener,
+                client,
+                namespace,
+                thread_pod,
+                pod_port,
+                thread_shared,
+            )
+        });
+
+        Ok(PortForward {
+            target,
+            pod,
+            local_port,
+            remote_port: pod_port,
+            shared,
+        })
+    }
+
+    /// Stops listening and closes the open connections.
+    pub fn stop(&self) {
+        if self.shared.stop.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        // Wakes the blocking accept, which then sees the stop flag.
+        let _ = TcpStream::connect(("127.0.0.1", self.local_port));
+        if let Ok(tunnels) = self.shared.tunnels.lock() {
+            for (local, remote) in tunnels.values() {
+                let _ = local.shutdown(Shutdown::Both);
+                remote.shutdown();
+            }
+        }
+    }
+
+    /// `Active` while connections are open, otherwise the listener's state.
+    pub fn status(&self) -> String {
+        let status = self
+            .shared
+            .status
+            .lock()
+            .map(|s| s.clone())
+            .unwrap_or_default();
+        if status == "Listening" && self.connections() > 0 {
+            "Active".to_string()
+        } else {
+            status
+        }
+    }
+
+    /// The most recent connection that failed, and when.
+    pub fn last_error(&self) -> Option<(DateTime<Utc>, String)> {
+        self.shared.last_error.lock().ok()?.clone()
+    }
+
+    pub fn bytes_in(&self) -> u64 {
+        self.shared.bytes_in.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_out(&self) -> u64 {
+        self.shared.bytes_out.load(Ordering::Relaxed)
+    }
+
+    pub fn connections(&self) -> usize {
+        self.shared.connections.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for PortForward {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn accept_loop(
+    listener: TcpListener,
+    client: Arc<KubeClient>,
+    namespace: String,
+    pod: String,
+    port: u16,
+    shared: Arc<ForwardShared>,
+) {
+    let path = format!(
+        "/api/v1/namespaces/{}/pods/{}/portforward?ports={}",
+        namespace, pod, port
+    );
+    for stream in listener.incoming() {
+        if shared.stop.load(Ordering::Relaxed) {
+            break;
+        }
+        match stream {
+            Ok(stream) => {
+                shared.set_status("Listening");
+                let client = client.clone();
+                let shared = shared.clone();
+                let path = path.clone();
+                thread::spawn(move || {
+                    shared.connections.fetch_add(1, Ordering::Relaxed);
+                    if let Err(e) = tunnel(&client, &path, stream, &shared) {
+                        shared.record_error(e);
+                    }
+                    shared.connections.fetch_sub(1, Ordering::Relaxed);
+                });
+            }
+            Err(e) => {
+                shared.set_status(&format!("Error: {}", e));
+                thread::sleep(Duration::from_millis(500));
+            }
+        }
+    }
+    shared.set_status("Stopped");
+}
+
+/// Pumps bytes between one local connection and one port-forward WebSocket,
+/// with a blocking thread for each direction.
+fn tunnel(
+    client: &KubeClient,
+    path: &str,
+    local: TcpStream,
+    shared: &ForwardShared,
+) -> Result<(), String> {
+    let mut socket = client.websocket(path, &PORT_FORWARD_PROTOCOLS)?;
+    let remote = socket.get_ref().try_clone().map_err(|e| e.to_string())?;
+    let writer = WebSocket::from_raw_socket(
+        remote.try_clone().map_err(|e| e.to_string())?,
+        Role::Client,
+        None,
+    );
+    let local_reader = local.try_clone().map_err(|e| e.to_string())?;
+
+    let id = shared.next_tunnel.fetch_add(1, Ordering::Relaxed);
+    if let Ok(mut tunnels) = shared.tunnels.lock() {
+        let local = local.try_clone().map_err(|e| e.to_string())?;
+        let remote = remote.try_clone().map_err(|e| e.to_string())?;
+        tunnels.insert(id, (local, remote));
+    }
+    // `stop` may have run before the tunnel was registered.
+    if shared.stop.load(Ordering::Relaxed) {
+        let _ = local.shutdown(Shutdown::Both);
+        remote.shutdown();
+    }
+
+    // Set once the local side is done, so the download treats a torn-down socket as the end.
+    let closing = AtomicBool::new(false);
+    let result = thread::scope(|scope| {
+        scope.spawn(|| upload(local_reader, writer, &remote, &closing, shared));
+        let result = download(&mut socket, &local, &closing, shared);
+        // Ends the upload too, if the pod side finished first.
+        let _ = local.shutdown(Shutdown::Both);
+        result
+    });
+
+    if let Ok(mut tunnels) = shared.tunnels.lock() {
+        tunnels.remove(&id);
+    }
+    remote.shutdown();
+    result
+}
+
+/// Local connection to pod: every chunk read becomes one frame on the data channel.
+fn upload(
+    mut local: TcpStream,
+    mut writer: WebSocket<KubeStream>,
+    remote: &KubeStream,
+    closing: &AtomicBool,
+    shared: &ForwardShared,
+) {
+    let mut buffer = vec![0u8; 32 * 1024];
+    loop {
+        match local.read(&mut buffer) {
+            Ok(0) => {
+                // The local client is done; the server answers the close and
+                // the download side finishes with it.
+                closing.store(true, Ordering::Relaxed);
+                let _ = writer.close(None);
+                let _ = writer.flush();
+                return;
+            }
+            Ok(n) => {
+                let mut frame = Vec::with_capacity(n + 1);
+                frame.push(DATA_CHANNEL);
+                frame.extend_from_slice(&buffer[..n]);
+                if writer.send(Message::Binary(frame)).is_err() {
+                    break;
+                }
+                shared.bytes_out.fetch_add(n as u64, Ordering::Relaxed);
+            }
+            Err(_) => break,
+        }
+    }
+    closing.store(true, Ordering::Relaxed);
+    remote.shutdown();
+}
+
+/// Pod to local connection. Every frame starts with a channel byte; the first
+/// frame on each channel carries the port number instead of data.
+fn download(
+    socket: &mut WebSocket<KubeStream>,
+    mut local: &TcpStream,
+    closing: &AtomicBool,
+    shared: &ForwardShared,
+) -> Result<(), String> {
+    let mut port_header_seen = [false; 2];
+    loop {
+        match socket.read() {
+            Ok(Message::Binary(data)) if !data.is_empty() => {
+                let channel = data[0];
+                let mut payload = &data[1..];
+                if let Some(seen) = port_header_seen.get_mut(channel as usize) {
+                    if !*seen {
+                        *seen = true;
+                        payload = payload.get(2..).unwrap_or_default();
+                    }
+                }
+                match channel {
+                    DATA_CHANNEL if !payload.is_empty() => {
+                        if local.write_all(payload).is_err() {
+                            return Ok(());
+                        }
+                        shared
+                            .bytes_in
+                            .fetch_add(payload.len() as u64, Ordering::Relaxed);
+                    }
+                    ERROR_CHANNEL if !payload.is_empty() => {
+                        return Err(String::from_utf8_lossy(payload).to_string());
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Message::Close(_)) => {
+                // Sends the queued close reply.
+                let _ = socket.flush();
+                return Ok(());
+            }
+            Ok(_) => {}
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+                return Ok(())
+            }
+            // Shut down from the upload side or by `stop`.
+            Err(_) if closing.load(Ordering::Relaxed) || shared.stop.load(Ordering::Relaxed) => {
+                return Ok(())
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+/// Picks a running pod behind the service and maps the service port to its target port,
+/// the same way `kubectl port-forward svc/...` does.
+fn resolve_service(
+    client: &KubeClient,
+    namespace: &str,
+    name: &str,
+    service_port: u16,
+) -> Result<(String, u16), String> {
+    let service: Service = client.get_json(&format!(
+        "/api/v1/namespaces/{}/services/{}",
+        namespace, name
+    ))?;
+    if service.spec.selector.is_empty() {
+        return Err(format!("Service {} has no selector", name));
+    }
+
+    let selector = service
+        .spec
+        .selector
+        .iter()
+        .map(|(k, v)| format!("{}%3D{}", k, v))
+        .collect::<Vec<_>>()
+        .join("%2C");
+    let pods: PodList = client.get_json(&format!(
+        "/api/v1/namespaces/{}/pods?labelSelector={}",
+        namespace, selector
+    ))?;
+    let pod = pods
+        .items
+        .into_iter()
+        .find(|p| {
+            p.status.phase.as_deref() == Some("Running") && p.metadata.deletion_timestamp.is_none()
+        })
+        .ok_or_else(|| format!("No running pods behind service {}", name))?;
+
+    let service_port_spec = service
+        .spec
+        .ports
+        .iter()
+        .find(|p| p.port == service_port)
+        .ok_or_else(|| format!("Service {} has no port {}", name, service_port))?;
+    let target_port = match &service_port_spec.target_port {
+        Some(serde_json::Value::Number(n)) => n.as_u64().map(|n| n as u16),
+        Some(serde_json::Value::String(port_name)) => pod
+            .spec
+            .containers
+            .iter()
+            .flat_map(|c| &c.ports)
+            .find(|p| p.name.as_deref() == Some(port_name.as_str()))
+            .map(|p| p.container_port),
+        _ => Some(service_port),
+    }
+    .ok_or_else(|| format!("Cannot resolve target port of {}:{}", name, service_port))?;
+
+    Ok((pod.metadata.name, target_port))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::k8s::mock::wait_until;
+    use crate::k8s::KubeClient;
+    use std::io::ErrorKind;
+    use tungstenite::handshake::server::{Request, Response};
+
+    /// An API server whose `portforward` WebSocket is served by `pod`.
+    fn mock_api(pod: fn(&mut WebSocket<TcpStream>)) -> (Arc<KubeClient>, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let paths = Arc::new(Mutex::new(Vec::new()));
+        let recorded = paths.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let recorded = recorded.clone();
+                thread::spawn(move || {
+                    // The signature is tungstenite's; its error type is a whole response.
+                    #[allow(clippy::result_large_err)]
+                    let callback = |request: &Request, mut response: Response| {
+                        recorded.lock().unwrap().push(request.uri().to_string());
+                        response.headers_mut().insert(
+                            "Sec-WebSocket-Protocol",
+                            "v4.channel.k8s.io".parse().unwrap(),
+                        );
+                        Ok(response)
+                    };
+                    let mut socket = tungstenite::accept_hdr(stream, callback).unwrap();
+                    // The port number opens both channels, little-endian.
+                    for channel in [DATA_CHANNEL, ERROR_CHANNEL] {
+                        socket.send(Message::Binary(vec![channel, 80, 0])).unwrap();
+                    }
+                    pod(&mut socket);
+                });
+            }
+        });
+
+        let client = KubeClient::new(crate::k8s::mock::MockServer::config_for(&url)).unwrap();
+        (Arc::new(client), paths)
+    }
+
+    /// Answers every chunk on the data channel in upper case.
+    fn shouting_pod(socket: &mut WebSocket<TcpStream>) {
+        while let Ok(message) = socket.read() {
+            if let Message::Binary(data) = message {
+                let mut reply = vec![DATA_CHANNEL];
+                reply.extend(data[1..].to_ascii_uppercase());
+                if socket.send(Message::Binary(reply)).is_err() {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn refusing_pod(socket: &mut WebSocket<TcpStream>) {
+        let mut frame = vec![ERROR_CHANNEL];
+        frame.extend_from_slice(b"connection refused on port 80");
+        let _ = socket.send(Message::Binary(frame));
+        let _ = socket.close(None);
+        while socket.read().is_ok() {}
+    }
+
+    fn forward(client: Arc<KubeClient>) -> PortForward {
+        let target = ForwardTarget::Pod {
+            namespace: "default".to_string(),
+            name: "web".to_string(),
+        };
+        PortForward::start(client, target, 0, 80).unwrap()
+    }
+
+    fn connect(forward: &PortForward) -> TcpStream {
+        let stream = TcpStream::connect(("127.0.0.1", forward.local_port)).unwrap();
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .unwrap();
+        stream
+    }
+
+    #[test]
+    fn tunnels_both_directions() {
+        let (client, paths) = mock_api(shouting_pod);
+        let forward = forward(client);
+        assert_eq!(forward.status(), "Listening");
+
+        let mut local = connect(&forward);
+        local.write_all(b"ping").unwrap();
+        let mut reply = [0u8; 4];
+        local.read_exact(&mut reply).unwrap();
+
+        assert_eq!(&reply, b"PING");
+        assert_eq!(forward.status(), "Active");
+        // Counters are bumped once a chunk has been passed on, so may trail the reply.
+        wait_until(|| forward.bytes_out() == 4 && forward.bytes_in() == 4);
+        assert_eq!(
+            paths.lock().unwrap().as_slice(),
+            ["/api/v1/namespaces/default/pods/web/portforward?ports=80"]
+        );
+
+        drop(local);
+        wait_until(|| forward.connections() == 0);
+        assert_eq!(forward.status(), "Listening");
+        assert!(forward.last_error().is_none());
+    }
+
+    #[test]
+    fn failed_connection_does_not_change_listener_state() {
+        let (client, _) = mock_api(refusing_pod);
+        let forward = forward(client);
+
+        let mut local = connect(&forward);
+        let mut buffer = [0u8; 16];
+        // The tunnel closes the local connection when the pod refuses it.
+        assert!(matches!(local.read(&mut buffer), Ok(0) | Err(_)));
+        wait_until(|| forward.connections() == 0);
+
+        assert_eq!(forward.status(), "Listening");
+        let (_, error) = forward.last_error().unwrap();
+        assert_eq!(error, "connection refused on port 80");
+    }
+
+    #[test]
+    fn stop_closes_open_connections() {
+        let (client, _) = mock_api(shouting_pod);
+        let forward = forward(client);
+        let mut local = connect(&forward);
+        local.write_all(b"hi").unwrap();
+        local.read_exact(&mut [0u8; 2]).unwrap();
+
+        forward.stop();
+
+        let mut buffer = [0u8; 16];
+        match local.read(&mut buffer) {
+            Ok(0) => {}
+            Err(e) => assert_ne!(e.kind(), ErrorKind::WouldBlock, "still open"),
+            Ok(n) => panic!("unexpected {} bytes", n),
+        }
+        wait_until(|| forward.status() == "Stopped" && forward.connections() == 0);
+        assert!(forward.last_error().is_none());
+    }
+}
@@ -0,0 +1,25 @@
+use std::{
+    sync::mpsc::{self, Receiver},
+    thread,
+};
+
+/// An API call running in the background, so a slow or unreachable API server
+/// does not freeze the UI.
+pub struct KubeTask<T> {
+    rx: Receiver<T>,
+}
+
+impl<T: Send + 'static> KubeTask<T> {
+    pub fn spawn(call: impl FnOnce() -> T + Send + 'static) -> Self {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(call());
+        });
+        KubeTask { rx }
+    }
+
+    /// Returns the result once the call has finished.
+    pub fn poll(&mut self) -> Option<T> {
+        self.rx.try_recv().ok()
+    }
+}
@@ -0,0 +1,379 @@
+use super::{client::KubeClient, ObjectMeta};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{
+    collections::BTreeMap,
+    io::BufRead,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+const WATCH_TIMEOUT_SECS: u64 = 290;
+const RETRY_DELAY: Duration = Duration::from_secs(3);
+
+/// Any API object with standard object metadata.
+pub trait Resource: DeserializeOwned + Send + 'static {
+    fn metadata(&self) -> &ObjectMeta;
+}
+
+#[derive(Deserialize)]
+struct ObjectList<T> {
+    #[serde(default)]
+    metadata: ListMeta,
+    #[serde(default = "Vec::new")]
+    items: Vec<T>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct ListMeta {
+    resource_version: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RawWatchEvent {
+    #[serde(rename = "type")]
+    event_type: String,
+    object: serde_json::Value,
+}
+
+enum WatchEvent<T> {
+    Reset(Vec<T>),
+    Applied(T),
+    Deleted(T),
+    Error(String),
+}
+
+/// Keeps a local copy of a collection in sync with the API server using list + watch.
+pub struct Watcher<T: Resource> {
+    rx: Receiver<WatchEvent<T>>,
+    items: BTreeMap<(String, String), T>,
+    synced: bool,
+    pub last_error: Option<String>,
+}
+
+impl<T: Resource> Watcher<T> {
+    /// Starts a background list/watch loop for `path` (e.g. `/api/v1/pods`).
+    /// The thread exits on its own once the watcher is dropped.
+    pub fn spawn(client: Arc<KubeClient>, path: &str) -> Self {
+        let (tx, rx) = mpsc::channel();
+        let path = path.to_string();
+        thread::spawn(move || watch_loop(client, path, tx));
+
+        Watcher {
+            rx,
+            items: BTreeMap::new(),
+            synced: false,
+            last_error: None,
+        }
+    }
+
+    /// Applies queued events. Returns true when the collection changed.
+    pub fn poll(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(event) = self.rx.try_recv() {
+            changed = true;
+            match event {
+                WatchEvent::Reset(items) => {
+                    self.items = items.into_iter().map(|item| (key(&item), item)).collect();
+                    self.synced = true;
+                    self.last_error = None;
+                }
+                WatchEvent::Applied(item) => {
+                    self.items.insert(key(&item), item);
+                }
+                WatchEvent::Deleted(item) => {
+                    self.items.remove(&key(&item));
+                }
+                WatchEvent::Error(error) => self.last_error = Some(error),
+            }
+        }
+        changed
+    }
+
+    pub fn is_synced(&self) -> bool {
+        self.synced
+    }
+
+    pub fn items(&self) -> impl Iterator<Item = &T> {
+        self.items.values()
+    }
+}
+
+fn key<T: Resource>(item: &T) -> (String, String) {
+    let meta = item.metadata();
+    (
+        meta.namespace.clone().unwrap_or_default(),
+        meta.name.clone(),
+    )
+}
+
+fn watch_loop<T: Resource>(client: Arc<KubeClient>, path: String, tx: Sender<WatchEvent<T>>) {
+    let separator = if path.contains('?') { '&' } else { '?' };
+
+    loop {
+        let list: ObjectList<T> = match client.get_json(&path) {
+            Ok(list) => list,
+            Err(e) => {
+                if tx.send(WatchEvent::Error(e)).is_err() {
+                    return;
+                }
+                thread::sleep(RETRY_DELAY);
+                continue;
+            }
+        };
+
+        let mut resource_version = list.metadata.resource_version.unwrap_or_default();
+        if tx.send(WatchEvent::Reset(list.items)).is_err() {
+            return;
+        }
+
+        // Keep re-opening the watch from the last seen version until the server
+        // tells us the version is too old (410 Gone), then relist.
+        'watch: loop {
+            let watch_path = format!(
+                "{}{}watch=1&allowWatchBookmarks=true&timeoutSeconds={}&resourceVersion={}",
+                path, separator, WATCH_TIMEOUT_SECS, resource_version
+            );
+            let reader = match client.stream(&watch_path) {
+                Ok(reader) => reader,
+                Err(e) => {
+                    if tx.send(WatchEvent::Error(e)).is_err() {
+                        return;
+                    }
+                    thread::sleep(RETRY_DELAY);
+                    break 'watch;
+                }
+            };
+
+            for line in reader.lines() {
+                let Ok(line) = line else { break };
+                if line.trim().is_empty() {
+                    continue;
+                }
+                let Ok(event) = serde_json::from_str::<RawWatchEvent>(&line) else {
+                    continue;
+                };
+
+                if let Some(version) = event
+                    .object
+                    .pointer("/metadata/resourceVersion")
+                    .and_then(|v| v.as_str())
+                {
+                    resource_version = version.to_string();
+                }
+
+                let sent = match event.event_type.as_str() {
+                    "ADDED" | "MODIFIED" => match serde_json::from_value(event.object) {
+                        Ok(item) => tx.send(WatchEvent::Applied(item)),
+                        Err(_) => Ok(()),
+                    },
+                    "DELETED" => match serde_json::from_value(event.object) {
+                        Ok(item) => tx.send(WatchEvent::Deleted(item)),
+                        Err(_) => Ok(()),
+                    },
+                    "ERROR" => {
+                        let code = event.object.get("code").and_then(|c| c.as_u64());
+                        if code == Some(410) {
+                            break 'watch;
+                        }
+                        let message = event
+                            .object
+                            .get("message")
+                            .and_then(|m| m.as_str())
+                            .unwrap_or("watch error")
+                            .to_string();
+                        tx.send(WatchEvent::Error(message))
+                    }
+                    _ => Ok(()),
+                };
+                if sent.is_err() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::k8s::mock::{wait_until, MockServer, Response};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[derive(Deserialize)]
+    struct Item {
+        metadata: ObjectMeta,
+    }
+
+    impl Resource for Item {
+        fn metadata(&self) -> &ObjectMeta {
+            &self.metadata
+        }
+    }
+
+    fn item(name: &str, version: &str) -> serde_json::Value {
+        json!({ "metadata": { "name": name, "namespace": "default", "resourceVersion": version } })
+    }
+
+    fn list(version: &str, names: &[&str]) -> Response {
+        Response::json(json!({
+            "metadata": { "resourceVersion": version },
+            "items": names.iter().map(|name| item(name, version)).collect::<Vec<_>>(),
+        }))
+    }
+
+    fn event(kind: &str, object: serde_json::Value) -> serde_json::Value {
+        json!({ "type": kind, "object": object })
+    }
+
+    /// A watch that stays open without news until the test is over.
+    fn idle() -> Response {
+        Response::events(&[]).hold(Duration::from_secs(30))
+    }
+
+    fn names(watcher: &Watcher<Item>) -> Vec<String> {
+        watcher.items().map(|i| i.metadata.name.clone()).collect()
+    }
+
+    fn watch_versions(server: &MockServer) -> Vec<String> {
+        server
+            .requests()
+            .iter()
+            .filter_map(|r| r.path.split("resourceVersion=").nth(1).map(str::to_string))
+            .collect()
+    }
+
+    fn spawn(server: &MockServer) -> Watcher<Item> {
+        let client = Arc::new(KubeClient::new(server.config()).unwrap());
+        Watcher::spawn(client, "/api/v1/pods")
+    }
+
+    #[test]
+    fn lists_then_applies_watch_events() {
+        let server = MockServer::start(|request| {
+            if !request.path.contains("watch=1") {
+                return list("5", &["a", "b"]);
+            }
+            if request.path.ends_with("resourceVersion=5") {
+                return Response::events(&[
+                    event("ADDED", item("c", "6")),
+                    event("MODIFIED", item("b", "7")),
+                    event(
+                        "BOOKMARK",
+                        json!({ "metadata": { "resourceVersion": "8" } }),
+                    ),
+                    event("DELETED", item("a", "9")),
+                ])
+                .hold(Duration::from_secs(30));
+            }
+            idle()
+        });
+        let mut watcher = spawn(&server);
+
+        wait_until(|| {
+            watcher.poll();
+            watcher.is_synced() && names(&watcher) == ["b", "c"]
+        });
+        assert!(watcher.last_error.is_none());
+        assert_eq!(watch_versions(&server), ["5"]);
+    }
+
+    #[test]
+    fn resumes_from_the_last_seen_version_when_a_watch_ends() {
+        let server = MockServer::start(|request| {
+            if !request.path.contains("watch=1") {
+                return list("5", &["a"]);
+            }
+            if request.path.ends_with("resourceVersion=5") {
+                // Closes after one event, as watches do when `timeoutSeconds` runs out.
+                return Response::events(&[event("ADDED", item("b", "6"))]);
+            }
+            idle()
+        });
+        let mut watcher = spawn(&server);
+
+        wait_until(|| {
+            watcher.poll();
+            watch_versions(&server) == ["5", "6"]
+        });
+        watcher.poll();
+        assert_eq!(names(&watcher), ["a", "b"]);
+        let lists = server
+            .requests()
+            .iter()
+            .filter(|r| !r.path.contains("watch=1"))
+            .count();
+        assert_eq!(lists, 1);
+    }
+
+    #[test]
+    fn relists_when_the_version_is_gone() {
+        let lists = AtomicUsize::new(0);
+        let server = MockServer::start(move |request| {
+            if !request.path.contains("watch=1") {
+                return match lists.fetch_add(1, Ordering::SeqCst) {
+                    0 => list("5", &["a"]),
+                    _ => list("20", &["b"]),
+                };
+            }
+            if request.path.ends_with("resourceVersion=5") {
+                return Response::events(&[event(
+                    "ERROR",
+                    json!({ "kind": "Status", "code": 410, "message": "too old resource version" }),
+                )]);
+            }
+            idle()
+        });
+        let mut watcher = spawn(&server);
+
+        wait_until(|| {
+            watcher.poll();
+            names(&watcher) == ["b"]
+        });
+        // The 410 is handled by relisting, not shown as an error.
+        assert!(watcher.last_error.is_none());
+        wait_until(|| watch_versions(&server) == ["5", "20"]);
+    }
+
+    #[test]
+    fn reports_list_errors() {
+        let server = MockServer::start(|_| Response::status(403, "pods is forbidden"));
+        let mut watcher = spawn(&server);
+
+        wait_until(|| {
+            watcher.poll();
+            watcher.last_error.is_some()
+        });
+        assert!(!watcher.is_synced());
+        assert_eq!(
+            watcher.last_error.as_deref(),
+            Some("API error 403 (GET /api/v1/pods): pods is forbidden")
+        );
+    }
+
+    #[test]
+    fn forwards_other_watch_errors() {
+        let server = MockServer::start(|request| {
+            if !request.path.contains("watch=1") {
+                return list("5", &[]);
+            }
+            Response::events(&[event(
+                "ERROR",
+                json!({ "kind": "Status", "code": 500, "message": "etcd unavailable" }),
+            )])
+            .hold(Duration::from_secs(30))
+        });
+        let mut watcher = spawn(&server);
+
+        wait_until(|| {
+            watcher.poll();
+            watcher.last_error.is_some()
+        });
+        assert_eq!(watcher.last_error.as_deref(), Some("etcd unavailable"));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::{docker::CommandLogStream, k8s::LogStream};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Paragraph},
+};
+
+const MAX_LINES: usize = 5000;
+
+/// Anything that delivers log lines in the background.
+pub trait LogSource {
+    /// Returns the lines received since the last call.
+    fn poll(&mut self) -> Vec<String>;
+}
+
+impl LogSource for LogStream {
+    fn poll(&mut self) -> Vec<String> {
+        LogStream::poll(self)
+    }
+}
+
+impl LogSource for CommandLogStream {
+    fn poll(&mut self) -> Vec<String> {
+        CommandLogStream::poll(self)
+    }
+}
+
+/// Full-screen log follower. Scrolling up pauses following; End resumes it.
+pub struct LogView {
+    title: String,
+    stream: Box<dyn LogSource>,
+    lines: Vec<String>,
+    /// First visible line while paused; `None` follows the tail.
+    scroll: Option<usize>,
+    height: usize,
+}
+
+impl LogView {
+    pub fn new(title: String, stream: impl LogSource + 'static) -> Self {
+        LogView {
+            title,
+            stream: Box::new(stream),
+            lines: Vec::new(),
+            scroll: None,
+            height: 0,
+        }
+    }
+
+    pub fn poll(&mut self) {
+        let new_lines = self.stream.poll();
+        if new_lines.is_empty() {
+            return;
+        }
+        self.lines.extend(new_lines);
+        if self.lines.len() > MAX_LINES {
+            let excess = self.lines.len() - MAX_LINES;
+            self.lines.drain(..excess);
+            if let Some(scroll) = self.scroll.as_mut() {
+                *scroll = scroll.saturating_sub(excess);
+            }
+        }
+    }
+
+    fn bottom(&self) -> usize {
+        self.lines.len().saturating_sub(self.height)
+    }
+
+    pub fn scroll_by(&mut self, delta: isize) {
+        let current = self.scroll.unwrap_or_else(|| self.bottom());
+        let next = (current as isize + delta).clamp(0, self.bottom() as isize) as usize;
+        self.scroll = if next >= self.bottom() && delta > 0 {
+            None
+        } else {
+            Some(next)
+        };
+    }
+
+    pub fn scroll_to_top(&mut self) {
+        self.scroll = Some(0);
+    }
+
+    pub fn follow(&mut self) {
+        self.scroll = None;
+    }
+
+    pub fn render(&mut self, f: &mut ratatui::Frame, area: Rect) {
+        self.height = area.height.saturating_sub(2) as usize;
+        let start = self.scroll.unwrap_or_else(|| self.bottom());
+
+        let text: Vec<Line> = self
+            .lines
+            .iter()
+            .skip(start)
+            .take(self.height)
+            .map(|line| {
+                let style = if line.contains("<error") || line.contains("<log stream ended>") {
+                    Style::default().fg(Color::Red)
+                } else {
+                    Style::default()
+                };
+                Line::styled(line.clone(), style)
+            })
+            .collect();
+
+        let mode = if self.scroll.is_some() {
+            "Paused"
+        } else {
+            "Following"
+        };
+        let paragraph =
+            Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(format!(
+                "{} [{} | {} lines] (↑↓/PgUp/PgDn Scroll | Home Top | End Follow | Esc Close)",
+                self.title,
+                mode,
+                self.lines.len()
+            )));
+        f.render_widget(paragraph, area);
+    }
+}
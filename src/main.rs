@@ -1,26 +1,63 @@
+mod docker;
+mod file_browser;
+mod k8s;
+mod log_view;
+mod process_view;
+mod text_input;
+mod yaml_view;
+
 use crossterm::{
-    event::{self, Event, KeyCode, KeyModifiers},
+    event::{self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyCode, KeyModifiers},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use docker::{
+    active_endpoint_name, list_contexts, on_endpoint, override_endpoint, select_endpoint, Endpoint,
+    EndpointGuard,
+};
+use docker::{
+    compose_args, container_states, containerd_namespace, host_path, inspect_limits,
+    is_valid_container_name, list_networks, next_containerd_namespace, parse_size, shell_quote,
+    signal_process, CommandLogStream, ComposeInfo, ContainerHealth, ContainerLimits,
+    ContainerState, DiskUsage, DiskUsageTask, DockerEvent, DockerNetwork, DockerTask, DockerVolume,
+    EventStream, HealthTask, ImageDetails, ImageInspectTask, ImagePull, ImageUsersTask, Prune,
+    RunOptions, Runtime, Transfer, VolumeListTask, RESTART_POLICIES,
+};
+use file_browser::FileBrowser;
+use k8s::{
+    api_path, format_age, format_cpu, format_memory, format_selector, get_manifest,
+    manifest_to_yaml, mask_secret_values, owning_workload, printer_columns, service_endpoints,
+    set_cron_job_suspended, strip_managed_fields, trigger_cron_job, ApiResource, ConfigMap,
+    ContainerResources, CronJob, DiscoveryTask, DynamicObject, EndpointSlice, ForwardTarget,
+    GenericRow, Ingress, Job, K8sCronJob, K8sDataObject, K8sIngress, K8sJob, K8sPod, K8sService,
+    KubeClient, KubeTask, LogStream, MetricsPoller, Pod, PodMetricsMap, PortForward, PrinterColumn,
+    Secret, Service, Watcher,
+};
+use log_view::LogView;
+use process_view::ProcessView;
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{
-        BarChart, Block, Borders, Cell, Gauge,  Paragraph, Row,
-        Sparkline, Table, TableState, Tabs, Wrap,
+        BarChart, Block, Borders, Cell, Clear, Gauge, Paragraph, Row, Sparkline, Table, TableState,
+        Tabs, Wrap,
     },
     Terminal,
 };
 use std::{
-    collections::HashMap,
-    io,
+    collections::{BTreeMap, HashMap, HashSet},
+    env, fs,
+    io::{self, Write},
     process::Command,
+    sync::{mpsc, Arc},
+    thread,
     time::{Duration, Instant},
 };
 use sysinfo::{Networks, System};
+use text_input::{path_completions, InputHistory, TextInput};
+use yaml_view::YamlView;
 
 #[derive(Clone, PartialEq)]
 enum MonitorTab {
@@ -41,6 +78,90 @@ struct DockerContainer {
     mem_percent: f64,
     net_io: String,
     block_io: String,
+    compose: Option<ComposeInfo>,
+    /// The Podman pod the container runs in, or its Kubernetes pod as
+    /// `namespace/name` under containerd.
+    pod: Option<String>,
+    state: ContainerState,
+    /// The endpoint it runs on, shown in the all-hosts view.
+    host: String,
+}
+
+impl DockerContainer {
+    /// Health check state from the status, e.g. "Up 2 minutes (health: starting)".
+    fn health(&self) -> Option<&str> {
+        let (_, rest) = self.status.rsplit_once('(')?;
+        match rest.trim_end_matches(')') {
+            "healthy" => Some("healthy"),
+            "unhealthy" => Some("unhealthy"),
+            "health: starting" => Some("starting"),
+            _ => None,
+        }
+    }
+
+    /// Restarting now, or restarted within `RECENT_RESTART`; a restart long ago
+    /// does not make a container a problem forever.
+    fn is_restarting(&self) -> bool {
+        let restarted_recently = self.state.restart_count > 0
+            && self.state.started_at.is_some_and(|at| {
+                (chrono::Utc::now() - at).num_seconds() < RECENT_RESTART.as_secs() as i64
+            });
+        self.status.starts_with("Restarting") || restarted_recently
+    }
+
+    fn status_color(&self) -> Color {
+        match self.health() {
+            Some("unhealthy") => Color::Red,
+            Some("starting") => Color::Yellow,
+            _ if self.status.starts_with("Restarting") => Color::Magenta,
+            _ if self.status.starts_with("Up") => Color::Green,
+            _ => Color::Red,
+        }
+    }
+}
+
+/// A row of the Containers view: a Compose project or pod header, or one container.
+enum DockerRow {
+    Group(ContainerGroup),
+    Container(usize),
+}
+
+#[derive(Clone)]
+enum GroupKind {
+    Compose(ComposeInfo),
+    /// A Podman pod, or a Kubernetes pod under containerd.
+    Pod(String),
+}
+
+impl GroupKind {
+    fn name(&self) -> &str {
+        match self {
+            GroupKind::Compose(info) => &info.project,
+            GroupKind::Pod(name) => name,
+        }
+    }
+}
+
+/// The containers of a Compose project (by `com.docker.compose.project` label)
+/// or of a Podman pod, with their totals.
+struct ContainerGroup {
+    kind: GroupKind,
+    host: String,
+    containers: Vec<usize>,
+    running: usize,
+    cpu_percent: f64,
+    mem_bytes: f64,
+}
+
+impl ContainerGroup {
+    /// Keeps the fold state of a project apart from a pod of the same name,
+    /// and from the same project on another host.
+    fn key(&self) -> String {
+        match &self.kind {
+            GroupKind::Compose(info) => format!("{}/{}", self.host, info.project),
+            GroupKind::Pod(name) => format!("{}/pod/{}", self.host, name),
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -49,15 +170,14 @@ struct DockerImage {
     tag: String,
     image_id: String,
     size: String,
+    in_use: bool,
 }
 
-#[derive(Clone)]
-struct K8sPod {
-    name: String,
-    namespace: String,
-    status: String,
-    restarts: String,
-    age: String,
+impl DockerImage {
+    /// Untagged images left behind when a tag moved to a newer build.
+    fn dangling(&self) -> bool {
+        self.repository == "<none>" && self.tag == "<none>"
+    }
 }
 
 struct AppState {
@@ -65,12 +185,118 @@ struct AppState {
     docker_list_state: TableState,
     docker_containers: Vec<DockerContainer>,
     docker_images: Vec<DockerImage>,
+    docker_networks: Vec<DockerNetwork>,
+    docker_volumes: Vec<DockerVolume>,
+    volume_task: Option<VolumeListTask>,
+    /// When `docker_volumes` was last listed; cleared by volume events to re-list.
+    docker_volumes_at: Option<Instant>,
+    collapsed_projects: HashSet<String>,
+    docker_tasks: Vec<DockerTask>,
+    image_pulls: Vec<ImagePull>,
+    /// Details of the selected image, keyed by its short ID.
+    /// Inspected images, keyed by their ID in the Images list.
+    image_details: HashMap<String, Result<ImageDetails, String>>,
+    image_inspect: Option<ImageInspectTask>,
+    /// The containers of each image, keyed by full image ID.
+    image_users: HashMap<String, Vec<String>>,
+    image_users_task: Option<ImageUsersTask>,
+    /// When `image_users` was last computed; cleared by container events.
+    image_users_at: Option<Instant>,
+    /// Health of the selected container, keyed by its ID, and when it was inspected.
+    container_health: Option<(String, Instant, Result<Option<ContainerHealth>, String>)>,
+    health_task: Option<HealthTask>,
+    /// Only list unhealthy containers and those restarting or restarted recently.
+    show_problem_containers: bool,
+    disk_usage: Option<Result<DiskUsage, String>>,
+    disk_task: Option<DiskUsageTask>,
+    /// When `disk_usage` was last computed; cleared to force a refresh.
+    disk_usage_at: Option<Instant>,
+    prune_preview: Option<PrunePreview>,
+    /// A preview waiting for fresh disk usage: the category and whether to prune all images.
+    prune_request: Option<(usize, bool)>,
+    docker_events: Option<EventStream>,
+    docker_event_log: Vec<DockerEvent>,
+    show_docker_events: bool,
+    event_kind_filter: Option<&'static str>,
+    /// Only show events of this container, as (short ID, name).
+    event_container_filter: Option<(String, String)>,
+    docker_stats_at: Option<Instant>,
+    /// Docker contexts and hosts added by hand, for the host picker and the all-hosts view.
+    docker_endpoints: Vec<Endpoint>,
+    host_picker: Option<HostPicker>,
+    /// List the containers of every endpoint at once, with a Host column.
+    all_docker_hosts: bool,
+    /// Endpoints that did not answer in time during the last all-hosts refresh.
+    silent_docker_hosts: Vec<String>,
+    host_listing: Option<HostListing>,
+    /// Containers changed while `host_listing` was running; list them again after it.
+    relist_docker_hosts: bool,
     k8s_pods: Vec<K8sPod>,
     k8s_list_state: TableState,
+    k8s_view: K8sView,
+    pod_selector: Option<PodSelector>,
+    k8s_services: Vec<K8sService>,
+    service_list_state: TableState,
+    k8s_ingresses: Vec<K8sIngress>,
+    ingress_list_state: TableState,
+    service_watcher: Option<Watcher<Service>>,
+    endpoint_slice_watcher: Option<Watcher<EndpointSlice>>,
+    ingress_watcher: Option<Watcher<Ingress>>,
+    k8s_config_maps: Vec<K8sDataObject>,
+    config_map_list_state: TableState,
+    k8s_secrets: Vec<K8sDataObject>,
+    secret_list_state: TableState,
+    config_map_watcher: Option<Watcher<ConfigMap>>,
+    secret_watcher: Option<Watcher<Secret>>,
+    data_key_state: TableState,
+    data_focus: bool,
+    show_data_value: bool,
+    reveal_secret: bool,
+    k8s_jobs: Vec<K8sJob>,
+    job_list_state: TableState,
+    k8s_cron_jobs: Vec<K8sCronJob>,
+    cron_job_list_state: TableState,
+    job_watcher: Option<Watcher<Job>>,
+    cron_job_watcher: Option<Watcher<CronJob>>,
+    discovery: Option<DiscoveryTask>,
+    api_resources: Vec<ApiResource>,
+    resource_picker: Option<ResourcePicker>,
+    browsed_resource: Option<(ApiResource, Vec<PrinterColumn>)>,
+    /// Changes sent to the API server; each reports a result message.
+    k8s_actions: Vec<KubeTask<String>>,
+    /// Port-forwards resolving their service and binding the local port.
+    forward_starts: Vec<KubeTask<Result<PortForward, String>>>,
+    /// Printer columns of the browsed kind, keyed by its full name.
+    columns_task: Option<KubeTask<(String, Vec<PrinterColumn>)>>,
+    generic_watcher: Option<Watcher<DynamicObject>>,
+    k8s_generic_rows: Vec<GenericRow>,
+    generic_list_state: TableState,
+    k8s_client: Option<Arc<KubeClient>>,
+    /// Loading the kubeconfig can run a credential plugin, so it happens in the background.
+    k8s_connect: Option<KubeTask<Result<KubeClient, String>>>,
+    pod_watcher: Option<Watcher<Pod>>,
+    metrics_poller: Option<MetricsPoller>,
+    pod_metrics: PodMetricsMap,
+    pod_metrics_error: Option<String>,
+    pod_stats_history: HashMap<String, Vec<PodStats>>,
+    port_forwards: Vec<PortForward>,
+    port_forward_list_state: TableState,
+    show_port_forwards: bool,
+    input_prompt: Option<InputPrompt>,
+    input_history: InputHistory,
+    yaml_view: Option<YamlView>,
+    /// A load, dry run or apply of a manifest.
+    yaml_task: Option<(YamlStep, ManifestTask)>,
+    log_view: Option<LogView>,
+    file_browser: Option<FileBrowser>,
+    /// Copies to and from containers; they keep running when the browser closes.
+    file_transfers: Vec<Transfer>,
+    process_view: Option<ProcessView>,
     docker_view: DockerView,
     message: String,
     show_create_dialog: bool,
     create_dialog_state: CreateDialogState,
+    update_dialog: Option<UpdateDialogState>,
     container_stats_history: HashMap<String, Vec<ContainerStats>>,
 }
 
@@ -82,45 +308,411 @@ struct ContainerStats {
     net_tx_kb: f64,
 }
 
+#[derive(Clone)]
+struct PodStats {
+    cpu_millis: f64,
+    memory_bytes: f64,
+}
+
+/// Fetches or sends a manifest; answers with the object path and the server's copy.
+type ManifestTask = KubeTask<Result<(String, serde_json::Value), String>>;
+
+/// What a running `yaml_task` does with its result.
+enum YamlStep {
+    Load,
+    DryRun(serde_json::Value),
+    Apply,
+}
+
+/// A one-line text prompt shown over the current tab.
+struct InputPrompt {
+    title: String,
+    input: TextInput,
+    action: PromptAction,
+}
+
+enum PromptAction {
+    PortForward(ForwardTarget),
+    ServicePortForward {
+        namespace: String,
+    },
+    CreateNetwork,
+    CreateVolume,
+    PullImage,
+    /// Runs the Docker commands once the user types `expected`.
+    ConfirmDocker {
+        expected: String,
+        label: String,
+        steps: Vec<Vec<String>>,
+    },
+    Container {
+        command: ContainerCommand,
+        id: String,
+        name: String,
+    },
+    DownloadFile {
+        container: String,
+        path: String,
+        size: Option<u64>,
+    },
+    UploadFile {
+        container: String,
+        directory: String,
+    },
+    /// `pid` is the host PID shown by `docker top`.
+    SignalProcess {
+        container: String,
+        pid: u32,
+    },
+    AddDockerHost,
+}
+
+impl PromptAction {
+    /// Prompts asking for the same kind of value share their history.
+    fn history_key(&self) -> Option<&'static str> {
+        Some(match self {
+            PromptAction::PortForward(_) => "port-forward",
+            PromptAction::ServicePortForward { .. } => "service port-forward",
+            PromptAction::CreateNetwork => "network",
+            PromptAction::CreateVolume => "volume",
+            PromptAction::PullImage => "image",
+            PromptAction::ConfirmDocker { .. } => return None,
+            PromptAction::Container { command, .. } => match command {
+                ContainerCommand::Rename => "container name",
+                ContainerCommand::Kill => "signal",
+                ContainerCommand::Commit => "image",
+                ContainerCommand::Export => "host path",
+            },
+            PromptAction::DownloadFile { .. } | PromptAction::UploadFile { .. } => "host path",
+            PromptAction::SignalProcess { .. } => "signal",
+            PromptAction::AddDockerHost => "docker host",
+        })
+    }
+}
+
+/// Container actions that take one argument from a prompt.
+#[derive(Clone, Copy)]
+enum ContainerCommand {
+    Rename,
+    Kill,
+    Commit,
+    Export,
+}
+
+#[derive(Clone, PartialEq)]
+enum K8sView {
+    Pods,
+    Services,
+    Ingresses,
+    ConfigMaps,
+    Secrets,
+    Jobs,
+    CronJobs,
+    Resources,
+}
+
+/// Filterable list of the kinds found by API discovery.
+struct ResourcePicker {
+    filter: String,
+    list_state: TableState,
+}
+
+/// Lists `AppState::docker_endpoints` to switch the Docker tab between them.
+struct HostPicker {
+    list_state: TableState,
+}
+
+/// Restricts the pod list to the pods a Service selects.
+struct PodSelector {
+    service: String,
+    namespace: String,
+    selector: BTreeMap<String, String>,
+}
+
 #[derive(Clone, PartialEq)]
 enum DockerView {
     Containers,
     Images,
+    Networks,
+    Volumes,
+    Disk,
 }
 
-#[derive(Clone)]
+/// Rows of the Disk Usage view, named like the `Type` column of `docker system df`.
+const DISK_CATEGORIES: [&str; 4] = ["Images", "Containers", "Local Volumes", "Build Cache"];
+
+/// What pruning a Disk Usage category removes; `all_images` includes tagged unused images.
+fn disk_prune(category: usize, all_images: bool) -> Prune {
+    match category {
+        0 if all_images => Prune::UnusedImages,
+        0 => Prune::DanglingImages,
+        1 => Prune::StoppedContainers,
+        2 => Prune::Volumes,
+        _ => Prune::BuildCache,
+    }
+}
+
+const EVENT_LOG_LIMIT: usize = 500;
+/// Event types the events panel can be narrowed to.
+const EVENT_KINDS: [&str; 4] = ["container", "image", "network", "volume"];
+/// `docker stats` takes a couple of seconds; with the event stream keeping the
+/// container list current, sample it only this often.
+const DOCKER_STATS_INTERVAL: Duration = Duration::from_secs(3);
+/// Containers that restarted this recently count as restarting in the problem filter.
+const RECENT_RESTART: Duration = Duration::from_secs(10 * 60);
+/// Volume sizes take a `docker system df -v` and image users an inspect of
+/// every container, so without events the Volumes and Images views only
+/// re-list them this often.
+const SLOW_LISTING_REFRESH: Duration = Duration::from_secs(30);
+/// How long the all-hosts view waits for each host's containers.
+const HOST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Lists the containers of every endpoint in the background. Each host's
+/// containers arrive on their own, so a slow host holds back nobody.
+struct HostListing {
+    receiver: mpsc::Receiver<(usize, Vec<DockerContainer>)>,
+    hosts: Vec<String>,
+    answered: Vec<bool>,
+    started: Instant,
+    /// Whether the listing samples stats.
+    stats: bool,
+}
+
+impl HostListing {
+    fn spawn(endpoints: &[Endpoint], stats: bool) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        for (index, endpoint) in endpoints.iter().enumerate() {
+            let sender = sender.clone();
+            let endpoint = endpoint.clone();
+            thread::spawn(move || {
+                let containers = on_endpoint(&endpoint, || host_containers(stats));
+                let _ = sender.send((index, containers));
+            });
+        }
+        HostListing {
+            receiver,
+            hosts: endpoints
+                .iter()
+                .map(|endpoint| endpoint.name.clone())
+                .collect(),
+            answered: vec![false; endpoints.len()],
+            started: Instant::now(),
+            stats,
+        }
+    }
+
+    /// The hosts that answered since the last call, with their containers.
+    fn poll(&mut self) -> Vec<(String, Vec<DockerContainer>)> {
+        let mut answers = Vec::new();
+        while let Ok((index, containers)) = self.receiver.try_recv() {
+            self.answered[index] = true;
+            answers.push((self.hosts[index].clone(), containers));
+        }
+        answers
+    }
+
+    /// Every host answered, or the rest took longer than `HOST_TIMEOUT`.
+    fn finished(&self) -> bool {
+        self.answered.iter().all(|answered| *answered) || self.started.elapsed() >= HOST_TIMEOUT
+    }
+
+    fn silent_hosts(&self) -> Vec<String> {
+        self.hosts
+            .iter()
+            .zip(&self.answered)
+            .filter(|(_, answered)| !**answered)
+            .map(|(host, _)| host.clone())
+            .collect()
+    }
+}
+/// Shown for pod actions under containerd, where pods belong to Kubernetes.
+const KUBELET_PODS: &str =
+    "Kubernetes pods are managed by the kubelet; select one of their containers";
+
+/// Everything a prune would remove, listed for confirmation before running it.
+struct PrunePreview {
+    title: String,
+    items: Vec<(String, String)>,
+    total_bytes: f64,
+    args: Vec<String>,
+    list_state: TableState,
+}
+
+/// The create-container form. Network and restart policy are picked from
+/// lists; every other field is free text.
 struct CreateDialogState {
     selected_field: usize,
-    image_name: String,
-    container_name: String,
-    ports: String,
-    env_vars: String,
-    volumes: String,
-    command: String,
+    /// One input per field. The choice fields hold the chosen value.
+    inputs: [TextInput; CreateDialogState::FIELD_COUNT],
+    /// Network choices; the empty name is the default bridge.
+    networks: Vec<String>,
+    /// Local images for completing the image field.
+    images: Vec<String>,
 }
 
 impl CreateDialogState {
+    const FIELD_COUNT: usize = 11;
+    const NETWORK_FIELD: usize = 3;
+    const RESTART_FIELD: usize = 4;
+    /// History keys of the text fields; image history is shared with the pull prompt.
+    const HISTORY_KEYS: [&'static str; Self::FIELD_COUNT] = [
+        "image",
+        "container name",
+        "ports",
+        "",
+        "",
+        "memory",
+        "cpus",
+        "command",
+        "env",
+        "volumes",
+        "labels",
+    ];
+
     fn new() -> Self {
+        let mut inputs: [TextInput; Self::FIELD_COUNT] = Default::default();
+        inputs[Self::RESTART_FIELD].set_value(RESTART_POLICIES[0]);
         Self {
             selected_field: 0,
-            image_name: String::new(),
-            container_name: String::new(),
-            ports: String::new(),
-            env_vars: String::new(),
-            volumes: String::new(),
-            command: String::new(),
+            inputs,
+            networks: vec![String::new()],
+            images: Vec::new(),
+        }
+    }
+
+    fn open(networks: &[DockerNetwork], images: &[DockerImage]) -> Self {
+        let mut state = Self::new();
+        state
+            .networks
+            .extend(networks.iter().map(|network| network.name.clone()));
+        state.images = image_references(images);
+        state
+    }
+
+    fn values(&self) -> RunOptions {
+        let value = |field: usize| self.inputs[field].value().to_string();
+        RunOptions {
+            image: value(0),
+            name: value(1),
+            ports: value(2),
+            network: value(Self::NETWORK_FIELD),
+            restart_policy: value(Self::RESTART_FIELD),
+            memory: value(5),
+            cpus: value(6),
+            command: value(7),
+            env: value(8),
+            volumes: value(9),
+            labels: value(10),
         }
     }
 
-    fn get_current_field_mut(&mut self) -> &mut String {
+    /// The text being edited, or `None` on a choice field.
+    fn current_input(&mut self) -> Option<&mut TextInput> {
         match self.selected_field {
-            0 => &mut self.image_name,
-            1 => &mut self.container_name,
-            2 => &mut self.ports,
-            3 => &mut self.env_vars,
-            4 => &mut self.volumes,
-            5 => &mut self.command,
-            _ => &mut self.image_name,
+            Self::NETWORK_FIELD | Self::RESTART_FIELD => None,
+            field => Some(&mut self.inputs[field]),
+        }
+    }
+
+    fn history_key(&self) -> Option<&'static str> {
+        Some(Self::HISTORY_KEYS[self.selected_field]).filter(|key| !key.is_empty())
+    }
+
+    /// Env, volumes and labels take one entry per line.
+    fn is_multi_line(&self) -> bool {
+        self.selected_field >= 8
+    }
+
+    fn cycle_choice(&mut self, delta: isize) {
+        let step = |choices: &[&str], current: &str| {
+            let index = choices.iter().position(|c| *c == current).unwrap_or(0) as isize;
+            choices[(index + delta).rem_euclid(choices.len() as isize) as usize].to_string()
+        };
+        let choice = match self.selected_field {
+            Self::NETWORK_FIELD => {
+                let networks: Vec<&str> = self.networks.iter().map(String::as_str).collect();
+                step(&networks, self.inputs[Self::NETWORK_FIELD].value())
+            }
+            Self::RESTART_FIELD => {
+                step(&RESTART_POLICIES, self.inputs[Self::RESTART_FIELD].value())
+            }
+            _ => return,
+        };
+        self.inputs[self.selected_field].set_value(choice);
+    }
+
+    /// Validation error of one field, so it can be flagged while typing.
+    fn field_error(&self, field: usize) -> Option<String> {
+        let values = self.values();
+        let result = match field {
+            0 if values.image.is_empty() => Ok(Vec::new()),
+            0 => values.image_args(),
+            1 => values.name_args(),
+            2 => values.port_args(),
+            5 => values.memory_args(),
+            6 => values.cpu_args(),
+            7 => values.command_args(),
+            8 => values.env_args(),
+            9 => values.volume_args(),
+            10 => values.label_args(),
+            _ => Ok(Vec::new()),
+        };
+        result.err()
+    }
+}
+
+/// Resource limits of one container being edited with `docker update`.
+struct UpdateDialogState {
+    container_id: String,
+    container_name: String,
+    selected_field: usize,
+    current: ContainerLimits,
+    /// CPU shares, CPUs, memory, memory + swap, PIDs limit and restart policy.
+    inputs: [TextInput; UpdateDialogState::FIELD_COUNT],
+    error: Option<String>,
+}
+
+impl UpdateDialogState {
+    const FIELD_COUNT: usize = 6;
+
+    fn new(container_id: &str, container_name: &str, current: ContainerLimits) -> Self {
+        let inputs = [
+            &current.cpu_shares,
+            &current.cpus,
+            &current.memory,
+            &current.memory_swap,
+            &current.pids_limit,
+            &current.restart_policy,
+        ]
+        .map(|value| TextInput::new(value.as_str()));
+        UpdateDialogState {
+            container_id: container_id.to_string(),
+            container_name: container_name.to_string(),
+            selected_field: 0,
+            current,
+            inputs,
+            error: None,
+        }
+    }
+
+    fn values(&self) -> ContainerLimits {
+        let value = |field: usize| self.inputs[field].value().to_string();
+        let mut values = self.current.clone();
+        values.cpu_shares = value(0);
+        values.cpus = value(1);
+        values.memory = value(2);
+        values.memory_swap = value(3);
+        values.pids_limit = value(4);
+        values.restart_policy = value(5);
+        values
+    }
+}
+
+impl Drop for AppState {
+    /// Runs on every way out of `main`, including errors, so no listener outlives the UI.
+    fn drop(&mut self) {
+        for forward in self.port_forwards.drain(..) {
+            forward.stop();
         }
     }
 }
@@ -132,23 +724,120 @@ impl AppState {
             docker_list_state: TableState::default(),
             docker_containers: Vec::new(),
             docker_images: Vec::new(),
+            docker_networks: Vec::new(),
+            docker_volumes: Vec::new(),
+            volume_task: None,
+            docker_volumes_at: None,
+            collapsed_projects: HashSet::new(),
+            docker_tasks: Vec::new(),
+            image_pulls: Vec::new(),
+            image_details: HashMap::new(),
+            image_inspect: None,
+            image_users: HashMap::new(),
+            image_users_task: None,
+            image_users_at: None,
+            container_health: None,
+            health_task: None,
+            show_problem_containers: false,
+            disk_usage: None,
+            disk_task: None,
+            disk_usage_at: None,
+            prune_preview: None,
+            prune_request: None,
+            docker_events: None,
+            docker_event_log: Vec::new(),
+            show_docker_events: false,
+            event_kind_filter: None,
+            event_container_filter: None,
+            docker_stats_at: None,
+            docker_endpoints: Vec::new(),
+            host_picker: None,
+            all_docker_hosts: false,
+            silent_docker_hosts: Vec::new(),
+            host_listing: None,
+            relist_docker_hosts: false,
             k8s_pods: Vec::new(),
             k8s_list_state: TableState::default(),
+            k8s_view: K8sView::Pods,
+            pod_selector: None,
+            k8s_services: Vec::new(),
+            service_list_state: TableState::default(),
+            k8s_ingresses: Vec::new(),
+            ingress_list_state: TableState::default(),
+            service_watcher: None,
+            endpoint_slice_watcher: None,
+            ingress_watcher: None,
+            k8s_config_maps: Vec::new(),
+            config_map_list_state: TableState::default(),
+            k8s_secrets: Vec::new(),
+            secret_list_state: TableState::default(),
+            config_map_watcher: None,
+            secret_watcher: None,
+            data_key_state: TableState::default(),
+            data_focus: false,
+            show_data_value: false,
+            reveal_secret: false,
+            k8s_jobs: Vec::new(),
+            job_list_state: TableState::default(),
+            k8s_cron_jobs: Vec::new(),
+            cron_job_list_state: TableState::default(),
+            job_watcher: None,
+            cron_job_watcher: None,
+            discovery: None,
+            api_resources: Vec::new(),
+            resource_picker: None,
+            browsed_resource: None,
+            k8s_actions: Vec::new(),
+            forward_starts: Vec::new(),
+            columns_task: None,
+            generic_watcher: None,
+            k8s_generic_rows: Vec::new(),
+            generic_list_state: TableState::default(),
+            k8s_client: None,
+            k8s_connect: None,
+            pod_watcher: None,
+            metrics_poller: None,
+            pod_metrics: HashMap::new(),
+            pod_metrics_error: None,
+            pod_stats_history: HashMap::new(),
+            port_forwards: Vec::new(),
+            port_forward_list_state: TableState::default(),
+            show_port_forwards: false,
+            input_prompt: None,
+            input_history: InputHistory::default(),
+            yaml_view: None,
+            yaml_task: None,
+            log_view: None,
+            file_browser: None,
+            file_transfers: Vec::new(),
+            process_view: None,
             docker_view: DockerView::Containers,
             message: String::new(),
             show_create_dialog: false,
             create_dialog_state: CreateDialogState::new(),
+            update_dialog: None,
             container_stats_history: HashMap::new(),
         };
         state.docker_list_state.select(Some(0));
         state.k8s_list_state.select(Some(0));
+        state.service_list_state.select(Some(0));
+        state.ingress_list_state.select(Some(0));
+        state.config_map_list_state.select(Some(0));
+        state.secret_list_state.select(Some(0));
+        state.data_key_state.select(Some(0));
+        state.job_list_state.select(Some(0));
+        state.cron_job_list_state.select(Some(0));
+        state.generic_list_state.select(Some(0));
         state
     }
 
     fn next_docker_item(&mut self) {
         let items_len = match self.docker_view {
-            DockerView::Containers => self.docker_containers.len(),
+            DockerView::Containers => self.docker_rows().len(),
             DockerView::Images => self.docker_images.len(),
+            DockerView::Networks => self.docker_networks.len(),
+            DockerView::Volumes => self.docker_volumes.len(),
+            DockerView::Disk => DISK_CATEGORIES.len(),
         };
 
         if items_len == 0 {
@@ -170,8 +859,11 @@ impl AppState {
 
     fn previous_docker_item(&mut self) {
         let items_len = match self.docker_view {
-            DockerView::Containers => self.docker_containers.len(),
+            DockerView::Containers => self.docker_rows().len(),
             DockerView::Images => self.docker_images.len(),
+            DockerView::Networks => self.docker_networks.len(),
+            DockerView::Volumes => self.docker_volumes.len(),
+            DockerView::Disk => DISK_CATEGORIES.len(),
         };
 
         if items_len == 0 {
@@ -191,377 +883,3220 @@ impl AppState {
         self.docker_list_state.select(Some(i));
     }
 
-    fn next_k8s_item(&mut self) {
-        if self.k8s_pods.is_empty() {
-            return;
+    /// Containers grouped into their Compose projects, then Podman pods, followed
+    /// by standalone ones.
+    fn docker_rows(&self) -> Vec<DockerRow> {
+        let mut groups: BTreeMap<(u8, String, String), ContainerGroup> = BTreeMap::new();
+        let mut standalone = Vec::new();
+        for (i, container) in self.docker_containers.iter().enumerate() {
+            if self.show_problem_containers
+                && container.health() != Some("unhealthy")
+                && !container.is_restarting()
+            {
+                continue;
+            }
+            let (order, kind) = match (&container.compose, &container.pod) {
+                (Some(info), _) => (0, GroupKind::Compose(info.clone())),
+                (None, Some(pod)) => (1, GroupKind::Pod(pod.clone())),
+                (None, None) => {
+                    standalone.push(i);
+                    continue;
+                }
+            };
+            let group = groups
+                .entry((order, kind.name().to_string(), container.host.clone()))
+                .or_insert_with(|| ContainerGroup {
+                    kind,
+                    host: container.host.clone(),
+                    containers: Vec::new(),
+                    running: 0,
+                    cpu_percent: 0.0,
+                    mem_bytes: 0.0,
+                });
+            group.containers.push(i);
+            if container.status.starts_with("Up") {
+                group.running += 1;
+            }
+            group.cpu_percent += container.cpu_percent;
+            group.mem_bytes += container
+                .mem_usage
+                .split('/')
+                .next()
+                .and_then(parse_size)
+                .unwrap_or(0.0);
         }
 
-        let i = match self.k8s_list_state.selected() {
-            Some(i) => {
-                if i >= self.k8s_pods.len() - 1 {
-                    0
-                } else {
-                    i + 1
+        let mut rows = Vec::new();
+        for mut group in groups.into_values() {
+            group.containers.sort_by_key(|&i| {
+                let container = &self.docker_containers[i];
+                let service = container.compose.as_ref().map(|c| c.service.clone());
+                (service, container.name.clone())
+            });
+            let children: Vec<usize> = if self.collapsed_projects.contains(&group.key()) {
+                Vec::new()
+            } else {
+                group.containers.clone()
+            };
+            rows.push(DockerRow::Group(group));
+            rows.extend(children.into_iter().map(DockerRow::Container));
+        }
+        rows.extend(standalone.into_iter().map(DockerRow::Container));
+        rows
+    }
+
+    fn selected_docker_row(&self) -> Option<DockerRow> {
+        let selected = self.docker_list_state.selected()?;
+        self.docker_rows().into_iter().nth(selected)
+    }
+
+    fn selected_container(&self) -> Option<&DockerContainer> {
+        match self.selected_docker_row()? {
+            DockerRow::Container(i) => self.docker_containers.get(i),
+            DockerRow::Group(_) => None,
+        }
+    }
+
+    /// The group of the selected header row, or of the selected container.
+    fn selected_group(&self) -> Option<GroupKind> {
+        match self.selected_docker_row()? {
+            DockerRow::Group(group) => Some(group.kind),
+            DockerRow::Container(i) => {
+                let container = self.docker_containers.get(i)?;
+                match (&container.compose, &container.pod) {
+                    (Some(info), _) => Some(GroupKind::Compose(info.clone())),
+                    (None, Some(pod)) => Some(GroupKind::Pod(pod.clone())),
+                    (None, None) => None,
                 }
             }
-            None => 0,
-        };
-        self.k8s_list_state.select(Some(i));
+        }
     }
 
-    fn previous_k8s_item(&mut self) {
-        if self.k8s_pods.is_empty() {
-            return;
+    fn toggle_selected_project(&mut self) {
+        if let Some(DockerRow::Group(group)) = self.selected_docker_row() {
+            let key = group.key();
+            if !self.collapsed_projects.remove(&key) {
+                self.collapsed_projects.insert(key);
+            }
         }
+    }
 
-        let i = match self.k8s_list_state.selected() {
-            Some(i) => {
-                if i == 0 {
-                    self.k8s_pods.len() - 1
-                } else {
-                    i - 1
+    /// Runs `docker compose up -d`, `down`, `restart` or `pull` for the selected
+    /// project, or `podman pod <pod_command>` for the selected pod.
+    fn run_group_action(&mut self, command: &[&str], pod_command: Option<&[&str]>) {
+        let (label, args) = match self.selected_group() {
+            Some(GroupKind::Compose(info)) => (
+                format!("compose {} ({})", command.join(" "), info.project),
+                compose_args(&info, command),
+            ),
+            Some(GroupKind::Pod(pod)) => {
+                if Runtime::current() != Runtime::Podman {
+                    self.message = KUBELET_PODS.to_string();
+                    return;
                 }
+                let Some(pod_command) = pod_command else {
+                    self.message = format!("Pods have no equivalent of compose {}", command[0]);
+                    return;
+                };
+                let mut args = vec!["pod".to_string()];
+                args.extend(pod_command.iter().map(|arg| arg.to_string()));
+                args.push(pod.clone());
+                (format!("pod {} ({})", pod_command.join(" "), pod), args)
+            }
+            None => {
+                self.message =
+                    "Select a Compose project, a pod or one of their containers".to_string();
+                return;
             }
-            None => 0,
         };
-        self.k8s_list_state.select(Some(i));
+        self.run_docker_task(label, vec![args]);
     }
 
-    fn update_container_stats(&mut self, container_id: &str, stats: ContainerStats) {
-        let history = self
-            .container_stats_history
-            .entry(container_id.to_string())
-            .or_insert_with(Vec::new);
+    /// Switches to the other containerd namespace; the next refresh lists its containers.
+    fn switch_containerd_namespace(&mut self) {
+        let namespace = next_containerd_namespace();
+        self.docker_images.clear();
+        self.reset_docker_host_state();
+        self.message = format!("containerd namespace: {}", namespace);
+    }
 
-        history.push(stats);
-        if history.len() > 60 {
-            history.remove(0);
+    /// Re-reads the Docker contexts, keeping the hosts added by hand.
+    fn load_docker_endpoints(&mut self) {
+        let added: Vec<Endpoint> = self
+            .docker_endpoints
+            .drain(..)
+            .filter(|endpoint| endpoint.context.is_none())
+            .collect();
+        self.docker_endpoints = list_contexts();
+        self.docker_endpoints.extend(added);
+    }
+
+    fn open_host_picker(&mut self) {
+        if Runtime::current() != Runtime::Docker {
+            self.message = format!(
+                "Hosts and contexts need the Docker CLI, not {}",
+                Runtime::current().name()
+            );
+            return;
         }
+        self.load_docker_endpoints();
+        let active = active_endpoint_name();
+        let selected = self
+            .docker_endpoints
+            .iter()
+            .position(|endpoint| endpoint.name == active)
+            .unwrap_or(0);
+        let mut list_state = TableState::default();
+        list_state.select(Some(selected));
+        self.host_picker = Some(HostPicker { list_state });
     }
-}
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    fn move_host_picker(&mut self, delta: isize) {
+        let Some(picker) = self.host_picker.as_mut() else {
+            return;
+        };
+        let len = self.docker_endpoints.len();
+        if len == 0 {
+            return;
+        }
+        let current = picker.list_state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).rem_euclid(len as isize) as usize;
+        picker.list_state.select(Some(next));
+    }
 
-    let mut sys = System::new_all();
-    let mut networks = Networks::new_with_refreshed_list();
+    /// Switches the Docker tab to the endpoint chosen in the picker.
+    fn use_picked_host(&mut self) {
+        let Some(picker) = self.host_picker.take() else {
+            return;
+        };
+        let Some(endpoint) = picker
+            .list_state
+            .selected()
+            .and_then(|i| self.docker_endpoints.get(i))
+            .cloned()
+        else {
+            return;
+        };
+        self.message = format!("Docker host: {} ({})", endpoint.name, endpoint.host);
+        select_endpoint(endpoint);
+        self.all_docker_hosts = false;
+        self.reset_docker_host_state();
+    }
 
-    let mut cpu_data = vec![0; 60];
-    let mut ram_data = vec![0; 60];
-    let mut network_receive_speed_data = vec![0; 60];
-    let mut network_send_speed_data = vec![0; 60];
+    fn toggle_all_docker_hosts(&mut self) {
+        if Runtime::current() != Runtime::Docker {
+            self.message = format!(
+                "Hosts and contexts need the Docker CLI, not {}",
+                Runtime::current().name()
+            );
+            return;
+        }
+        self.all_docker_hosts = !self.all_docker_hosts;
+        if self.all_docker_hosts {
+            self.load_docker_endpoints();
+            self.message = format!("Listing {} Docker hosts", self.docker_endpoints.len());
+        } else {
+            self.message = format!("Docker host: {}", active_endpoint_name());
+        }
+        self.reset_docker_host_state();
+    }
 
-    let mut prev_total_received = None;
-    let mut prev_total_transmitted = None;
-    let mut last_update = Instant::now();
+    /// Forgets everything read from the previous endpoint, so the next tick
+    /// re-lists containers and re-subscribes to events.
+    fn reset_docker_host_state(&mut self) {
+        self.docker_events = None;
+        self.docker_event_log.clear();
+        self.event_container_filter = None;
+        self.docker_stats_at = None;
+        self.docker_containers.clear();
+        self.silent_docker_hosts.clear();
+        self.host_listing = None;
+        self.relist_docker_hosts = false;
+        self.container_health = None;
+        self.health_task = None;
+        self.image_details.clear();
+        self.image_inspect = None;
+        self.image_users.clear();
+        self.image_users_task = None;
+        self.image_users_at = None;
+        self.disk_usage_at = None;
+        self.docker_volumes.clear();
+        self.volume_task = None;
+        self.docker_volumes_at = None;
+        self.docker_list_state.select(Some(0));
+    }
 
-    let mut app_state = AppState::new();
+    fn prompt_add_docker_host(&mut self) {
+        self.input_prompt = Some(InputPrompt {
+            title: "Add Docker host (unix:///path, tcp://host:2376 [--tlsverify | --tls], ssh://user@host; certificates from DOCKER_CERT_PATH)".to_string(),
+            input: TextInput::new(""),
+            action: PromptAction::AddDockerHost,
+        });
+    }
 
-    loop {
-        sys.refresh_all();
-        networks.refresh();
+    /// In the all-hosts view, targets the selected row's host until the guard
+    /// is dropped, so actions, logs and inspections reach the right daemon.
+    fn selected_host_guard(&self) -> Option<EndpointGuard> {
+        if !self.all_docker_hosts
+            || self.current_tab != MonitorTab::Docker
+            || self.docker_view != DockerView::Containers
+        {
+            return None;
+        }
+        let host = match self.selected_docker_row()? {
+            DockerRow::Group(group) => group.host,
+            DockerRow::Container(i) => self.docker_containers.get(i)?.host.clone(),
+        };
+        let endpoint = self
+            .docker_endpoints
+            .iter()
+            .find(|endpoint| endpoint.name == host)?;
+        Some(override_endpoint(endpoint.clone()))
+    }
 
-        let cpu_usage = sys.global_cpu_usage();
-        let memory = sys.total_memory();
-        let used_memory = sys.used_memory();
-        let memory_usage_percentage = (used_memory as f64 / memory as f64) * 100.0;
+    fn run_docker_task(&mut self, label: String, steps: Vec<Vec<String>>) {
+        self.message = format!("{}: running...", label);
+        self.docker_tasks.push(DockerTask::spawn(label, steps));
+    }
 
-        let mut total_received = 0;
-        let mut total_transmitted = 0;
-        for (_, network) in &networks {
-            total_received += network.received();
-            total_transmitted += network.transmitted();
-        }
+    fn selected_network(&self) -> Option<&DockerNetwork> {
+        self.docker_list_state
+            .selected()
+            .and_then(|i| self.docker_networks.get(i))
+    }
 
-        let elapsed_secs = last_update.elapsed().as_secs_f64().max(1e-6);
-        let mut receive_rate_kbs = 0.0;
-        let mut send_rate_kbs = 0.0;
+    fn selected_volume(&self) -> Option<&DockerVolume> {
+        self.docker_list_state
+            .selected()
+            .and_then(|i| self.docker_volumes.get(i))
+    }
 
-        if let (Some(prev_recv), Some(prev_send)) = (prev_total_received, prev_total_transmitted) {
-            let diff_recv = total_received.saturating_sub(prev_recv);
-            let diff_send = total_transmitted.saturating_sub(prev_send);
-            receive_rate_kbs = diff_recv as f64 / 1024.0 / elapsed_secs;
-            send_rate_kbs = diff_send as f64 / 1024.0 / elapsed_secs;
+    fn prompt_create(&mut self) {
+        let (title, action) = match self.docker_view {
+            DockerView::Networks => (
+                "Create network: NAME [DRIVER] [SUBNET]",
+                PromptAction::CreateNetwork,
+            ),
+            DockerView::Volumes => ("Create volume: NAME [DRIVER]", PromptAction::CreateVolume),
+            _ => return,
+        };
+        self.input_prompt = Some(InputPrompt {
+            title: title.to_string(),
+            input: TextInput::default(),
+            action,
+        });
+    }
+
+    /// Removes the selected network. Networks with attached containers are only
+    /// removed after typing their name, and those containers are disconnected first.
+    fn remove_selected_network(&mut self) {
+        let Some(network) = self.selected_network().cloned() else {
+            return;
+        };
+        if network.is_built_in() {
+            self.message = format!(
+                "{} is a built-in network and cannot be removed",
+                network.name
+            );
+            return;
         }
 
-        prev_total_received = Some(total_received);
-        prev_total_transmitted = Some(total_transmitted);
-        last_update = Instant::now();
+        let label = format!("Remove network {}", network.name);
+        let mut steps: Vec<Vec<String>> = network
+            .containers
+            .iter()
+            .map(|(container, _)| {
+                docker_args(&["network", "disconnect", "-f", &network.name, container])
+            })
+            .collect();
+        steps.push(docker_args(&["network", "rm", &network.name]));
+        if network.containers.is_empty() {
+            self.run_docker_task(label, steps);
+            return;
+        }
 
-        cpu_data.remove(0);
-        cpu_data.push(cpu_usage.round() as u64);
-        ram_data.remove(0);
-        ram_data.push(memory_usage_percentage as u64);
-        network_receive_speed_data.remove(0);
-        network_receive_speed_data.push(receive_rate_kbs.round() as u64);
-        network_send_speed_data.remove(0);
-        network_send_speed_data.push(send_rate_kbs.round() as u64);
+        let users: Vec<&str> = network.containers.iter().map(|(c, _)| c.as_str()).collect();
+        self.input_prompt = Some(InputPrompt {
+            title: format!(
+                "Network {} is used by {}. Type its name to disconnect them and remove it",
+                network.name,
+                users.join(", ")
+            ),
+            input: TextInput::default(),
+            action: PromptAction::ConfirmDocker {
+                expected: network.name,
+                label,
+                steps,
+            },
+        });
+    }
 
-        match app_state.current_tab {
-            MonitorTab::Docker => {
-                app_state.docker_containers = get_docker_containers_with_stats();
-
-                let mut updates = Vec::new();
-                for container in &app_state.docker_containers {
-                    let stats = ContainerStats {
-                        cpu_percent: container.cpu_percent,
-                        mem_percent: container.mem_percent,
-                        net_rx_kb: parse_net_io(&container.net_io).0,
-                        net_tx_kb: parse_net_io(&container.net_io).1,
-                    };
-                    updates.push((container.id.clone(), stats));
-                }
+    /// Removes the selected volume. A volume mounted by running containers is left
+    /// alone; one mounted only by stopped containers can go together with them after
+    /// typing the volume name to confirm.
+    fn remove_selected_volume(&mut self) {
+        let Some(volume) = self.selected_volume().cloned() else {
+            return;
+        };
 
-                for (id, stats) in updates {
-                    app_state.update_container_stats(&id, stats);
-                }
+        let label = format!("Remove volume {}", volume.name);
+        let names = |running: bool| -> Vec<&str> {
+            volume
+                .containers
+                .iter()
+                .filter(|user| user.running == running)
+                .map(|user| user.name.as_str())
+                .collect()
+        };
+        let running = names(true);
+        if !running.is_empty() {
+            self.message = format!(
+                "Volume {} is in use by running containers: {}. Stop them first",
+                volume.name,
+                running.join(", ")
+            );
+            return;
+        }
 
-                app_state.docker_images = get_docker_images();
+        let stopped = names(false);
+        // No `-f`: a container that was started again in the meantime makes this fail.
+        let mut steps: Vec<Vec<String>> = stopped
+            .iter()
+            .map(|container| docker_args(&["rm", container]))
+            .collect();
+        steps.push(docker_args(&["volume", "rm", &volume.name]));
+        if stopped.is_empty() {
+            self.run_docker_task(label, steps);
+            return;
+        }
+
+        self.input_prompt = Some(InputPrompt {
+            title: format!(
+                "Volume {} is used by stopped containers {}. Type the volume name to remove them and the volume",
+                volume.name,
+                stopped.join(", ")
+            ),
+            input: TextInput::default(),
+            action: PromptAction::ConfirmDocker {
+                expected: volume.name,
+                label,
+                steps,
+            },
+        });
+    }
+
+    fn prompt_prune(&mut self) {
+        let (kind, prune) = match self.docker_view {
+            DockerView::Networks => ("networks", Prune::Networks),
+            DockerView::Volumes => ("volumes", Prune::Volumes),
+            _ => return,
+        };
+        let args = match Runtime::current().prune_args(prune) {
+            Ok(args) => docker_args(&args),
+            Err(e) => {
+                self.message = e;
+                return;
             }
+        };
+        self.input_prompt = Some(InputPrompt {
+            title: format!("Remove all unused {}? Type 'yes' to confirm", kind),
+            input: TextInput::default(),
+            action: PromptAction::ConfirmDocker {
+                expected: "yes".to_string(),
+                label: format!("Prune {}", kind),
+                steps: vec![args],
+            },
+        });
+    }
 
-            MonitorTab::Kubernetes => {
-                app_state.k8s_pods = get_k8s_pods();
+    fn poll_docker_tasks(&mut self) {
+        let mut finished = Vec::new();
+        self.docker_tasks.retain_mut(|task| match task.poll() {
+            Some(message) => {
+                finished.push(message);
+                false
+            }
+            None => true,
+        });
+        for pull in &mut self.image_pulls {
+            if pull.poll() {
+                finished.push(match (&pull.error, &pull.digest) {
+                    (Some(error), _) => format!("Pull {}: failed: {}", pull.reference, error),
+                    (None, Some(digest)) => format!("Pulled {} ({})", pull.reference, digest),
+                    (None, None) => format!("Pulled {}", pull.reference),
+                });
             }
+        }
+        let mut refresh_browser = false;
+        for transfer in &mut self.file_transfers {
+            if transfer.poll() {
+                finished.push(match &transfer.result {
+                    Some(Err(e)) => format!("Copy {}: failed: {}", transfer.label, e),
+                    _ => format!("Copied {}", transfer.label),
+                });
+                if let (Some(Ok(())), Some((container, directory)), Some(browser)) = (
+                    &transfer.result,
+                    &transfer.upload_target,
+                    &self.file_browser,
+                ) {
+                    refresh_browser |=
+                        *container == browser.container_id && *directory == browser.path;
+                }
+            }
+        }
+        if refresh_browser {
+            if let Some(browser) = self.file_browser.as_mut() {
+                browser.refresh();
+            }
+        }
+        if !finished.is_empty() {
+            self.message = finished.join(" | ");
+            // Any finished task may have freed or used space.
+            self.disk_usage_at = None;
+            self.docker_volumes_at = None;
+        }
+    }
 
-            _ => {}
+    /// Applies Docker events and refreshes the containers. Stats are re-sampled
+    /// periodically, or on every tick when the event stream is down; in between,
+    /// container events re-list the containers right away, keeping their last stats.
+    /// The all-hosts view lists in the background and shows each host as it answers.
+    /// Returns true when stats were sampled.
+    fn refresh_docker_containers(&mut self) -> bool {
+        let endpoints = if self.all_docker_hosts {
+            self.all_host_endpoints()
+        } else {
+            Vec::new()
+        };
+        let stream = self
+            .docker_events
+            .get_or_insert_with(|| EventStream::spawn(&endpoints));
+        let events = stream.poll();
+        let live = stream.connected;
+        let containers_changed = events.iter().any(|event| event.affects_containers());
+        if events.iter().any(|event| event.affects_volumes()) {
+            self.docker_volumes_at = None;
+        }
+        if containers_changed {
+            self.image_users_at = None;
+        }
+        if events.iter().any(|event| event.kind == "image") {
+            self.image_details.clear();
+        }
+        self.docker_event_log.extend(events);
+        let overflow = self.docker_event_log.len().saturating_sub(EVENT_LOG_LIMIT);
+        self.docker_event_log.drain(..overflow);
+
+        let stats_due = !live
+            || self
+                .docker_stats_at
+                .is_none_or(|at| at.elapsed() >= DOCKER_STATS_INTERVAL);
+        if self.all_docker_hosts {
+            self.relist_docker_hosts |= containers_changed;
+            if self.host_listing.is_none() && (stats_due || self.relist_docker_hosts) {
+                self.host_listing = Some(HostListing::spawn(&endpoints, stats_due));
+                self.relist_docker_hosts = false;
+                if stats_due {
+                    self.docker_stats_at = Some(Instant::now());
+                }
+            }
+            return self.poll_host_listing();
         }
+        if stats_due {
+            self.docker_containers = host_containers(true);
+            self.docker_stats_at = Some(Instant::now());
+        } else if containers_changed {
+            let previous = std::mem::take(&mut self.docker_containers);
+            self.docker_containers = host_containers(false);
+            keep_stats(&mut self.docker_containers, &previous);
+        }
+        stats_due
+    }
 
-        terminal.draw(|f| {
-            let size = f.area();
+    /// The endpoints of the all-hosts view, one per address.
+    fn all_host_endpoints(&self) -> Vec<Endpoint> {
+        let mut endpoints: Vec<Endpoint> = Vec::new();
+        for endpoint in &self.docker_endpoints {
+            if !endpoints.iter().any(|e| e.host == endpoint.host) {
+                endpoints.push(endpoint.clone());
+            }
+        }
+        endpoints
+    }
 
-            if app_state.show_create_dialog {
-                render_create_dialog(f, size, &app_state.create_dialog_state);
-                return;
+    /// Replaces the containers of each host that answered. Once the listing is
+    /// over, hosts that did not answer within `HOST_TIMEOUT` are left out and
+    /// noted in `silent_docker_hosts`. Returns true when a stats listing finished.
+    fn poll_host_listing(&mut self) -> bool {
+        let Some(listing) = self.host_listing.as_mut() else {
+            return false;
+        };
+        for (host, mut containers) in listing.poll() {
+            let (previous, others): (Vec<DockerContainer>, Vec<DockerContainer>) =
+                std::mem::take(&mut self.docker_containers)
+                    .into_iter()
+                    .partition(|container| container.host == host);
+            if !listing.stats {
+                keep_stats(&mut containers, &previous);
             }
+            self.docker_containers = others;
+            self.docker_containers.extend(containers);
+        }
+        // Keep hosts in endpoint order, whatever order they answered in.
+        self.docker_containers.sort_by_key(|container| {
+            listing
+                .hosts
+                .iter()
+                .position(|host| *host == container.host)
+        });
+        if !listing.finished() {
+            return false;
+        }
+        let silent = listing.silent_hosts();
+        let stats = listing.stats;
+        self.host_listing = None;
+        self.docker_containers
+            .retain(|container| !silent.contains(&container.host));
+        self.silent_docker_hosts = silent;
+        stats
+    }
 
-            let tabs_block = Block::default().borders(Borders::ALL).title("Tabs");
-            let tabs = Tabs::new(vec![
-                "System (Ctrl+S)",
-                "Docker (Ctrl+D)",
-                "Kubernetes (Ctrl+K)",
-            ])
-            .block(tabs_block)
-            .select(match app_state.current_tab {
-                MonitorTab::System => 0,
-                MonitorTab::Docker => 1,
-                MonitorTab::Kubernetes => 2,
+    /// Inspects the selected container's health checks when the selection changes,
+    /// and again once the previous result is older than the stats interval.
+    /// The inspection runs in the background, one container at a time.
+    fn refresh_container_health(&mut self) {
+        if let Some(result) = self.health_task.as_mut().and_then(|task| task.poll()) {
+            if let Some(task) = self.health_task.take() {
+                self.container_health = Some((task.id, Instant::now(), result));
+            }
+        }
+        let Some(id) = self.selected_container().map(|c| c.id.clone()) else {
+            self.container_health = None;
+            return;
+        };
+        if self.health_task.is_some()
+            || matches!(&self.container_health, Some((cached, at, _))
+                if *cached == id && at.elapsed() < DOCKER_STATS_INTERVAL)
+        {
+            return;
+        }
+        self.health_task = Some(HealthTask::spawn(&id));
+    }
+
+    fn filtered_docker_events(&self) -> Vec<&DockerEvent> {
+        self.docker_event_log
+            .iter()
+            .filter(|event| self.event_kind_filter.is_none_or(|kind| event.kind == kind))
+            .filter(|event| {
+                self.event_container_filter
+                    .as_ref()
+                    .is_none_or(|(id, _)| event.kind == "container" && event.id.starts_with(id))
             })
-            .style(Style::default().fg(Color::White))
-            .highlight_style(
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD),
-            );
+            .collect()
+    }
 
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
-                .split(size);
+    fn cycle_event_kind_filter(&mut self) {
+        self.event_kind_filter = match self.event_kind_filter {
+            None => Some(EVENT_KINDS[0]),
+            Some(kind) => EVENT_KINDS
+                .iter()
+                .position(|k| *k == kind)
+                .and_then(|i| EVENT_KINDS.get(i + 1))
+                .copied(),
+        };
+        self.show_docker_events = true;
+    }
 
-            f.render_widget(tabs, chunks[0]);
+    /// Narrows the events panel to the selected container, or clears that filter.
+    fn toggle_event_container_filter(&mut self) {
+        if self.event_container_filter.take().is_some() {
+            return;
+        }
+        if let Some(container) = self.selected_container() {
+            self.event_container_filter = Some((container.id.clone(), container.name.clone()));
+            self.show_docker_events = true;
+        }
+    }
 
-            match app_state.current_tab {
-                MonitorTab::System => render_system_tab(
-                    f,
-                    chunks[1],
-                    &sys,
-                    &cpu_data,
-                    &ram_data,
-                    &network_receive_speed_data,
-                    &network_send_speed_data,
-                    cpu_usage,
-                    memory,
-                    used_memory,
-                    memory_usage_percentage,
-                    receive_rate_kbs,
-                    send_rate_kbs,
-                    total_received,
-                    total_transmitted,
-                ),
-                MonitorTab::Docker => render_docker_tab(f, chunks[1], &mut app_state),
-                MonitorTab::Kubernetes => render_k8s_tab(f, chunks[1], &mut app_state),
+    fn open_update_dialog(&mut self) {
+        let Some(container) = self.selected_container() else {
+            return;
+        };
+        match inspect_limits(&container.id) {
+            Ok(limits) => {
+                self.update_dialog = Some(UpdateDialogState::new(
+                    &container.id,
+                    &container.name,
+                    limits,
+                ))
             }
-        })?;
+            Err(e) => self.message = format!("Failed to inspect {}: {}", container.name, e),
+        }
+    }
 
-        if event::poll(Duration::from_millis(200))? {
-            if let Event::Key(key) = event::read()? {
-                if app_state.show_create_dialog {
-                    match key.code {
-                        KeyCode::Esc => {
-                            app_state.show_create_dialog = false;
-                            app_state.create_dialog_state = CreateDialogState::new();
-                        }
-                        KeyCode::Tab => {
-                            app_state.create_dialog_state.selected_field =
-                                (app_state.create_dialog_state.selected_field + 1) % 6;
-                        }
-                        KeyCode::BackTab => {
-                            app_state.create_dialog_state.selected_field =
-                                if app_state.create_dialog_state.selected_field == 0 {
-                                    5
-                                } else {
-                                    app_state.create_dialog_state.selected_field - 1
-                                };
-                        }
-                        KeyCode::Char(c) => {
-                            app_state
-                                .create_dialog_state
-                                .get_current_field_mut()
-                                .push(c);
-                        }
-                        KeyCode::Backspace => {
-                            app_state.create_dialog_state.get_current_field_mut().pop();
-                        }
-                        KeyCode::Enter => {
-                            let result = create_custom_container(&app_state.create_dialog_state);
-                            app_state.message = result;
-                            app_state.show_create_dialog = false;
-                            app_state.create_dialog_state = CreateDialogState::new();
-                        }
-                        _ => {}
-                    }
-                } else {
-                    match key.code {
-                        KeyCode::Char('q') => break,
-                        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            app_state.current_tab = MonitorTab::System;
-                        }
-                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            app_state.current_tab = MonitorTab::Docker;
-                        }
-                        KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                            app_state.current_tab = MonitorTab::Kubernetes;
-                        }
-                        KeyCode::Down => match app_state.current_tab {
-                            MonitorTab::Docker => app_state.next_docker_item(),
-                            MonitorTab::Kubernetes => app_state.next_k8s_item(),
-                            _ => {}
-                        },
-                        KeyCode::Up => match app_state.current_tab {
-                            MonitorTab::Docker => app_state.previous_docker_item(),
-                            MonitorTab::Kubernetes => app_state.previous_k8s_item(),
-                            _ => {}
-                        },
-                        KeyCode::Tab if app_state.current_tab == MonitorTab::Docker => {
-                            app_state.docker_view = match app_state.docker_view {
-                                DockerView::Containers => DockerView::Images,
-                                DockerView::Images => DockerView::Containers,
-                            };
-                            app_state.docker_list_state.select(Some(0));
-                        }
-                        KeyCode::Char('n') if app_state.current_tab == MonitorTab::Docker => {
-                            app_state.show_create_dialog = true;
-                            app_state.create_dialog_state = CreateDialogState::new();
-                        }
-                        KeyCode::Char('p') if app_state.current_tab == MonitorTab::Docker => {
-                            create_postgres_container();
-                            app_state.message = "Creating PostgreSQL container...".to_string();
-                        }
-                        KeyCode::Char('r') if app_state.current_tab == MonitorTab::Docker => {
-                            create_redis_container();
-                            app_state.message = "Creating Redis container...".to_string();
-                        }
-                        KeyCode::Char('m') if app_state.current_tab == MonitorTab::Docker => {
-                            create_mongodb_container();
-                            app_state.message = "Creating MongoDB container...".to_string();
-                        }
-                        KeyCode::Char('g') if app_state.current_tab == MonitorTab::Docker => {
-                            create_grafana_container();
-                            app_state.message = "Creating Grafana container...".to_string();
-                        }
-                        KeyCode::Char('x') if app_state.current_tab == MonitorTab::Docker => {
-                            if let Some(selected) = app_state.docker_list_state.selected() {
-                                match app_state.docker_view {
-                                    DockerView::Containers => {
-                                        if let Some(container) =
-                                            app_state.docker_containers.get(selected)
-                                        {
-                                            stop_docker_container(&container.id);
-                                            app_state.message =
-                                                format!("Stopped container: {}", container.name);
-                                        }
-                                    }
-                                    DockerView::Images => {
-                                        if let Some(image) = app_state.docker_images.get(selected) {
-                                            delete_docker_image(&image.image_id);
-                                            app_state.message =
-                                                format!("Deleted image: {}", image.repository);
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                        KeyCode::Char('s')
-                            if app_state.current_tab == MonitorTab::Docker
-                                && app_state.docker_view == DockerView::Containers =>
-                        {
-                            if let Some(selected) = app_state.docker_list_state.selected() {
-                                if let Some(container) = app_state.docker_containers.get(selected) {
-                                    start_docker_container(&container.id);
-                                    app_state.message =
-                                        format!("Started container: {}", container.name);
-                                }
-                            }
-                        }
-                        KeyCode::Char('t')
-                            if app_state.current_tab == MonitorTab::Docker
-                                && app_state.docker_view == DockerView::Containers =>
-                        {
-                            if let Some(selected) = app_state.docker_list_state.selected() {
-                                if let Some(container) = app_state.docker_containers.get(selected) {
-                                    restart_docker_container(&container.id);
-                                    app_state.message =
-                                        format!("Restarting container: {}", container.name);
-                                }
-                            }
-                        }
-                        KeyCode::Delete
-                            if app_state.current_tab == MonitorTab::Docker
-                                && app_state.docker_view == DockerView::Containers =>
-                        {
-                            if let Some(selected) = app_state.docker_list_state.selected() {
-                                if let Some(container) = app_state.docker_containers.get(selected) {
-                                    delete_docker_container(&container.id);
-                                    app_state.message =
-                                        format!("Deleted container: {}", container.name);
-                                }
-                            }
-                        }
-                        KeyCode::Char('d') if app_state.current_tab == MonitorTab::Kubernetes => {
-                            if let Some(selected) = app_state.k8s_list_state.selected() {
-                                if let Some(pod) = app_state.k8s_pods.get(selected) {
-                                    delete_k8s_pod(&pod.name, &pod.namespace);
-                                    app_state.message = format!("Deleted pod: {}", pod.name);
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
-                }
+    /// Starts `docker run` in the background. An invalid form stays open; the
+    /// preview already shows why.
+    fn submit_create_dialog(&mut self) {
+        let dialog = &self.create_dialog_state;
+        let values = dialog.values();
+        let Ok(args) = values.run_args() else {
+            return;
+        };
+        for (field, key) in CreateDialogState::HISTORY_KEYS.iter().enumerate() {
+            if !key.is_empty() {
+                self.input_history.record(key, dialog.inputs[field].value());
             }
         }
+        let label = format!("Create container from {}", values.image.trim());
+        self.show_create_dialog = false;
+        self.create_dialog_state = CreateDialogState::new();
+        self.run_docker_task(label, vec![args]);
     }
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+    /// Runs `docker update` with the changed limits, or keeps the dialog open
+    /// with the validation error.
+    fn submit_update_dialog(&mut self) {
+        let Some(dialog) = self.update_dialog.as_mut() else {
+            return;
+        };
+        match dialog
+            .current
+            .update_args(&dialog.values(), &dialog.container_id)
+        {
+            Ok(args) => {
+                let label = format!("Update {}", dialog.container_name);
+                self.update_dialog = None;
+                self.run_docker_task(label, vec![args]);
+            }
+            Err(e) => dialog.error = Some(e),
+        }
+    }
 
-    Ok(())
-}
+    /// Recomputes disk usage in the background every 30 seconds while the view is open.
+    /// Re-lists volumes in the background when a volume event arrived or the
+    /// list is older than `SLOW_LISTING_REFRESH`.
+    fn refresh_docker_volumes(&mut self) {
+        if let Some(volumes) = self.volume_task.as_mut().and_then(|task| task.poll()) {
+            self.docker_volumes = volumes;
+            self.volume_task = None;
+            self.docker_volumes_at = Some(Instant::now());
+            let len = self.docker_volumes.len();
+            clamp_selection(&mut self.docker_list_state, len);
+        }
+        let stale = self
+            .docker_volumes_at
+            .is_none_or(|at| at.elapsed() >= SLOW_LISTING_REFRESH);
+        if self.volume_task.is_none() && stale {
+            self.volume_task = Some(VolumeListTask::spawn());
+        }
+    }
 
-fn render_system_tab(
-    f: &mut ratatui::Frame,
-    area: Rect,
-    sys: &System,
-    cpu_data: &[u64],
-    ram_data: &[u64],
-    network_receive_speed_data: &[u64],
-    network_send_speed_data: &[u64],
-    cpu_usage: f32,
+    fn refresh_disk_usage(&mut self) {
+        if let Some(result) = self.disk_task.as_mut().and_then(|task| task.poll()) {
+            self.disk_usage = Some(result);
+            self.disk_task = None;
+            self.disk_usage_at = Some(Instant::now());
+            if let Some((category, all_images)) = self.prune_request.take() {
+                self.open_prune_preview(category, all_images);
+            }
+        }
+        let stale = self
+            .disk_usage_at
+            .is_none_or(|at| at.elapsed() >= Duration::from_secs(30));
+        if self.disk_task.is_none() && stale {
+            self.disk_task = Some(DiskUsageTask::spawn());
+        }
+    }
+
+    /// Lists exactly what pruning the selected disk usage category would remove.
+    /// `all_images` previews `image prune -a` instead of dangling images only.
+    /// The preview opens once disk usage has been recomputed, so it lists what is there now.
+    fn preview_prune(&mut self, all_images: bool) {
+        let category = self.docker_list_state.selected().unwrap_or(0);
+        if let Err(e) = Runtime::current().prune_args(disk_prune(category, all_images)) {
+            self.message = e;
+            return;
+        }
+        self.prune_request = Some((category, all_images));
+        if self.disk_task.is_none() {
+            self.disk_task = Some(DiskUsageTask::spawn());
+        }
+        self.message = "Computing disk usage...".to_string();
+    }
+
+    fn open_prune_preview(&mut self, category: usize, all_images: bool) {
+        let usage = match &self.disk_usage {
+            Some(Ok(usage)) => usage,
+            Some(Err(e)) => {
+                self.message = format!("Failed to compute disk usage: {}", e);
+                return;
+            }
+            None => return,
+        };
+        let args = match Runtime::current().prune_args(disk_prune(category, all_images)) {
+            Ok(args) => args,
+            Err(e) => {
+                self.message = e;
+                return;
+            }
+        };
+        let (title, items): (&str, Vec<(String, String)>) = match category {
+            0 => (
+                if all_images {
+                    "Prune unused images"
+                } else {
+                    "Prune dangling images"
+                },
+                usage
+                    .images
+                    .iter()
+                    .filter(|image| !image.in_use() && (all_images || image.dangling()))
+                    .map(|image| (image.name(), image.unique_size.clone()))
+                    .collect(),
+            ),
+            1 => (
+                "Prune stopped containers",
+                usage
+                    .containers
+                    .iter()
+                    .filter(|container| container.stopped())
+                    .map(|container| {
+                        (
+                            format!("{} ({})", container.names, container.status),
+                            container.size.clone(),
+                        )
+                    })
+                    .collect(),
+            ),
+            2 => (
+                "Prune unused volumes",
+                usage
+                    .volumes
+                    .iter()
+                    .filter(|volume| !volume.in_use())
+                    .map(|volume| (volume.name.clone(), volume.size.clone()))
+                    .collect(),
+            ),
+            _ => (
+                "Prune unused build cache",
+                usage
+                    .build_cache
+                    .iter()
+                    .filter(|cache| !cache.in_use())
+                    .map(|cache| {
+                        let id: String = cache.id.chars().take(12).collect();
+                        (
+                            format!("{} {} {}", id, cache.cache_type, cache.description),
+                            cache.size.clone(),
+                        )
+                    })
+                    .collect(),
+            ),
+        };
+        self.message.clear();
+        if items.is_empty() {
+            self.message = format!("{}: nothing to remove", title);
+            return;
+        }
+        // Container sizes read like "2B (virtual 187MB)"; only the first part is freed.
+        let total_bytes = items
+            .iter()
+            .filter_map(|(_, size)| size.split_whitespace().next().and_then(parse_size))
+            .sum();
+        let mut list_state = TableState::default();
+        list_state.select(Some(0));
+        self.prune_preview = Some(PrunePreview {
+            title: title.to_string(),
+            items,
+            total_bytes,
+            args: docker_args(&args),
+            list_state,
+        });
+    }
+
+    fn move_prune_preview(&mut self, delta: isize) {
+        let Some(preview) = self.prune_preview.as_mut() else {
+            return;
+        };
+        let last = preview.items.len().saturating_sub(1) as isize;
+        let selected = preview.list_state.selected().unwrap_or(0) as isize;
+        preview
+            .list_state
+            .select(Some((selected + delta).clamp(0, last) as usize));
+    }
+
+    fn confirm_prune(&mut self) {
+        if let Some(preview) = self.prune_preview.take() {
+            self.run_docker_task(preview.title, vec![preview.args]);
+        }
+    }
+
+    /// Marks the images containers were created from, re-computed in the
+    /// background after container events or `SLOW_LISTING_REFRESH`.
+    fn refresh_image_users(&mut self) {
+        if let Some(users) = self.image_users_task.as_mut().and_then(|task| task.poll()) {
+            self.image_users = users;
+            self.image_users_task = None;
+            self.image_users_at = Some(Instant::now());
+        }
+        let stale = self
+            .image_users_at
+            .is_none_or(|at| at.elapsed() >= SLOW_LISTING_REFRESH);
+        if self.image_users_task.is_none() && stale {
+            self.image_users_task = Some(ImageUsersTask::spawn());
+        }
+        for image in &mut self.docker_images {
+            image.in_use = self
+                .image_users
+                .keys()
+                .any(|id| id.starts_with(&image.image_id));
+        }
+    }
+
+    /// Inspects the selected image in the background unless it is cached,
+    /// one image at a time.
+    fn refresh_image_details(&mut self) {
+        if let Some(result) = self.image_inspect.as_mut().and_then(|task| task.poll()) {
+            if let Some(task) = self.image_inspect.take() {
+                self.image_details.insert(task.id, result);
+            }
+        }
+        if self.image_inspect.is_some() {
+            return;
+        }
+        let Some(image) = self.selected_image() else {
+            return;
+        };
+        if !self.image_details.contains_key(&image.image_id) {
+            self.image_inspect = Some(ImageInspectTask::spawn(&image.image_id));
+        }
+    }
+
+    fn selected_image(&self) -> Option<&DockerImage> {
+        self.docker_list_state
+            .selected()
+            .and_then(|i| self.docker_images.get(i))
+    }
+
+    /// Tab completions for the open prompt: local images or host paths.
+    fn prompt_completions(&self) -> Vec<String> {
+        let Some(prompt) = &self.input_prompt else {
+            return Vec::new();
+        };
+        match prompt.action {
+            PromptAction::PullImage
+            | PromptAction::Container {
+                command: ContainerCommand::Commit,
+                ..
+            } => image_references(&self.docker_images),
+            PromptAction::DownloadFile { .. }
+            | PromptAction::UploadFile { .. }
+            | PromptAction::Container {
+                command: ContainerCommand::Export,
+                ..
+            } => path_completions(prompt.input.value()),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Bracketed paste goes to whichever text field has focus.
+    fn paste(&mut self, text: &str) {
+        if self.show_create_dialog {
+            let dialog = &mut self.create_dialog_state;
+            let multi_line = dialog.is_multi_line();
+            if let Some(input) = dialog.current_input() {
+                input.paste(text, multi_line);
+            }
+        } else if let Some(dialog) = self.update_dialog.as_mut() {
+            dialog.inputs[dialog.selected_field].paste(text, false);
+        } else if let Some(prompt) = self.input_prompt.as_mut() {
+            prompt.input.paste(text, false);
+        } else if let Some(input) = self
+            .yaml_view
+            .as_mut()
+            .and_then(|view| view.search_input.as_mut())
+        {
+            input.push_str(text.lines().next().unwrap_or_default());
+        } else if let Some(picker) = self.resource_picker.as_mut() {
+            picker
+                .filter
+                .push_str(text.lines().next().unwrap_or_default());
+            picker.list_state.select(Some(0));
+        }
+    }
+
+    fn prompt_pull_image(&mut self) {
+        if self.docker_images.is_empty() {
+            self.docker_images = get_docker_images();
+        }
+        self.input_prompt = Some(InputPrompt {
+            title: "Pull image (e.g. nginx:latest, ghcr.io/org/app:1.2)".to_string(),
+            input: TextInput::default(),
+            action: PromptAction::PullImage,
+        });
+    }
+
+    /// Follows the logs of the selected project, Compose service or container.
+    fn open_docker_logs(&mut self) {
+        let tail = ["logs", "-f", "--tail", "500"];
+        let (title, args) = match self.selected_docker_row() {
+            Some(DockerRow::Group(group)) => match &group.kind {
+                GroupKind::Compose(info) => (
+                    format!("Compose project {}", info.project),
+                    compose_args(info, &tail),
+                ),
+                GroupKind::Pod(_) if Runtime::current() != Runtime::Podman => {
+                    self.message = KUBELET_PODS.to_string();
+                    return;
+                }
+                GroupKind::Pod(pod) => {
+                    let mut args = vec!["pod".to_string()];
+                    args.extend(tail.iter().map(|arg| arg.to_string()));
+                    args.push(pod.clone());
+                    (format!("Pod {}", pod), args)
+                }
+            },
+            Some(DockerRow::Container(i)) => {
+                let container = &self.docker_containers[i];
+                match &container.compose {
+                    // All replicas of the service, prefixed by compose.
+                    Some(info) => {
+                        let mut command = tail.to_vec();
+                        command.push(&info.service);
+                        (
+                            format!("Compose service {}/{}", info.project, info.service),
+                            compose_args(info, &command),
+                        )
+                    }
+                    None => {
+                        let mut args: Vec<String> = tail.iter().map(|a| a.to_string()).collect();
+                        args.push(container.id.clone());
+                        (format!("Container {}", container.name), args)
+                    }
+                }
+            }
+            None => return,
+        };
+        self.log_view = Some(LogView::new(title, CommandLogStream::spawn(args)));
+    }
+
+    fn k8s_list(&mut self) -> (usize, &mut TableState) {
+        match self.k8s_view {
+            K8sView::Pods => (self.k8s_pods.len(), &mut self.k8s_list_state),
+            K8sView::Services => (self.k8s_services.len(), &mut self.service_list_state),
+            K8sView::Ingresses => (self.k8s_ingresses.len(), &mut self.ingress_list_state),
+            K8sView::ConfigMaps => (self.k8s_config_maps.len(), &mut self.config_map_list_state),
+            K8sView::Secrets => (self.k8s_secrets.len(), &mut self.secret_list_state),
+            K8sView::Jobs => (self.k8s_jobs.len(), &mut self.job_list_state),
+            K8sView::CronJobs => (self.k8s_cron_jobs.len(), &mut self.cron_job_list_state),
+            K8sView::Resources => (self.k8s_generic_rows.len(), &mut self.generic_list_state),
+        }
+    }
+
+    fn next_k8s_item(&mut self) {
+        self.reset_data_key();
+        let (items_len, list_state) = self.k8s_list();
+        if items_len == 0 {
+            return;
+        }
+
+        let i = match list_state.selected() {
+            Some(i) => {
+                if i >= items_len - 1 {
+                    0
+                } else {
+                    i + 1
+                }
+            }
+            None => 0,
+        };
+        list_state.select(Some(i));
+    }
+
+    fn previous_k8s_item(&mut self) {
+        self.reset_data_key();
+        let (items_len, list_state) = self.k8s_list();
+        if items_len == 0 {
+            return;
+        }
+
+        let i = match list_state.selected() {
+            Some(i) => {
+                if i == 0 {
+                    items_len - 1
+                } else {
+                    i - 1
+                }
+            }
+            None => 0,
+        };
+        list_state.select(Some(i));
+    }
+
+    fn update_container_stats(&mut self, container_id: &str, stats: ContainerStats) {
+        let history = self
+            .container_stats_history
+            .entry(container_id.to_string())
+            .or_default();
+
+        history.push(stats);
+        if history.len() > 60 {
+            history.remove(0);
+        }
+    }
+
+    fn connect_k8s(&mut self) {
+        if self.k8s_client.is_some() || self.k8s_connect.is_some() {
+            return;
+        }
+        self.message = "Connecting to Kubernetes...".to_string();
+        self.k8s_connect = Some(KubeTask::spawn(KubeClient::from_default_config));
+    }
+
+    fn finish_k8s_connect(&mut self) {
+        let Some(result) = self.k8s_connect.as_mut().and_then(|task| task.poll()) else {
+            return;
+        };
+        self.k8s_connect = None;
+
+        match result {
+            Ok(client) => {
+                let client = Arc::new(client);
+                self.pod_watcher = Some(Watcher::spawn(client.clone(), "/api/v1/pods"));
+                self.metrics_poller = Some(MetricsPoller::spawn(client.clone()));
+                self.message = format!("Connected to context: {}", client.config().context);
+                self.k8s_client = Some(client);
+            }
+            Err(e) => self.message = format!("Kubernetes unavailable: {}", e),
+        }
+    }
+
+    fn refresh_k8s_pods(&mut self) {
+        let Some(watcher) = self.pod_watcher.as_mut() else {
+            return;
+        };
+
+        if watcher.poll() {
+            if let Some(error) = &watcher.last_error {
+                self.message = format!("Watch error: {}", error);
+            }
+            self.update_pod_rows();
+        }
+    }
+
+    fn update_pod_rows(&mut self) {
+        let Some(watcher) = &self.pod_watcher else {
+            return;
+        };
+
+        self.k8s_pods = watcher
+            .items()
+            .map(K8sPod::from_pod)
+            .filter(|pod| match &self.pod_selector {
+                Some(filter) => {
+                    pod.namespace == filter.namespace && pod.matches_selector(&filter.selector)
+                }
+                None => true,
+            })
+            .collect();
+        clamp_selection(&mut self.k8s_list_state, self.k8s_pods.len());
+    }
+
+    /// Switches between the Pods, Services and Ingresses views, starting the
+    /// watches for a view the first time it is opened.
+    fn switch_k8s_view(&mut self, view: K8sView) {
+        if let Some(client) = self.k8s_client.clone() {
+            if view == K8sView::Services && self.service_watcher.is_none() {
+                self.service_watcher = Some(Watcher::spawn(client.clone(), "/api/v1/services"));
+                self.endpoint_slice_watcher = Some(Watcher::spawn(
+                    client,
+                    "/apis/discovery.k8s.io/v1/endpointslices",
+                ));
+            } else if view == K8sView::Ingresses && self.ingress_watcher.is_none() {
+                self.ingress_watcher = Some(Watcher::spawn(
+                    client,
+                    "/apis/networking.k8s.io/v1/ingresses",
+                ));
+            } else if view == K8sView::ConfigMaps && self.config_map_watcher.is_none() {
+                self.config_map_watcher = Some(Watcher::spawn(client, "/api/v1/configmaps"));
+            } else if view == K8sView::Secrets && self.secret_watcher.is_none() {
+                // Never pull every Secret in the cluster, only those of the context's namespace.
+                let path = format!("/api/v1/namespaces/{}/secrets", client.config().namespace);
+                self.secret_watcher = Some(Watcher::spawn(client, &path));
+            } else if matches!(view, K8sView::Jobs | K8sView::CronJobs) {
+                // The CronJobs view lists each CronJob's jobs, so it needs both.
+                if self.job_watcher.is_none() {
+                    self.job_watcher = Some(Watcher::spawn(client.clone(), "/apis/batch/v1/jobs"));
+                }
+                if view == K8sView::CronJobs && self.cron_job_watcher.is_none() {
+                    self.cron_job_watcher = Some(Watcher::spawn(client, "/apis/batch/v1/cronjobs"));
+                }
+            } else if view == K8sView::Resources && self.browsed_resource.is_none() {
+                self.open_resource_picker();
+            }
+        }
+        self.k8s_view = view;
+        self.data_focus = false;
+        self.reset_data_key();
+    }
+
+    fn refresh_k8s_config_data(&mut self) {
+        if let Some(watcher) = self.config_map_watcher.as_mut() {
+            if watcher.poll() {
+                if let Some(error) = &watcher.last_error {
+                    self.message = format!("Watch error: {}", error);
+                }
+                self.k8s_config_maps = watcher
+                    .items()
+                    .map(K8sDataObject::from_config_map)
+                    .collect();
+                clamp_selection(&mut self.config_map_list_state, self.k8s_config_maps.len());
+            }
+        }
+        if let Some(watcher) = self.secret_watcher.as_mut() {
+            if watcher.poll() {
+                if let Some(error) = &watcher.last_error {
+                    self.message = format!("Watch error: {}", error);
+                }
+                self.k8s_secrets = watcher.items().map(K8sDataObject::from_secret).collect();
+                clamp_selection(&mut self.secret_list_state, self.k8s_secrets.len());
+            }
+        }
+    }
+
+    fn refresh_k8s_jobs(&mut self) {
+        if let Some(watcher) = self.job_watcher.as_mut() {
+            if watcher.poll() {
+                if let Some(error) = &watcher.last_error {
+                    self.message = format!("Watch error: {}", error);
+                }
+                let mut jobs: Vec<K8sJob> = watcher.items().map(K8sJob::from_job).collect();
+                // Newest first, like the order jobs are usually inspected in.
+                jobs.sort_by_key(|job| std::cmp::Reverse(job.created));
+                self.k8s_jobs = jobs;
+                clamp_selection(&mut self.job_list_state, self.k8s_jobs.len());
+            }
+        }
+        if let Some(watcher) = self.cron_job_watcher.as_mut() {
+            if watcher.poll() {
+                if let Some(error) = &watcher.last_error {
+                    self.message = format!("Watch error: {}", error);
+                }
+                self.k8s_cron_jobs = watcher.items().map(K8sCronJob::from_cron_job).collect();
+                clamp_selection(&mut self.cron_job_list_state, self.k8s_cron_jobs.len());
+            }
+        }
+    }
+
+    fn refresh_k8s_resources(&mut self) {
+        if let Some(result) = self.discovery.as_mut().and_then(|task| task.poll()) {
+            self.discovery = None;
+            match result {
+                Ok(resources) => self.api_resources = resources,
+                Err(e) => self.message = format!("API discovery failed: {}", e),
+            }
+        }
+        let (Some(watcher), Some((_, columns))) =
+            (self.generic_watcher.as_mut(), &self.browsed_resource)
+        else {
+            return;
+        };
+        if watcher.poll() {
+            if let Some(error) = &watcher.last_error {
+                self.message = format!("Watch error: {}", error);
+            }
+            self.k8s_generic_rows = watcher
+                .items()
+                .map(|object| GenericRow::from_object(object, columns))
+                .collect();
+            clamp_selection(&mut self.generic_list_state, self.k8s_generic_rows.len());
+        }
+    }
+
+    /// Opens the kind picker, running API discovery the first time.
+    fn open_resource_picker(&mut self) {
+        let Some(client) = self.k8s_client.clone() else {
+            self.message = "Kubernetes is not connected".to_string();
+            return;
+        };
+        if self.api_resources.is_empty() && self.discovery.is_none() {
+            self.discovery = Some(DiscoveryTask::spawn(client));
+        }
+        let mut list_state = TableState::default();
+        list_state.select(Some(0));
+        self.resource_picker = Some(ResourcePicker {
+            filter: String::new(),
+            list_state,
+        });
+    }
+
+    fn move_resource_picker(&mut self, delta: isize) {
+        let Some(picker) = self.resource_picker.as_mut() else {
+            return;
+        };
+        let len = filter_api_resources(&self.api_resources, &picker.filter).len();
+        if len == 0 {
+            return;
+        }
+        let current = picker.list_state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).rem_euclid(len as isize) as usize;
+        picker.list_state.select(Some(next));
+    }
+
+    /// Starts watching the kind chosen in the picker.
+    fn browse_picked_resource(&mut self) {
+        let (Some(client), Some(picker)) = (self.k8s_client.clone(), self.resource_picker.take())
+        else {
+            return;
+        };
+        let resources = filter_api_resources(&self.api_resources, &picker.filter);
+        let Some(resource) = picker
+            .list_state
+            .selected()
+            .and_then(|i| resources.get(i))
+            .map(|r| (*r).clone())
+        else {
+            return;
+        };
+
+        self.generic_watcher = Some(Watcher::spawn(client.clone(), &resource.list_path()));
+        self.k8s_generic_rows.clear();
+        self.generic_list_state.select(Some(0));
+        self.message = format!("Browsing {}", resource.full_name());
+        // Rows show up with the default columns until the CRD has been read.
+        let picked = resource.clone();
+        self.columns_task = Some(KubeTask::spawn(move || {
+            (picked.full_name(), printer_columns(&client, &picked))
+        }));
+        self.browsed_resource = Some((resource, Vec::new()));
+    }
+
+    fn open_generic_yaml(&mut self) {
+        let Some((resource, _)) = &self.browsed_resource else {
+            return;
+        };
+        let Some(row) = self
+            .generic_list_state
+            .selected()
+            .and_then(|i| self.k8s_generic_rows.get(i))
+        else {
+            return;
+        };
+
+        let namespace = resource.namespaced.then_some(row.namespace.as_str());
+        let path = api_path(
+            &resource.group_version,
+            &resource.plural,
+            namespace,
+            &row.name,
+        );
+        self.open_yaml(path);
+    }
+
+    fn delete_selected_pod(&mut self) {
+        let Some(pod) = self
+            .k8s_list_state
+            .selected()
+            .and_then(|i| self.k8s_pods.get(i))
+        else {
+            return;
+        };
+        let (name, namespace) = (pod.name.clone(), pod.namespace.clone());
+        let pending = format!("Deleting pod {}...", name);
+        self.run_k8s_action(pending, move |client| {
+            match delete_k8s_pod(client, &name, &namespace) {
+                Ok(()) => format!("Deleted pod: {}", name),
+                Err(e) => format!("Failed to delete pod: {}", e),
+            }
+        });
+    }
+
+    fn selected_cron_job(&self) -> Option<&K8sCronJob> {
+        self.cron_job_list_state
+            .selected()
+            .and_then(|i| self.k8s_cron_jobs.get(i))
+    }
+
+    /// Sends a change to the API server in the background; `call` returns the
+    /// message to show once it is done.
+    fn run_k8s_action(
+        &mut self,
+        pending: String,
+        call: impl FnOnce(&KubeClient) -> String + Send + 'static,
+    ) {
+        let Some(client) = self.k8s_client.clone() else {
+            return;
+        };
+        self.message = pending;
+        self.k8s_actions
+            .push(KubeTask::spawn(move || call(&client)));
+    }
+
+    fn trigger_selected_cron_job(&mut self) {
+        let Some(cron_job) = self.selected_cron_job().cloned() else {
+            return;
+        };
+        let pending = format!("Triggering cronjob/{}...", cron_job.name);
+        self.run_k8s_action(pending, move |client| {
+            match trigger_cron_job(client, &cron_job) {
+                Ok(job) => format!("Created job {} from cronjob/{}", job, cron_job.name),
+                Err(e) => format!("Failed to trigger {}: {}", cron_job.name, e),
+            }
+        });
+    }
+
+    fn toggle_selected_cron_job_suspend(&mut self) {
+        let Some(cron_job) = self.selected_cron_job().cloned() else {
+            return;
+        };
+        let suspend = !cron_job.suspend;
+        let pending = format!("Updating cronjob/{}...", cron_job.name);
+        self.run_k8s_action(pending, move |client| {
+            match set_cron_job_suspended(client, &cron_job, suspend) {
+                Ok(()) if suspend => format!("Suspended cronjob/{}", cron_job.name),
+                Ok(()) => format!("Resumed cronjob/{}", cron_job.name),
+                Err(e) => format!("Failed to update {}: {}", cron_job.name, e),
+            }
+        });
+    }
+
+    /// Pods created by `job`, found through the `job-name` label.
+    fn job_pods(&self, job: &K8sJob) -> Vec<K8sPod> {
+        let (label, value) = job.pod_selector();
+        let Some(watcher) = &self.pod_watcher else {
+            return Vec::new();
+        };
+        watcher
+            .items()
+            .map(K8sPod::from_pod)
+            .filter(|pod| {
+                pod.namespace == job.namespace
+                    && pod.labels.get(label).map(String::as_str) == Some(value)
+            })
+            .collect()
+    }
+
+    fn open_logs(&mut self, title: String, namespace: &str, pods: &[K8sPod]) {
+        let Some(client) = self.k8s_client.clone() else {
+            self.message = "Kubernetes is not connected".to_string();
+            return;
+        };
+        let targets: Vec<(String, String)> = pods
+            .iter()
+            .flat_map(|pod| {
+                pod.containers
+                    .iter()
+                    .map(|c| (pod.name.clone(), c.name.clone()))
+            })
+            .collect();
+        if targets.is_empty() {
+            self.message = format!("No containers to show logs for in {}", title);
+            return;
+        }
+
+        let stream = LogStream::spawn(client, namespace, targets);
+        self.log_view = Some(LogView::new(title, stream));
+    }
+
+    fn open_selected_logs(&mut self) {
+        match self.k8s_view {
+            K8sView::Pods => {
+                let Some(pod) = self
+                    .k8s_list_state
+                    .selected()
+                    .and_then(|i| self.k8s_pods.get(i))
+                    .cloned()
+                else {
+                    return;
+                };
+                let title = format!("Logs pod/{}/{}", pod.namespace, pod.name);
+                self.open_logs(title, &pod.namespace, std::slice::from_ref(&pod));
+            }
+            K8sView::Jobs => {
+                let Some(job) = self
+                    .job_list_state
+                    .selected()
+                    .and_then(|i| self.k8s_jobs.get(i))
+                    .cloned()
+                else {
+                    return;
+                };
+                let pods = self.job_pods(&job);
+                if pods.is_empty() {
+                    self.message = format!("Job {} has no pods left", job.name);
+                    return;
+                }
+                let title = format!(
+                    "Logs job/{}/{} ({} pods)",
+                    job.namespace,
+                    job.name,
+                    pods.len()
+                );
+                self.open_logs(title, &job.namespace, &pods);
+            }
+            _ => {}
+        }
+    }
+
+    /// The ConfigMap or Secret selected in the current view.
+    fn selected_data_object(&self) -> Option<&K8sDataObject> {
+        match self.k8s_view {
+            K8sView::ConfigMaps => self
+                .config_map_list_state
+                .selected()
+                .and_then(|i| self.k8s_config_maps.get(i)),
+            K8sView::Secrets => self
+                .secret_list_state
+                .selected()
+                .and_then(|i| self.k8s_secrets.get(i)),
+            _ => None,
+        }
+    }
+
+    fn reset_data_key(&mut self) {
+        self.data_key_state.select(Some(0));
+        self.show_data_value = false;
+        self.reveal_secret = false;
+    }
+
+    fn next_data_key(&mut self) {
+        let Some(len) = self.selected_data_object().map(|o| o.entries.len()) else {
+            return;
+        };
+        if len == 0 {
+            return;
+        }
+
+        let i = match self.data_key_state.selected() {
+            Some(i) if i + 1 < len => i + 1,
+            _ => 0,
+        };
+        self.data_key_state.select(Some(i));
+        self.reveal_secret = false;
+    }
+
+    fn previous_data_key(&mut self) {
+        let Some(len) = self.selected_data_object().map(|o| o.entries.len()) else {
+            return;
+        };
+        if len == 0 {
+            return;
+        }
+
+        let i = match self.data_key_state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => len - 1,
+        };
+        self.data_key_state.select(Some(i));
+        self.reveal_secret = false;
+    }
+
+    fn copy_data_value(&mut self) {
+        let Some(object) = self.selected_data_object() else {
+            return;
+        };
+        let Some(entry) = self
+            .data_key_state
+            .selected()
+            .and_then(|i| object.entries.get(i))
+        else {
+            self.message = format!("{} has no keys", object.name);
+            return;
+        };
+
+        self.message = match copy_to_clipboard(&entry.value) {
+            Ok(()) => format!(
+                "Copied {} of {}/{} to the clipboard ({})",
+                entry.key,
+                object.namespace,
+                object.name,
+                format_bytes(entry.value.len() as u64)
+            ),
+            Err(e) => format!("Failed to copy: {}", e),
+        };
+    }
+
+    fn refresh_k8s_network(&mut self) {
+        if let Some(watcher) = self.service_watcher.as_mut() {
+            if watcher.poll() {
+                if let Some(error) = &watcher.last_error {
+                    self.message = format!("Watch error: {}", error);
+                }
+                self.k8s_services = watcher.items().map(K8sService::from_service).collect();
+                clamp_selection(&mut self.service_list_state, self.k8s_services.len());
+            }
+        }
+        if let Some(watcher) = self.endpoint_slice_watcher.as_mut() {
+            watcher.poll();
+        }
+        if let Some(watcher) = self.ingress_watcher.as_mut() {
+            if watcher.poll() {
+                if let Some(error) = &watcher.last_error {
+                    self.message = format!("Watch error: {}", error);
+                }
+                self.k8s_ingresses = watcher.items().map(K8sIngress::from_ingress).collect();
+                clamp_selection(&mut self.ingress_list_state, self.k8s_ingresses.len());
+            }
+        }
+    }
+
+    /// Jumps from the selected Service to the pods its selector matches.
+    fn show_service_pods(&mut self) {
+        let Some(service) = self
+            .service_list_state
+            .selected()
+            .and_then(|i| self.k8s_services.get(i))
+        else {
+            return;
+        };
+        if service.selector.is_empty() {
+            self.message = format!("Service {} has no selector", service.name);
+            return;
+        }
+
+        self.message = format!(
+            "Showing pods selected by svc/{} ({}) | Esc: Show all pods",
+            service.name,
+            service.selector_display()
+        );
+        self.pod_selector = Some(PodSelector {
+            service: service.name.clone(),
+            namespace: service.namespace.clone(),
+            selector: service.selector.clone(),
+        });
+        self.k8s_view = K8sView::Pods;
+        self.k8s_list_state.select(Some(0));
+        self.update_pod_rows();
+    }
+
+    fn clear_pod_selector(&mut self) {
+        if self.pod_selector.take().is_some() {
+            self.message = "Showing all pods".to_string();
+            self.update_pod_rows();
+        }
+    }
+
+    fn next_port_forward(&mut self) {
+        if self.port_forwards.is_empty() {
+            return;
+        }
+
+        let i = match self.port_forward_list_state.selected() {
+            Some(i) if i + 1 < self.port_forwards.len() => i + 1,
+            _ => 0,
+        };
+        self.port_forward_list_state.select(Some(i));
+    }
+
+    fn previous_port_forward(&mut self) {
+        if self.port_forwards.is_empty() {
+            return;
+        }
+
+        let i = match self.port_forward_list_state.selected() {
+            Some(i) if i > 0 => i - 1,
+            _ => self.port_forwards.len() - 1,
+        };
+        self.port_forward_list_state.select(Some(i));
+    }
+
+    fn prompt_port_forward(&mut self, target: ForwardTarget, suggested_port: Option<u16>) {
+        self.input_prompt = Some(InputPrompt {
+            title: format!("Port-forward {} (local:remote)", target.describe()),
+            input: TextInput::new(
+                suggested_port
+                    .map(|p| format!("{}:{}", p, p))
+                    .unwrap_or_default(),
+            ),
+            action: PromptAction::PortForward(target),
+        });
+    }
+
+    fn submit_prompt(&mut self, prompt: InputPrompt) {
+        if let Some(key) = prompt.action.history_key() {
+            self.input_history.record(key, prompt.input.value());
+        }
+        match prompt.action {
+            PromptAction::PortForward(target) => {
+                self.start_port_forward(target, prompt.input.value())
+            }
+            PromptAction::ServicePortForward { namespace } => {
+                // Same shape as `kubectl port-forward svc/NAME LOCAL:REMOTE`.
+                let mut parts = prompt.input.value().split_whitespace();
+                match (parts.next(), parts.next()) {
+                    (Some(name), Some(ports)) => {
+                        let target = ForwardTarget::Service {
+                            namespace,
+                            name: name.trim_start_matches("svc/").to_string(),
+                        };
+                        self.start_port_forward(target, ports);
+                    }
+                    _ => {
+                        self.message = format!(
+                            "Expected 'svc/NAME LOCAL:REMOTE', got '{}'",
+                            prompt.input.value()
+                        )
+                    }
+                }
+            }
+            PromptAction::CreateNetwork => {
+                let mut parts = prompt.input.value().split_whitespace();
+                let Some(name) = parts.next() else {
+                    self.message = "A network name is required".to_string();
+                    return;
+                };
+                let mut args = docker_args(&["network", "create"]);
+                if let Some(driver) = parts.next() {
+                    args.extend(docker_args(&["--driver", driver]));
+                }
+                if let Some(subnet) = parts.next() {
+                    args.extend(docker_args(&["--subnet", subnet]));
+                }
+                args.push(name.to_string());
+                self.run_docker_task(format!("Create network {}", name), vec![args]);
+            }
+            PromptAction::CreateVolume => {
+                let mut parts = prompt.input.value().split_whitespace();
+                let Some(name) = parts.next() else {
+                    self.message = "A volume name is required".to_string();
+                    return;
+                };
+                let mut args = docker_args(&["volume", "create"]);
+                if let Some(driver) = parts.next() {
+                    args.extend(docker_args(&["--driver", driver]));
+                }
+                args.push(name.to_string());
+                self.run_docker_task(format!("Create volume {}", name), vec![args]);
+            }
+            PromptAction::PullImage => {
+                let reference = prompt.input.value().trim();
+                if reference.is_empty() {
+                    self.message = "An image reference is required".to_string();
+                    return;
+                }
+                self.message = format!("Pulling {} in the background...", reference);
+                self.image_pulls.push(ImagePull::spawn(reference));
+            }
+            PromptAction::ConfirmDocker {
+                expected,
+                label,
+                steps,
+            } => {
+                if prompt.input.value().trim() == expected {
+                    self.run_docker_task(label, steps);
+                } else {
+                    self.message = format!("{}: cancelled, confirmation did not match", label);
+                }
+            }
+            PromptAction::Container { command, id, name } => {
+                let value = prompt.input.value().trim();
+                let (label, args) = match command {
+                    ContainerCommand::Rename => {
+                        if !is_valid_container_name(value) {
+                            self.message = format!(
+                                "Invalid name '{}': use letters, digits, '_', '.' and '-'",
+                                value
+                            );
+                            return;
+                        }
+                        (
+                            format!("Rename {} to {}", name, value),
+                            docker_args(&["rename", &id, value]),
+                        )
+                    }
+                    ContainerCommand::Kill => {
+                        let Some(signal) = parse_signal(value) else {
+                            self.message = format!("Invalid signal '{}'", value);
+                            return;
+                        };
+                        (
+                            format!("Kill {} with {}", name, value),
+                            docker_args(&["kill", "--signal", &signal, &id]),
+                        )
+                    }
+                    ContainerCommand::Commit => {
+                        if value.is_empty() || value.chars().any(|c| c.is_whitespace()) {
+                            self.message =
+                                "An image reference like repo:tag is required".to_string();
+                            return;
+                        }
+                        (
+                            format!("Commit {} to {}", name, value),
+                            docker_args(&["commit", &id, value]),
+                        )
+                    }
+                    ContainerCommand::Export => {
+                        if value.is_empty() {
+                            self.message = "An output path is required".to_string();
+                            return;
+                        }
+                        let output = host_path(value);
+                        if let Some(parent) = output
+                            .parent()
+                            .filter(|parent| !parent.as_os_str().is_empty() && !parent.is_dir())
+                        {
+                            self.message = format!("{} is not a directory", parent.display());
+                            return;
+                        }
+                        if output.is_dir() {
+                            self.message =
+                                format!("{} is a directory, add a file name", output.display());
+                            return;
+                        }
+                        let output = output.to_string_lossy();
+                        (
+                            format!("Export {} to {}", name, output),
+                            docker_args(&["export", "--output", &output, &id]),
+                        )
+                    }
+                };
+                self.run_docker_task(label, vec![args]);
+            }
+            PromptAction::DownloadFile {
+                container,
+                path,
+                size,
+            } => {
+                let destination = host_path(prompt.input.value().trim());
+                if !destination.is_dir() {
+                    self.message = format!("{} is not a directory", destination.display());
+                    return;
+                }
+                self.message = format!("Downloading {} to {}", path, destination.display());
+                self.file_transfers
+                    .push(Transfer::download(&container, &path, &destination, size));
+            }
+            PromptAction::UploadFile {
+                container,
+                directory,
+            } => {
+                let local = host_path(prompt.input.value().trim());
+                if prompt.input.value().trim().is_empty() || !local.exists() {
+                    self.message = format!("{} does not exist", local.display());
+                    return;
+                }
+                self.message = format!("Uploading {} to {}", local.display(), directory);
+                self.file_transfers
+                    .push(Transfer::upload(&container, &local, &directory));
+            }
+            PromptAction::AddDockerHost => {
+                let endpoint = match Endpoint::parse(prompt.input.value()) {
+                    Ok(endpoint) => endpoint,
+                    Err(e) => {
+                        self.message = e;
+                        return;
+                    }
+                };
+                if !self.docker_endpoints.contains(&endpoint) {
+                    self.docker_endpoints.push(endpoint.clone());
+                }
+                self.open_host_picker();
+                let selected = self.docker_endpoints.iter().position(|e| *e == endpoint);
+                if let Some(picker) = self.host_picker.as_mut() {
+                    picker.list_state.select(selected);
+                }
+            }
+            PromptAction::SignalProcess { container, pid } => {
+                let value = prompt.input.value().trim();
+                let Some(signal) = parse_signal(value) else {
+                    self.message = format!("Invalid signal '{}'", value);
+                    return;
+                };
+                let label = format!("Send SIG{} to PID {}", signal, pid);
+                self.message = format!("{}: running...", label);
+                self.docker_tasks
+                    .push(DockerTask::spawn_with(label, move || {
+                        signal_process(&container, pid, &signal)
+                    }));
+            }
+        }
+    }
+
+    fn open_process_view(&mut self) {
+        if let Some(container) = self.selected_container() {
+            self.process_view = Some(ProcessView::open(&container.id, &container.name));
+        }
+    }
+
+    fn prompt_signal_process(&mut self) {
+        let Some(view) = &self.process_view else {
+            return;
+        };
+        let Some(process) = view.selected() else {
+            return;
+        };
+        self.input_prompt = Some(InputPrompt {
+            title: format!(
+                "Send signal to PID {} ({}) e.g. TERM, KILL, HUP, 9",
+                process.pid, process.command
+            ),
+            input: TextInput::new("TERM"),
+            action: PromptAction::SignalProcess {
+                container: view.container_id.clone(),
+                pid: process.pid,
+            },
+        });
+    }
+
+    fn open_file_browser(&mut self) {
+        if let Some(container) = self.selected_container() {
+            self.file_browser = Some(FileBrowser::open(&container.id, &container.name));
+        }
+    }
+
+    fn prompt_download(&mut self) {
+        let Some(browser) = &self.file_browser else {
+            return;
+        };
+        let (Some(entry), Some(path)) = (browser.selected(), browser.selected_path()) else {
+            return;
+        };
+        let size = (entry.kind == '-').then_some(entry.size);
+        self.input_prompt = Some(InputPrompt {
+            title: format!("Download {} into host directory", path),
+            input: TextInput::new("."),
+            action: PromptAction::DownloadFile {
+                container: browser.container_id.clone(),
+                path,
+                size,
+            },
+        });
+    }
+
+    fn prompt_upload(&mut self) {
+        let Some(browser) = &self.file_browser else {
+            return;
+        };
+        self.input_prompt = Some(InputPrompt {
+            title: format!("Upload host file or directory into {}", browser.path),
+            input: TextInput::default(),
+            action: PromptAction::UploadFile {
+                container: browser.container_id.clone(),
+                directory: browser.path.clone(),
+            },
+        });
+    }
+
+    /// Runs `args` followed by the selected container's ID, e.g. `stop <id>`.
+    fn run_on_selected_container(&mut self, verb: &str, args: &[&str]) {
+        let Some(container) = self.selected_container() else {
+            return;
+        };
+        let label = format!("{} {}", verb, container.name);
+        let mut step = docker_args(args);
+        step.push(container.id.clone());
+        self.run_docker_task(label, vec![step]);
+    }
+
+    fn toggle_selected_pause(&mut self) {
+        let Some(container) = self.selected_container() else {
+            return;
+        };
+        let (label, command) = if container.state.paused {
+            (format!("Unpause {}", container.name), "unpause")
+        } else {
+            (format!("Pause {}", container.name), "pause")
+        };
+        let args = docker_args(&[command, &container.id]);
+        self.run_docker_task(label, vec![args]);
+    }
+
+    /// Asks for the argument of `command`, pre-filled with a sensible default.
+    fn prompt_container_command(&mut self, command: ContainerCommand) {
+        let Some(container) = self.selected_container() else {
+            return;
+        };
+        let name = &container.name;
+        let (title, value) = match command {
+            ContainerCommand::Rename => (format!("Rename {} to", name), name.clone()),
+            ContainerCommand::Kill => (
+                format!(
+                    "Kill {} with signal (e.g. SIGKILL, SIGTERM, SIGHUP, 9)",
+                    name
+                ),
+                "SIGKILL".to_string(),
+            ),
+            ContainerCommand::Commit => (
+                format!("Commit {} to image (REPOSITORY[:TAG])", name),
+                // Image repositories must be lowercase.
+                format!("{}:snapshot", name.to_lowercase()),
+            ),
+            ContainerCommand::Export => (
+                format!("Export the filesystem of {} to tarball", name),
+                format!("./{}.tar", name),
+            ),
+        };
+        self.input_prompt = Some(InputPrompt {
+            title,
+            input: TextInput::new(value),
+            action: PromptAction::Container {
+                command,
+                id: container.id.clone(),
+                name: container.name.clone(),
+            },
+        });
+    }
+
+    fn start_port_forward(&mut self, target: ForwardTarget, ports: &str) {
+        let Some(client) = self.k8s_client.clone() else {
+            self.message = "Kubernetes is not connected".to_string();
+            return;
+        };
+        let Some((local_port, remote_port)) = parse_port_pair(ports) else {
+            self.message = format!("Invalid port mapping: '{}'", ports);
+            return;
+        };
+
+        self.message = format!("Starting port-forward on 127.0.0.1:{}...", local_port);
+        self.forward_starts.push(KubeTask::spawn(move || {
+            PortForward::start(client, target, local_port, remote_port)
+        }));
+    }
+
+    /// Lists the port-forwards that have started.
+    fn finish_port_forwards(&mut self) {
+        let mut started = Vec::new();
+        self.forward_starts.retain_mut(|task| match task.poll() {
+            Some(result) => {
+                started.push(result);
+                false
+            }
+            None => true,
+        });
+        for result in started {
+            self.message = match result {
+                Ok(forward) => {
+                    let message = format!(
+                        "Forwarding 127.0.0.1:{} -> {}:{}",
+                        forward.local_port, forward.pod, forward.remote_port
+                    );
+                    self.port_forwards.push(forward);
+                    self.port_forward_list_state
+                        .select(Some(self.port_forwards.len() - 1));
+                    self.show_port_forwards = true;
+                    message
+                }
+                Err(e) => format!("Port-forward failed: {}", e),
+            };
+        }
+    }
+
+    fn stop_selected_port_forward(&mut self) {
+        let Some(selected) = self.port_forward_list_state.selected() else {
+            return;
+        };
+        if selected < self.port_forwards.len() {
+            let forward = self.port_forwards.remove(selected);
+            forward.stop();
+            self.message = format!("Stopped port-forward on 127.0.0.1:{}", forward.local_port);
+            if self.port_forwards.is_empty() {
+                self.port_forward_list_state.select(None);
+            } else if selected >= self.port_forwards.len() {
+                self.port_forward_list_state
+                    .select(Some(self.port_forwards.len() - 1));
+            }
+        }
+    }
+
+    /// Opens the YAML of the selected pod, or of the workload that owns it.
+    fn open_pod_yaml(&mut self, owner: bool) {
+        let Some(client) = self.k8s_client.clone() else {
+            self.message = "Kubernetes is not connected".to_string();
+            return;
+        };
+        let Some(pod) = self
+            .k8s_list_state
+            .selected()
+            .and_then(|i| self.k8s_pods.get(i))
+        else {
+            return;
+        };
+
+        if !owner {
+            let path = api_path("v1", "pods", Some(&pod.namespace), &pod.name);
+            return self.open_yaml(path);
+        }
+        let Some(reference) = pod.owner.clone() else {
+            self.message = format!("Pod {} has no owning controller", pod.name);
+            return;
+        };
+        let namespace = pod.namespace.clone();
+        self.message = format!("Resolving owner of {}...", pod.name);
+        self.yaml_task = Some((
+            YamlStep::Load,
+            KubeTask::spawn(move || {
+                let (_, path) = owning_workload(&client, &namespace, &reference)
+                    .map_err(|e| format!("failed to resolve owner: {}", e))?;
+                Ok((path.clone(), get_manifest(&client, &path)?))
+            }),
+        ));
+    }
+
+    /// Opens the YAML of the object selected in any view other than Pods.
+    fn open_resource_yaml(&mut self) {
+        let selected = match self.k8s_view {
+            K8sView::Services => self
+                .service_list_state
+                .selected()
+                .and_then(|i| self.k8s_services.get(i))
+                .map(|s| ("v1", "services", &s.namespace, &s.name)),
+            K8sView::Ingresses => self
+                .ingress_list_state
+                .selected()
+                .and_then(|i| self.k8s_ingresses.get(i))
+                .map(|i| ("networking.k8s.io/v1", "ingresses", &i.namespace, &i.name)),
+            K8sView::ConfigMaps => self
+                .selected_data_object()
+                .map(|c| ("v1", "configmaps", &c.namespace, &c.name)),
+            K8sView::Secrets => self
+                .selected_data_object()
+                .map(|s| ("v1", "secrets", &s.namespace, &s.name)),
+            K8sView::Jobs => self
+                .job_list_state
+                .selected()
+                .and_then(|i| self.k8s_jobs.get(i))
+                .map(|j| ("batch/v1", "jobs", &j.namespace, &j.name)),
+            K8sView::CronJobs => self
+                .selected_cron_job()
+                .map(|c| ("batch/v1", "cronjobs", &c.namespace, &c.name)),
+            K8sView::Resources => return self.open_generic_yaml(),
+            K8sView::Pods => None,
+        };
+        let Some((api_version, plural, namespace, name)) = selected else {
+            return;
+        };
+
+        let path = api_path(api_version, plural, Some(namespace), name);
+        self.open_yaml(path);
+    }
+
+    fn open_yaml(&mut self, path: String) {
+        let Some(client) = self.k8s_client.clone() else {
+            self.message = "Kubernetes is not connected".to_string();
+            return;
+        };
+
+        self.message = "Loading YAML...".to_string();
+        self.yaml_task = Some((
+            YamlStep::Load,
+            KubeTask::spawn(move || Ok((path.clone(), get_manifest(&client, &path)?))),
+        ));
+    }
+
+    /// Sends an edited manifest as a server-side dry run and shows the resulting diff.
+    fn preview_yaml_edit(&mut self, edited_yaml: &str) {
+        let (Some(client), Some(view)) = (self.k8s_client.clone(), self.yaml_view.as_mut()) else {
+            return;
+        };
+        let edited: serde_json::Value = match serde_yaml::from_str(edited_yaml) {
+            Ok(value) => value,
+            Err(e) => {
+                self.message = format!("Invalid YAML: {}", e);
+                return;
+            }
+        };
+
+        let path = view.object_path.clone();
+        let body = edited.clone();
+        self.message = "Running dry run...".to_string();
+        self.yaml_task = Some((
+            YamlStep::DryRun(edited),
+            KubeTask::spawn(move || {
+                let result = client.put_json(&format!("{}?dryRun=All", path), &body)?;
+                Ok((path, result))
+            }),
+        ));
+    }
+
+    fn apply_yaml_edit(&mut self) {
+        let (Some(client), Some(view)) = (self.k8s_client.clone(), self.yaml_view.as_ref()) else {
+            return;
+        };
+        // The diff stays up until the server answers; `pending_apply` is only cleared then.
+        if matches!(self.yaml_task, Some((YamlStep::Apply, _))) {
+            return;
+        }
+        let Some(edited) = view.pending_apply.clone() else {
+            return;
+        };
+
+        let path = view.object_path.clone();
+        self.message = format!("Applying changes to {}...", view.title);
+        self.yaml_task = Some((
+            YamlStep::Apply,
+            KubeTask::spawn(move || Ok((path.clone(), client.put_json(&path, &edited)?))),
+        ));
+    }
+
+    /// Handles finished actions, answers to YAML loads, dry runs and applies, and
+    /// printer column lookups.
+    fn poll_k8s_tasks(&mut self) {
+        self.finish_k8s_connect();
+        self.finish_port_forwards();
+
+        let mut finished = Vec::new();
+        self.k8s_actions.retain_mut(|task| match task.poll() {
+            Some(message) => {
+                finished.push(message);
+                false
+            }
+            None => true,
+        });
+        if !finished.is_empty() {
+            self.message = finished.join(" | ");
+        }
+
+        if let Some((name, columns)) = self.columns_task.as_mut().and_then(|task| task.poll()) {
+            self.columns_task = None;
+            if let Some((resource, current)) = self.browsed_resource.as_mut() {
+                if resource.full_name() == name {
+                    *current = columns;
+                }
+            }
+        }
+
+        let Some(result) = self.yaml_task.as_mut().and_then(|(_, task)| task.poll()) else {
+            return;
+        };
+        let Some((step, _)) = self.yaml_task.take() else {
+            return;
+        };
+        match step {
+            YamlStep::Load => match result {
+                Ok((path, manifest)) => {
+                    let title = manifest_title(&manifest);
+                    self.message.clear();
+                    self.yaml_view = Some(yaml_view_for(title, path, &manifest));
+                }
+                Err(e) => self.message = format!("Failed to load YAML: {}", e),
+            },
+            YamlStep::DryRun(edited) => {
+                // The view may have been closed or replaced while the server was busy.
+                let Some(view) = self.yaml_view.as_mut() else {
+                    return;
+                };
+                match result {
+                    Ok((path, mut result)) if path == view.object_path => {
+                        strip_managed_fields(&mut result);
+                        view.show_diff(&manifest_to_yaml(&result), edited);
+                        self.message = if view.has_changes() {
+                            "Dry run succeeded, review the diff and press Y to apply".to_string()
+                        } else {
+                            "Dry run produced no changes".to_string()
+                        };
+                    }
+                    Ok(_) => {}
+                    Err(e) => self.message = format!("Dry run rejected: {}", e),
+                }
+            }
+            YamlStep::Apply => {
+                let view = self.yaml_view.as_mut();
+                match (result, view) {
+                    (Ok((path, mut result)), Some(view)) if path == view.object_path => {
+                        strip_managed_fields(&mut result);
+                        *view = yaml_view_for(view.title.clone(), path, &result);
+                        self.message = format!("Applied changes to {}", view.title);
+                    }
+                    (Ok((path, _)), _) => self.message = format!("Applied changes to {}", path),
+                    (Err(e), view) => {
+                        if let Some(view) = view {
+                            view.discard_diff();
+                        }
+                        self.message = format!("Apply failed: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    fn refresh_k8s_metrics(&mut self) {
+        let Some(result) = self.metrics_poller.as_mut().and_then(|p| p.poll()) else {
+            return;
+        };
+
+        match result {
+            Ok(metrics) => {
+                for ((namespace, name), usage) in &metrics {
+                    let history = self
+                        .pod_stats_history
+                        .entry(format!("{}/{}", namespace, name))
+                        .or_default();
+                    history.push(PodStats {
+                        cpu_millis: usage.cpu_millis,
+                        memory_bytes: usage.memory_bytes,
+                    });
+                    if history.len() > 60 {
+                        history.remove(0);
+                    }
+                }
+                self.pod_stats_history.retain(|key, _| {
+                    key.split_once('/')
+                        .map(|(ns, name)| metrics.contains_key(&(ns.to_string(), name.to_string())))
+                        .unwrap_or(false)
+                });
+                self.pod_metrics = metrics;
+                self.pod_metrics_error = None;
+            }
+            Err(e) => {
+                self.pod_metrics.clear();
+                self.pod_metrics_error = Some(e);
+            }
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let mut sys = System::new_all();
+    let mut networks = Networks::new_with_refreshed_list();
+
+    let mut cpu_data = vec![0; 60];
+    let mut ram_data = vec![0; 60];
+    let mut network_receive_speed_data = vec![0; 60];
+    let mut network_send_speed_data = vec![0; 60];
+
+    let mut prev_total_received = None;
+    let mut prev_total_transmitted = None;
+    let mut last_update = Instant::now();
+
+    let mut app_state = AppState::new();
+
+    loop {
+        sys.refresh_all();
+        networks.refresh();
+
+        let cpu_usage = sys.global_cpu_usage();
+        let memory = sys.total_memory();
+        let used_memory = sys.used_memory();
+        let memory_usage_percentage = (used_memory as f64 / memory as f64) * 100.0;
+
+        let mut total_received = 0;
+        let mut total_transmitted = 0;
+        for (_, network) in &networks {
+            total_received += network.received();
+            total_transmitted += network.transmitted();
+        }
+
+        let elapsed_secs = last_update.elapsed().as_secs_f64().max(1e-6);
+        let mut receive_rate_kbs = 0.0;
+        let mut send_rate_kbs = 0.0;
+
+        if let (Some(prev_recv), Some(prev_send)) = (prev_total_received, prev_total_transmitted) {
+            let diff_recv = total_received.saturating_sub(prev_recv);
+            let diff_send = total_transmitted.saturating_sub(prev_send);
+            receive_rate_kbs = diff_recv as f64 / 1024.0 / elapsed_secs;
+            send_rate_kbs = diff_send as f64 / 1024.0 / elapsed_secs;
+        }
+
+        prev_total_received = Some(total_received);
+        prev_total_transmitted = Some(total_transmitted);
+        last_update = Instant::now();
+
+        cpu_data.remove(0);
+        cpu_data.push(cpu_usage.round() as u64);
+        ram_data.remove(0);
+        ram_data.push(memory_usage_percentage as u64);
+        network_receive_speed_data.remove(0);
+        network_receive_speed_data.push(receive_rate_kbs.round() as u64);
+        network_send_speed_data.remove(0);
+        network_send_speed_data.push(send_rate_kbs.round() as u64);
+
+        // Held until the end of the iteration, so key actions use it too.
+        let mut _host_guard = None;
+        match app_state.current_tab {
+            MonitorTab::Docker => {
+                let stats_sampled = app_state.refresh_docker_containers();
+                _host_guard = app_state.selected_host_guard();
+                if app_state.docker_view == DockerView::Containers {
+                    let rows = app_state.docker_rows().len();
+                    clamp_selection(&mut app_state.docker_list_state, rows);
+                    app_state.refresh_container_health();
+                }
+
+                // Only record history for fresh samples, not for reused stats.
+                if stats_sampled {
+                    let mut updates = Vec::new();
+                    for container in &app_state.docker_containers {
+                        let stats = ContainerStats {
+                            cpu_percent: container.cpu_percent,
+                            mem_percent: container.mem_percent,
+                            net_rx_kb: parse_net_io(&container.net_io).0,
+                            net_tx_kb: parse_net_io(&container.net_io).1,
+                        };
+                        updates.push((container.id.clone(), stats));
+                    }
+
+                    for (id, stats) in updates {
+                        app_state.update_container_stats(&id, stats);
+                    }
+                }
+
+                app_state.docker_images = get_docker_images();
+                // Inspecting images, networks and volumes is slower, so only do it when visible.
+                match app_state.docker_view {
+                    DockerView::Images => {
+                        app_state.refresh_image_users();
+                        app_state.refresh_image_details();
+                    }
+                    DockerView::Networks => {
+                        app_state.docker_networks = list_networks();
+                        let len = app_state.docker_networks.len();
+                        clamp_selection(&mut app_state.docker_list_state, len);
+                    }
+                    DockerView::Volumes => app_state.refresh_docker_volumes(),
+                    DockerView::Disk => app_state.refresh_disk_usage(),
+                    _ => {}
+                }
+            }
+
+            MonitorTab::Kubernetes => {
+                app_state.refresh_k8s_pods();
+                app_state.refresh_k8s_network();
+                app_state.refresh_k8s_config_data();
+                app_state.refresh_k8s_jobs();
+                app_state.refresh_k8s_resources();
+                app_state.refresh_k8s_metrics();
+            }
+
+            _ => {}
+        }
+
+        app_state.poll_docker_tasks();
+        app_state.poll_k8s_tasks();
+        if let Some(view) = app_state.log_view.as_mut() {
+            view.poll();
+        }
+        if let Some(view) = app_state.process_view.as_mut() {
+            view.refresh_if_stale();
+        }
+
+        terminal.draw(|f| {
+            let size = f.area();
+
+            if app_state.show_create_dialog {
+                render_create_dialog(f, size, &app_state.create_dialog_state);
+                return;
+            }
+            if let Some(dialog) = &app_state.update_dialog {
+                render_update_dialog(f, size, dialog);
+                return;
+            }
+
+            let tabs_block = Block::default().borders(Borders::ALL).title("Tabs");
+            let tabs = Tabs::new(vec![
+                "System (Ctrl+S)",
+                "Docker (Ctrl+D)",
+                "Kubernetes (Ctrl+K)",
+            ])
+            .block(tabs_block)
+            .select(match app_state.current_tab {
+                MonitorTab::System => 0,
+                MonitorTab::Docker => 1,
+                MonitorTab::Kubernetes => 2,
+            })
+            .style(Style::default().fg(Color::White))
+            .highlight_style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            );
+
+            let chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
+                .split(size);
+
+            f.render_widget(tabs, chunks[0]);
+
+            if let Some(view) = app_state.yaml_view.as_mut() {
+                let yaml_chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
+                    .split(chunks[1]);
+                view.render(f, yaml_chunks[0]);
+                let message = Paragraph::new(app_state.message.as_str())
+                    .block(Block::default().borders(Borders::ALL).title("Message"));
+                f.render_widget(message, yaml_chunks[1]);
+                return;
+            }
+
+            if let Some(view) = app_state.log_view.as_mut() {
+                view.render(f, chunks[1]);
+                return;
+            }
+
+            if let Some(view) = app_state.process_view.as_mut() {
+                let process_chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Min(0), Constraint::Length(3)].as_ref())
+                    .split(chunks[1]);
+                view.render(f, process_chunks[0]);
+                let message = Paragraph::new(app_state.message.as_str())
+                    .block(Block::default().borders(Borders::ALL).title("Message"));
+                f.render_widget(message, process_chunks[1]);
+                if let Some(prompt) = &app_state.input_prompt {
+                    render_input_prompt(f, size, prompt, &app_state.prompt_completions());
+                }
+                return;
+            }
+
+            if let Some(browser) = app_state.file_browser.as_mut() {
+                let transfers_height = if app_state.file_transfers.is_empty() {
+                    0
+                } else {
+                    app_state.file_transfers.len().min(8) as u16 + 2
+                };
+                let browser_chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(
+                        [
+                            Constraint::Min(0),
+                            Constraint::Length(transfers_height),
+                            Constraint::Length(3),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(chunks[1]);
+                browser.render(f, browser_chunks[0]);
+                if transfers_height > 0 {
+                    render_file_transfers(f, browser_chunks[1], &app_state.file_transfers);
+                }
+                let message = Paragraph::new(app_state.message.as_str())
+                    .block(Block::default().borders(Borders::ALL).title("Message"));
+                f.render_widget(message, browser_chunks[2]);
+                if let Some(prompt) = &app_state.input_prompt {
+                    render_input_prompt(f, size, prompt, &app_state.prompt_completions());
+                }
+                return;
+            }
+
+            match app_state.current_tab {
+                MonitorTab::System => render_system_tab(
+                    f,
+                    chunks[1],
+                    &sys,
+                    &cpu_data,
+                    &ram_data,
+                    &network_receive_speed_data,
+                    &network_send_speed_data,
+                    cpu_usage,
+                    memory,
+                    used_memory,
+                    memory_usage_percentage,
+                    receive_rate_kbs,
+                    send_rate_kbs,
+                    total_received,
+                    total_transmitted,
+                ),
+                MonitorTab::Docker => render_docker_tab(f, chunks[1], &mut app_state),
+                MonitorTab::Kubernetes => render_k8s_tab(f, chunks[1], &mut app_state),
+            }
+
+            if app_state.resource_picker.is_some() {
+                render_resource_picker(f, size, &mut app_state);
+            }
+            if app_state.host_picker.is_some() {
+                render_host_picker(f, size, &mut app_state);
+            }
+            if app_state.prune_preview.is_some() {
+                render_prune_preview(f, size, &mut app_state);
+            }
+            if let Some(prompt) = &app_state.input_prompt {
+                render_input_prompt(f, size, prompt, &app_state.prompt_completions());
+            }
+        })?;
+
+        if event::poll(Duration::from_millis(200))? {
+            let event = event::read()?;
+            if let Event::Paste(text) = &event {
+                app_state.paste(text);
+            } else if let Event::Key(key) = event {
+                if app_state.show_create_dialog {
+                    let dialog = &mut app_state.create_dialog_state;
+                    match key.code {
+                        KeyCode::Esc => {
+                            app_state.show_create_dialog = false;
+                            app_state.create_dialog_state = CreateDialogState::new();
+                        }
+                        KeyCode::Tab | KeyCode::Down => {
+                            // Tab completes the image first, then moves on.
+                            let completed = key.code == KeyCode::Tab
+                                && dialog.selected_field == 0
+                                && dialog.inputs[0].complete(&dialog.images);
+                            if !completed {
+                                dialog.selected_field =
+                                    (dialog.selected_field + 1) % CreateDialogState::FIELD_COUNT;
+                            }
+                        }
+                        KeyCode::BackTab | KeyCode::Up => {
+                            dialog.selected_field =
+                                (dialog.selected_field + CreateDialogState::FIELD_COUNT - 1)
+                                    % CreateDialogState::FIELD_COUNT;
+                        }
+                        KeyCode::Left if dialog.current_input().is_none() => {
+                            dialog.cycle_choice(-1)
+                        }
+                        KeyCode::Right if dialog.current_input().is_none() => {
+                            dialog.cycle_choice(1)
+                        }
+                        KeyCode::Char(c @ ('p' | 'n'))
+                            if key.modifiers.contains(KeyModifiers::CONTROL) =>
+                        {
+                            if let Some(history_key) = dialog.history_key() {
+                                let history = app_state.input_history.entries(history_key);
+                                if let Some(input) = dialog.current_input() {
+                                    if c == 'p' {
+                                        input.history_previous(history);
+                                    } else {
+                                        input.history_next(history);
+                                    }
+                                }
+                            }
+                        }
+                        KeyCode::Enter if dialog.is_multi_line() => {
+                            if let Some(input) = dialog.current_input() {
+                                input.insert('\n');
+                            }
+                        }
+                        KeyCode::Enter => app_state.submit_create_dialog(),
+                        _ => {
+                            if let Some(input) = dialog.current_input() {
+                                input.handle_key(&key);
+                            }
+                        }
+                    }
+                } else if let Some(dialog) = app_state.update_dialog.as_mut() {
+                    match key.code {
+                        KeyCode::Esc => app_state.update_dialog = None,
+                        KeyCode::Tab | KeyCode::Down => {
+                            dialog.selected_field =
+                                (dialog.selected_field + 1) % UpdateDialogState::FIELD_COUNT;
+                        }
+                        KeyCode::BackTab | KeyCode::Up => {
+                            dialog.selected_field =
+                                (dialog.selected_field + UpdateDialogState::FIELD_COUNT - 1)
+                                    % UpdateDialogState::FIELD_COUNT;
+                        }
+                        KeyCode::Enter => app_state.submit_update_dialog(),
+                        _ => {
+                            dialog.inputs[dialog.selected_field].handle_key(&key);
+                        }
+                    }
+                } else if let Some(prompt) = app_state.input_prompt.as_mut() {
+                    let history = prompt
+                        .action
+                        .history_key()
+                        .map_or(&[][..], |key| app_state.input_history.entries(key));
+                    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+                    match key.code {
+                        KeyCode::Esc => app_state.input_prompt = None,
+                        KeyCode::Enter => {
+                            if let Some(prompt) = app_state.input_prompt.take() {
+                                app_state.submit_prompt(prompt);
+                            }
+                        }
+                        KeyCode::Tab => {
+                            let completions = app_state.prompt_completions();
+                            if let Some(prompt) = app_state.input_prompt.as_mut() {
+                                prompt.input.complete(&completions);
+                            }
+                        }
+                        KeyCode::Up => prompt.input.history_previous(history),
+                        KeyCode::Down => prompt.input.history_next(history),
+                        KeyCode::Char('p') if ctrl => prompt.input.history_previous(history),
+                        KeyCode::Char('n') if ctrl => prompt.input.history_next(history),
+                        _ => {
+                            prompt.input.handle_key(&key);
+                        }
+                    }
+                } else if let Some(view) = app_state.yaml_view.as_mut() {
+                    if let Some(input) = view.search_input.as_mut() {
+                        match key.code {
+                            KeyCode::Esc => view.search_input = None,
+                            KeyCode::Char(c) => input.push(c),
+                            KeyCode::Backspace => {
+                                input.pop();
+                            }
+                            KeyCode::Enter => view.commit_search(),
+                            _ => {}
+                        }
+                    } else if view.pending_apply.is_some() {
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') => app_state.apply_yaml_edit(),
+                            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                                view.discard_diff();
+                                app_state.message = "Discarded edit".to_string();
+                            }
+                            KeyCode::Down => view.move_cursor(1),
+                            KeyCode::Up => view.move_cursor(-1),
+                            KeyCode::PageDown => view.move_cursor(20),
+                            KeyCode::PageUp => view.move_cursor(-20),
+                            _ => {}
+                        }
+                    } else {
+                        match key.code {
+                            KeyCode::Esc | KeyCode::Char('q') => app_state.yaml_view = None,
+                            KeyCode::Down | KeyCode::Char('j') => view.move_cursor(1),
+                            KeyCode::Up | KeyCode::Char('k') => view.move_cursor(-1),
+                            KeyCode::PageDown => view.move_cursor(20),
+                            KeyCode::PageUp => view.move_cursor(-20),
+                            KeyCode::Home | KeyCode::Char('g') => view.move_to_start(),
+                            KeyCode::End | KeyCode::Char('G') => view.move_to_end(),
+                            KeyCode::Char(' ') | KeyCode::Enter => view.toggle_fold(),
+                            KeyCode::Char('z') => view.unfold_all(),
+                            KeyCode::Char('/') => view.search_input = Some(String::new()),
+                            KeyCode::Char('n') => view.find_match(true, false),
+                            KeyCode::Char('N') => view.find_match(false, false),
+                            KeyCode::Char('r') | KeyCode::Char('R') => view.reveal(),
+                            KeyCode::Char('e') if view.is_masked() => {
+                                app_state.message =
+                                    "Reveal the values with R before editing".to_string()
+                            }
+                            KeyCode::Char('e') => {
+                                let original = view.yaml.clone();
+                                match edit_in_editor(&mut terminal, &original) {
+                                    Ok(edited) if edited == original => {
+                                        app_state.message = "Edit cancelled, no changes".to_string()
+                                    }
+                                    Ok(edited) => app_state.preview_yaml_edit(&edited),
+                                    Err(e) => app_state.message = e,
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                } else if app_state.prune_preview.is_some() {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('n') | KeyCode::Char('q') => {
+                            app_state.prune_preview = None
+                        }
+                        KeyCode::Char('y') | KeyCode::Enter => app_state.confirm_prune(),
+                        KeyCode::Down => app_state.move_prune_preview(1),
+                        KeyCode::Up => app_state.move_prune_preview(-1),
+                        KeyCode::PageDown => app_state.move_prune_preview(10),
+                        KeyCode::PageUp => app_state.move_prune_preview(-10),
+                        _ => {}
+                    }
+                } else if let Some(picker) = app_state.resource_picker.as_mut() {
+                    match key.code {
+                        KeyCode::Esc => app_state.resource_picker = None,
+                        KeyCode::Char(c) => {
+                            picker.filter.push(c);
+                            picker.list_state.select(Some(0));
+                        }
+                        KeyCode::Backspace => {
+                            picker.filter.pop();
+                            picker.list_state.select(Some(0));
+                        }
+                        KeyCode::Down => app_state.move_resource_picker(1),
+                        KeyCode::Up => app_state.move_resource_picker(-1),
+                        KeyCode::PageDown => app_state.move_resource_picker(10),
+                        KeyCode::PageUp => app_state.move_resource_picker(-10),
+                        KeyCode::Enter => app_state.browse_picked_resource(),
+                        _ => {}
+                    }
+                } else if app_state.host_picker.is_some() {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => app_state.host_picker = None,
+                        KeyCode::Down => app_state.move_host_picker(1),
+                        KeyCode::Up => app_state.move_host_picker(-1),
+                        KeyCode::Enter => app_state.use_picked_host(),
+                        KeyCode::Char('n') => app_state.prompt_add_docker_host(),
+                        KeyCode::Char('a') => {
+                            app_state.host_picker = None;
+                            app_state.toggle_all_docker_hosts();
+                        }
+                        _ => {}
+                    }
+                } else if let Some(view) = app_state.process_view.as_mut() {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => app_state.process_view = None,
+                        KeyCode::Down => view.move_selection(1),
+                        KeyCode::Up => view.move_selection(-1),
+                        KeyCode::PageDown => view.move_selection(20),
+                        KeyCode::PageUp => view.move_selection(-20),
+                        KeyCode::Char('k' | 'K') => app_state.prompt_signal_process(),
+                        _ => {}
+                    }
+                } else if let Some(browser) = app_state.file_browser.as_mut() {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => app_state.file_browser = None,
+                        KeyCode::Down => browser.move_selection(1),
+                        KeyCode::Up => browser.move_selection(-1),
+                        KeyCode::PageDown => browser.move_selection(20),
+                        KeyCode::PageUp => browser.move_selection(-20),
+                        KeyCode::Enter | KeyCode::Right => {
+                            if let Err(e) = browser.enter() {
+                                app_state.message = e;
+                            }
+                        }
+                        KeyCode::Backspace | KeyCode::Left => browser.go_up(),
+                        KeyCode::Char('r') => browser.refresh(),
+                        KeyCode::Char('d') => app_state.prompt_download(),
+                        KeyCode::Char('u') => app_state.prompt_upload(),
+                        KeyCode::Char('c') => app_state
+                            .file_transfers
+                            .retain(|transfer| transfer.result.is_none()),
+                        _ => {}
+                    }
+                } else if let Some(view) = app_state.log_view.as_mut() {
+                    match key.code {
+                        KeyCode::Esc | KeyCode::Char('q') => app_state.log_view = None,
+                        KeyCode::Down => view.scroll_by(1),
+                        KeyCode::Up => view.scroll_by(-1),
+                        KeyCode::PageDown => view.scroll_by(20),
+                        KeyCode::PageUp => view.scroll_by(-20),
+                        KeyCode::Home => view.scroll_to_top(),
+                        KeyCode::End => view.follow(),
+                        _ => {}
+                    }
+                } else {
+                    match key.code {
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app_state.current_tab = MonitorTab::System;
+                        }
+                        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app_state.current_tab = MonitorTab::Docker;
+                        }
+                        KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                            app_state.current_tab = MonitorTab::Kubernetes;
+                            app_state.connect_k8s();
+                        }
+                        KeyCode::Down => match app_state.current_tab {
+                            MonitorTab::Docker => app_state.next_docker_item(),
+                            MonitorTab::Kubernetes
+                                if app_state.show_port_forwards
+                                    && app_state.k8s_view == K8sView::Pods =>
+                            {
+                                app_state.next_port_forward()
+                            }
+                            MonitorTab::Kubernetes if app_state.data_focus => {
+                                app_state.next_data_key()
+                            }
+                            MonitorTab::Kubernetes => app_state.next_k8s_item(),
+                            _ => {}
+                        },
+                        KeyCode::Up => match app_state.current_tab {
+                            MonitorTab::Docker => app_state.previous_docker_item(),
+                            MonitorTab::Kubernetes
+                                if app_state.show_port_forwards
+                                    && app_state.k8s_view == K8sView::Pods =>
+                            {
+                                app_state.previous_port_forward()
+                            }
+                            MonitorTab::Kubernetes if app_state.data_focus => {
+                                app_state.previous_data_key()
+                            }
+                            MonitorTab::Kubernetes => app_state.previous_k8s_item(),
+                            _ => {}
+                        },
+                        KeyCode::Tab if app_state.current_tab == MonitorTab::Docker => {
+                            app_state.docker_view = match app_state.docker_view {
+                                DockerView::Containers => DockerView::Images,
+                                DockerView::Images => DockerView::Networks,
+                                DockerView::Networks => DockerView::Volumes,
+                                DockerView::Volumes => DockerView::Disk,
+                                DockerView::Disk => DockerView::Containers,
+                            };
+                            app_state.docker_list_state.select(Some(0));
+                        }
+                        KeyCode::Tab if app_state.current_tab == MonitorTab::Kubernetes => {
+                            let view = match app_state.k8s_view {
+                                K8sView::Pods => K8sView::Services,
+                                K8sView::Services => K8sView::Ingresses,
+                                K8sView::Ingresses => K8sView::ConfigMaps,
+                                K8sView::ConfigMaps => K8sView::Secrets,
+                                K8sView::Secrets => K8sView::Jobs,
+                                K8sView::Jobs => K8sView::CronJobs,
+                                K8sView::CronJobs => K8sView::Resources,
+                                K8sView::Resources => K8sView::Pods,
+                            };
+                            app_state.switch_k8s_view(view);
+                        }
+                        KeyCode::Enter
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Services =>
+                        {
+                            app_state.show_service_pods();
+                        }
+                        KeyCode::Enter
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.selected_data_object().is_some() =>
+                        {
+                            app_state.data_focus = !app_state.data_focus;
+                        }
+                        KeyCode::Esc
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.data_focus =>
+                        {
+                            app_state.data_focus = false;
+                            app_state.reset_data_key();
+                        }
+                        KeyCode::Char('v')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.selected_data_object().is_some() =>
+                        {
+                            app_state.show_data_value = !app_state.show_data_value;
+                        }
+                        KeyCode::Char('r')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Secrets =>
+                        {
+                            app_state.reveal_secret = !app_state.reveal_secret;
+                            app_state.show_data_value = true;
+                        }
+                        KeyCode::Char('l')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && matches!(app_state.k8s_view, K8sView::Pods | K8sView::Jobs) =>
+                        {
+                            app_state.open_selected_logs();
+                        }
+                        KeyCode::Char('t')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::CronJobs =>
+                        {
+                            app_state.trigger_selected_cron_job();
+                        }
+                        KeyCode::Char('s')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::CronJobs =>
+                        {
+                            app_state.toggle_selected_cron_job_suspend();
+                        }
+                        KeyCode::Char('c')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.selected_data_object().is_some() =>
+                        {
+                            app_state.copy_data_value();
+                        }
+                        KeyCode::Esc
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Pods =>
+                        {
+                            app_state.clear_pod_selector();
+                        }
+                        KeyCode::Char('f')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Services =>
+                        {
+                            if let Some(selected) = app_state.service_list_state.selected() {
+                                if let Some(service) = app_state.k8s_services.get(selected) {
+                                    let target = ForwardTarget::Service {
+                                        namespace: service.namespace.clone(),
+                                        name: service.name.clone(),
+                                    };
+                                    let port = service.service_ports.first().copied();
+                                    app_state.prompt_port_forward(target, port);
+                                }
+                            }
+                        }
+                        KeyCode::Char(':') if app_state.current_tab == MonitorTab::Kubernetes => {
+                            app_state.switch_k8s_view(K8sView::Resources);
+                            app_state.open_resource_picker();
+                        }
+                        KeyCode::Char('y')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view != K8sView::Pods =>
+                        {
+                            app_state.open_resource_yaml();
+                        }
+                        KeyCode::Char('P')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Images =>
+                        {
+                            app_state.prompt_pull_image();
+                        }
+                        KeyCode::Char('c')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Images =>
+                        {
+                            app_state.image_pulls.retain(|pull| !pull.finished);
+                        }
+                        KeyCode::Char('n') | KeyCode::Char('P')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && matches!(
+                                    app_state.docker_view,
+                                    DockerView::Networks | DockerView::Volumes
+                                ) =>
+                        {
+                            if key.code == KeyCode::Char('n') {
+                                app_state.prompt_create();
+                            } else {
+                                app_state.prompt_prune();
+                            }
+                        }
+                        KeyCode::Char('P') | KeyCode::Char('A')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Disk =>
+                        {
+                            app_state.preview_prune(key.code == KeyCode::Char('A'));
+                        }
+                        KeyCode::Char('e') if app_state.current_tab == MonitorTab::Docker => {
+                            app_state.show_docker_events = !app_state.show_docker_events;
+                        }
+                        KeyCode::Char('H') if app_state.current_tab == MonitorTab::Docker => {
+                            app_state.open_host_picker();
+                        }
+                        KeyCode::Char('a')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.toggle_all_docker_hosts();
+                        }
+                        KeyCode::Char('E') if app_state.current_tab == MonitorTab::Docker => {
+                            app_state.cycle_event_kind_filter();
+                        }
+                        KeyCode::Char('f')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.toggle_event_container_filter();
+                        }
+                        KeyCode::Char('L')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.open_update_dialog();
+                        }
+                        KeyCode::Char('z')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.toggle_selected_pause();
+                        }
+                        KeyCode::Char('b')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.open_file_browser();
+                        }
+                        KeyCode::Char('T')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.open_process_view();
+                        }
+                        KeyCode::Char(c @ ('R' | 'K' | 'C' | 'O'))
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.prompt_container_command(match c {
+                                'R' => ContainerCommand::Rename,
+                                'K' => ContainerCommand::Kill,
+                                'C' => ContainerCommand::Commit,
+                                _ => ContainerCommand::Export,
+                            });
+                        }
+                        KeyCode::Char('h')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.show_problem_containers = !app_state.show_problem_containers;
+                            app_state.docker_list_state.select(Some(0));
+                        }
+                        KeyCode::Char('N')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && Runtime::current() == Runtime::Nerdctl =>
+                        {
+                            app_state.switch_containerd_namespace();
+                        }
+                        KeyCode::Char('n') if app_state.current_tab == MonitorTab::Docker => {
+                            app_state.show_create_dialog = true;
+                            app_state.create_dialog_state =
+                                CreateDialogState::open(&list_networks(), &get_docker_images());
+                        }
+                        KeyCode::Char('p') if app_state.current_tab == MonitorTab::Docker => {
+                            let label = "Create PostgreSQL container".to_string();
+                            app_state.run_docker_task(label, vec![postgres_container_args()]);
+                        }
+                        KeyCode::Char('r') if app_state.current_tab == MonitorTab::Docker => {
+                            let label = "Create Redis container".to_string();
+                            app_state.run_docker_task(label, vec![redis_container_args()]);
+                        }
+                        KeyCode::Char('m') if app_state.current_tab == MonitorTab::Docker => {
+                            let label = "Create MongoDB container".to_string();
+                            app_state.run_docker_task(label, vec![mongodb_container_args()]);
+                        }
+                        KeyCode::Char('g') if app_state.current_tab == MonitorTab::Docker => {
+                            let label = "Create Grafana container".to_string();
+                            app_state.run_docker_task(label, vec![grafana_container_args()]);
+                        }
+                        KeyCode::Char('x') if app_state.current_tab == MonitorTab::Docker => {
+                            if let Some(selected) = app_state.docker_list_state.selected() {
+                                match app_state.docker_view {
+                                    DockerView::Containers => {
+                                        app_state.run_on_selected_container("Stop", &["stop"]);
+                                    }
+                                    DockerView::Images => {
+                                        if let Some(image) = app_state.docker_images.get(selected) {
+                                            let label =
+                                                format!("Delete image {}", image.repository);
+                                            let args = docker_args(&["rmi", "-f", &image.image_id]);
+                                            app_state.run_docker_task(label, vec![args]);
+                                        }
+                                    }
+                                    DockerView::Networks => app_state.remove_selected_network(),
+                                    DockerView::Volumes => app_state.remove_selected_volume(),
+                                    DockerView::Disk => {}
+                                }
+                            }
+                        }
+                        KeyCode::Char('s')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.run_on_selected_container("Start", &["start"]);
+                        }
+                        KeyCode::Char('t')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            if app_state.selected_container().is_some() {
+                                app_state.run_on_selected_container("Restart", &["restart"]);
+                            } else {
+                                app_state.run_group_action(&["restart"], Some(&["restart"]));
+                            }
+                        }
+                        KeyCode::Delete
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.run_on_selected_container("Delete", &["rm", "-f"]);
+                        }
+                        KeyCode::Enter | KeyCode::Char(' ')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.toggle_selected_project();
+                        }
+                        KeyCode::Char('u')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.run_group_action(&["up", "-d"], Some(&["start"]));
+                        }
+                        KeyCode::Char('D')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.run_group_action(&["down"], Some(&["rm", "-f"]));
+                        }
+                        KeyCode::Char('P')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.run_group_action(&["pull"], None);
+                        }
+                        KeyCode::Char('l')
+                            if app_state.current_tab == MonitorTab::Docker
+                                && app_state.docker_view == DockerView::Containers =>
+                        {
+                            app_state.open_docker_logs();
+                        }
+                        KeyCode::Char('d')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Pods =>
+                        {
+                            app_state.delete_selected_pod();
+                        }
+                        KeyCode::Char('f')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Pods =>
+                        {
+                            if let Some(selected) = app_state.k8s_list_state.selected() {
+                                if let Some(pod) = app_state.k8s_pods.get(selected) {
+                                    let target = ForwardTarget::Pod {
+                                        namespace: pod.namespace.clone(),
+                                        name: pod.name.clone(),
+                                    };
+                                    let port = pod.ports.first().copied();
+                                    app_state.prompt_port_forward(target, port);
+                                }
+                            }
+                        }
+                        KeyCode::Char('F')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Pods =>
+                        {
+                            let namespace = app_state
+                                .k8s_list_state
+                                .selected()
+                                .and_then(|i| app_state.k8s_pods.get(i))
+                                .map(|pod| pod.namespace.clone())
+                                .unwrap_or_else(|| "default".to_string());
+                            app_state.input_prompt = Some(InputPrompt {
+                                title: format!(
+                                    "Port-forward service in {} (svc/NAME local:remote)",
+                                    namespace
+                                ),
+                                input: TextInput::new("svc/"),
+                                action: PromptAction::ServicePortForward { namespace },
+                            });
+                        }
+                        KeyCode::Char('y')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Pods =>
+                        {
+                            app_state.open_pod_yaml(false);
+                        }
+                        KeyCode::Char('Y')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Pods =>
+                        {
+                            app_state.open_pod_yaml(true);
+                        }
+                        KeyCode::Char('w')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Pods =>
+                        {
+                            app_state.show_port_forwards = !app_state.show_port_forwards;
+                            if app_state.port_forward_list_state.selected().is_none()
+                                && !app_state.port_forwards.is_empty()
+                            {
+                                app_state.port_forward_list_state.select(Some(0));
+                            }
+                        }
+                        KeyCode::Char('x')
+                            if app_state.current_tab == MonitorTab::Kubernetes
+                                && app_state.k8s_view == K8sView::Pods
+                                && app_state.show_port_forwards =>
+                        {
+                            app_state.stop_selected_port_forward();
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    )?;
+    terminal.show_cursor()?;
+
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn render_system_tab(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    sys: &System,
+    cpu_data: &[u64],
+    ram_data: &[u64],
+    network_receive_speed_data: &[u64],
+    network_send_speed_data: &[u64],
+    cpu_usage: f32,
     memory: u64,
     used_memory: u64,
     memory_usage_percentage: f64,
@@ -574,538 +4109,3038 @@ fn render_system_tab(
         .direction(Direction::Vertical)
         .constraints(
             [
-                Constraint::Length(6),
-                Constraint::Percentage(28),
-                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Percentage(28),
+                Constraint::Length(6),
+                Constraint::Percentage(60),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let load = System::load_average();
+    let uptime =
+        format_duration(Duration::from_secs(System::uptime())).unwrap_or_else(|| "N/A".to_string());
+    let host_name = System::host_name().unwrap_or_else(|| "Unknown host".to_string());
+    let summary_lines = vec![
+        Line::from(vec![Span::styled(
+            "System Overview",
+            Style::default().fg(Color::Yellow),
+        )]),
+        Line::from(format!(
+            "Host: {}  |  CPUs: {}  |  Uptime: {}",
+            host_name,
+            sys.cpus().len(),
+            uptime
+        )),
+        Line::from(format!(
+            "Load Avg (1/5/15m): {:.2} / {:.2} / {:.2}  |  Press 'q' to quit",
+            load.one, load.five, load.fifteen
+        )),
+    ];
+    let summary = Paragraph::new(summary_lines)
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    f.render_widget(summary, chunks[0]);
+
+    let cpu_ram_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
+
+    let cpu_percent = clamp_percent(cpu_usage);
+    let cpu_gauge = Gauge::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("CPU Usage (%)"),
+        )
+        .label(format!("{:.1}%", cpu_usage))
+        .gauge_style(Style::default().fg(Color::Blue))
+        .percent(cpu_percent);
+    f.render_widget(cpu_gauge, cpu_ram_chunks[0]);
+
+    let memory_label = format!(
+        "{:.1}% ({:.1} / {:.1} GiB)",
+        memory_usage_percentage,
+        kib_to_gib(used_memory as f64),
+        kib_to_gib(memory as f64)
+    );
+    let ram_gauge = Gauge::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("RAM Usage (%)"),
+        )
+        .label(memory_label)
+        .gauge_style(Style::default().fg(Color::Green))
+        .percent(memory_usage_percentage as u16);
+    f.render_widget(ram_gauge, cpu_ram_chunks[1]);
+
+    let network_info = Paragraph::new(vec![
+        Line::from(vec![Span::styled(
+            "Network",
+            Style::default().fg(Color::Cyan),
+        )]),
+        Line::from(format!(
+            "Download: {:>7.1} KB/s  |  Upload: {:>7.1} KB/s",
+            receive_rate_kbs, send_rate_kbs
+        )),
+        Line::from(format!(
+            "Total Received: {:>8.2} MiB  |  Total Sent: {:>8.2} MiB",
+            total_received as f64 / 1024.0 / 1024.0,
+            total_transmitted as f64 / 1024.0 / 1024.0
+        )),
+    ])
+    .block(Block::default().borders(Borders::ALL))
+    .wrap(Wrap { trim: true });
+    f.render_widget(network_info, chunks[2]);
+
+    let graph_chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+        .split(chunks[3]);
+
+    let cpu_graph_data: Vec<(String, u64)> = cpu_data
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| (i.to_string(), value))
+        .collect();
+
+    let cpu_graph_data_ref: Vec<(&str, u64)> = cpu_graph_data
+        .iter()
+        .map(|(label, value)| (label.as_str(), *value))
+        .collect();
+
+    let cpu_graph = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("CPU Usage Over Time"),
+        )
+        .data(&cpu_graph_data_ref)
+        .bar_width(3)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(Color::Blue))
+        .value_style(Style::default().fg(Color::Black).bg(Color::Blue));
+    f.render_widget(cpu_graph, graph_chunks[0]);
+
+    let ram_graph_data: Vec<(String, u64)> = ram_data
+        .iter()
+        .enumerate()
+        .map(|(i, &value)| (i.to_string(), value))
+        .collect();
+
+    let ram_graph_data_ref: Vec<(&str, u64)> = ram_graph_data
+        .iter()
+        .map(|(label, value)| (label.as_str(), *value))
+        .collect();
+
+    let ram_graph = BarChart::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("RAM Usage Over Time"),
+        )
+        .data(&ram_graph_data_ref)
+        .bar_width(3)
+        .bar_gap(1)
+        .bar_style(Style::default().fg(Color::Green))
+        .value_style(Style::default().fg(Color::Black).bg(Color::Green));
+
+    let right_graph_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(graph_chunks[1]);
+
+    f.render_widget(ram_graph, right_graph_chunks[0]);
+
+    let spark_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(right_graph_chunks[1]);
+
+    let download_sparkline = Sparkline::default()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Download KB/s"),
+        )
+        .style(Style::default().fg(Color::Cyan))
+        .data(network_receive_speed_data);
+    f.render_widget(download_sparkline, spark_chunks[0]);
+
+    let upload_sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title("Upload KB/s"))
+        .style(Style::default().fg(Color::Magenta))
+        .data(network_send_speed_data);
+    f.render_widget(upload_sparkline, spark_chunks[1]);
+}
+
+fn render_docker_tab(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState) {
+    let events_height = if app_state.show_docker_events { 12 } else { 0 };
+    let main_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
+                Constraint::Min(0),
+                Constraint::Length(events_height),
+                Constraint::Length(5),
+            ]
+            .as_ref(),
+        )
+        .split(area);
+
+    let mut view_title = match app_state.docker_view {
+        DockerView::Containers => {
+            "Containers | Tab:Switch | ↑↓:Nav | N:New | P:Postgres | R:Redis | M:Mongo | G:Grafana | S:Start | X:Stop | T:Restart | Z:Pause/Unpause | Shift+R:Rename | Shift+K:Kill | Shift+C:Commit | Shift+O:Export | Del:Remove | L:Logs | B:Files | Shift+T:Top | Shift+L:Limits | E:Events | Shift+E:Event type | F:Events of selected | H:Unhealthy/restarting only | Compose: Enter:Fold U:Up Shift+D:Down Shift+P:Pull"
+        }
+        DockerView::Images => {
+            "Images | Tab:Switch | ↑↓:Nav | X:Delete | Shift+P:Pull | C:Clear finished pulls"
+        }
+        DockerView::Networks => {
+            "Networks | Tab:Switch | ↑↓:Nav | N:Create | X:Remove | Shift+P:Prune"
+        }
+        DockerView::Volumes => {
+            "Volumes | Tab:Switch | ↑↓:Nav | N:Create | X:Remove | Shift+P:Prune"
+        }
+        DockerView::Disk => {
+            "Disk Usage | Tab:Switch | ↑↓:Category | Shift+P:Prune | Shift+A:Prune all unused images"
+        }
+    }
+    .to_string();
+    if app_state.docker_view == DockerView::Containers {
+        if app_state.show_problem_containers {
+            view_title = view_title.replacen(
+                "Containers",
+                "Containers [unhealthy/restarting/restarted <10m]",
+                1,
+            );
+        }
+        match Runtime::current() {
+            Runtime::Podman => view_title = view_title.replacen("Compose:", "Compose/Pod:", 1),
+            Runtime::Nerdctl => view_title.push_str(" | Shift+N:Namespace"),
+            Runtime::Docker => view_title.push_str(" | A:All hosts"),
+        }
+    }
+    // Hide Prune where the runtime has no command for it.
+    let prune = match app_state.docker_view {
+        DockerView::Volumes => Some(Prune::Volumes),
+        DockerView::Disk => Some(disk_prune(
+            app_state.docker_list_state.selected().unwrap_or(0),
+            false,
+        )),
+        _ => None,
+    };
+    if prune.is_some_and(|prune| Runtime::current().prune_args(prune).is_err()) {
+        view_title = view_title.replacen(" | Shift+P:Prune", "", 1);
+    }
+    if Runtime::current() == Runtime::Docker {
+        view_title.push_str(" | Shift+H:Hosts");
+    }
+    let view_title = match Runtime::current() {
+        Runtime::Nerdctl => format!(
+            "{} [{}] {}",
+            Runtime::current().name(),
+            containerd_namespace(),
+            view_title
+        ),
+        Runtime::Docker if app_state.all_docker_hosts => {
+            let mut hosts = "all hosts".to_string();
+            if !app_state.silent_docker_hosts.is_empty() {
+                hosts.push_str(&format!(
+                    ", no answer: {}",
+                    app_state.silent_docker_hosts.join(", ")
+                ));
+            }
+            format!("{} [{}] {}", Runtime::Docker.name(), hosts, view_title)
+        }
+        Runtime::Docker if active_endpoint_name() != "default" => format!(
+            "{} [{}] {}",
+            Runtime::Docker.name(),
+            active_endpoint_name(),
+            view_title
+        ),
+        runtime => format!("{} {}", runtime.name(), view_title),
+    };
+    let view_title = view_title.as_str();
+
+    match app_state.docker_view {
+        DockerView::Containers => {
+            let has_selection = app_state.docker_list_state.selected().is_some();
+
+            if has_selection && !app_state.docker_containers.is_empty() {
+                let chunks = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                    .split(main_chunks[0]);
+
+                let rows: Vec<Row> = app_state
+                    .docker_rows()
+                    .iter()
+                    .map(|row| docker_row(row, app_state, false))
+                    .collect();
+
+                let mut widths = vec![
+                    Constraint::Length(13),
+                    Constraint::Length(20),
+                    Constraint::Length(20),
+                    Constraint::Length(15),
+                    Constraint::Length(8),
+                    Constraint::Length(8),
+                    Constraint::Min(10),
+                ];
+                let mut header = vec!["ID", "Name", "Image", "Status", "Restarts", "CPU%", "Mem"];
+                if app_state.all_docker_hosts {
+                    widths.insert(1, Constraint::Length(16));
+                    header.insert(1, "Host");
+                }
+
+                let table = Table::new(rows, widths)
+                    .header(
+                        Row::new(header).style(
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                    )
+                    .block(Block::default().borders(Borders::ALL).title(view_title))
+                    .highlight_style(Style::default().bg(Color::DarkGray))
+                    .highlight_symbol(">> ");
+
+                f.render_stateful_widget(table, chunks[0], &mut app_state.docker_list_state);
+
+                match app_state.selected_docker_row() {
+                    Some(DockerRow::Group(group)) => {
+                        render_container_group(f, chunks[1], &group, app_state)
+                    }
+                    Some(DockerRow::Container(i)) => render_container_stats(
+                        f,
+                        chunks[1],
+                        &app_state.docker_containers[i],
+                        app_state,
+                    ),
+                    None => {}
+                }
+            } else {
+                let rows: Vec<Row> = app_state
+                    .docker_rows()
+                    .iter()
+                    .map(|row| docker_row(row, app_state, true))
+                    .collect();
+
+                let mut widths = vec![
+                    Constraint::Length(13),
+                    Constraint::Length(20),
+                    Constraint::Length(20),
+                    Constraint::Length(15),
+                    Constraint::Length(8),
+                    Constraint::Length(8),
+                    Constraint::Length(15),
+                    Constraint::Length(15),
+                    Constraint::Min(10),
+                ];
+                let mut header = vec![
+                    "ID",
+                    "Name",
+                    "Image",
+                    "Status",
+                    "Restarts",
+                    "CPU%",
+                    "Mem",
+                    "Net I/O",
+                    "Block I/O",
+                ];
+                if app_state.all_docker_hosts {
+                    widths.insert(1, Constraint::Length(16));
+                    header.insert(1, "Host");
+                }
+
+                let table = Table::new(rows, widths)
+                    .header(
+                        Row::new(header).style(
+                            Style::default()
+                                .fg(Color::Yellow)
+                                .add_modifier(Modifier::BOLD),
+                        ),
+                    )
+                    .block(Block::default().borders(Borders::ALL).title(view_title))
+                    .highlight_style(Style::default().bg(Color::DarkGray))
+                    .highlight_symbol(">> ");
+
+                f.render_stateful_widget(table, main_chunks[0], &mut app_state.docker_list_state);
+            }
+        }
+        DockerView::Images => {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(main_chunks[0]);
+            let (table_area, pulls_area) = if app_state.image_pulls.is_empty() {
+                (columns[0], None)
+            } else {
+                let chunks = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                    .split(columns[0]);
+                (chunks[0], Some(chunks[1]))
+            };
+
+            let rows: Vec<Row> = app_state
+                .docker_images
+                .iter()
+                .map(|img| {
+                    let (usage, color) = if img.dangling() {
+                        ("dangling", Color::Red)
+                    } else if img.in_use {
+                        ("in use", Color::Green)
+                    } else {
+                        ("unused", Color::Yellow)
+                    };
+                    Row::new(vec![
+                        Cell::from(img.repository.clone()),
+                        Cell::from(img.tag.clone()),
+                        Cell::from(img.image_id[..12].to_string()),
+                        Cell::from(img.size.clone()),
+                        Cell::from(Span::styled(usage, Style::default().fg(color))),
+                    ])
+                })
+                .collect();
+
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Length(30),
+                    Constraint::Length(15),
+                    Constraint::Length(13),
+                    Constraint::Length(10),
+                    Constraint::Min(8),
+                ],
+            )
+            .header(
+                Row::new(vec!["Repository", "Tag", "Image ID", "Size", "Usage"]).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            )
+            .block(Block::default().borders(Borders::ALL).title(view_title))
+            .highlight_style(Style::default().bg(Color::DarkGray))
+            .highlight_symbol(">> ");
+
+            f.render_stateful_widget(table, table_area, &mut app_state.docker_list_state);
+            if let Some(area) = pulls_area {
+                render_image_pulls(f, area, &app_state.image_pulls);
+            }
+            let details = app_state
+                .selected_image()
+                .and_then(|image| app_state.image_details.get(&image.image_id));
+            match details {
+                Some(Ok(details)) => {
+                    let containers = app_state
+                        .image_users
+                        .get(details.id.trim_start_matches("sha256:"))
+                        .map_or(&[][..], Vec::as_slice);
+                    render_image_details(f, columns[1], details, containers)
+                }
+                Some(Err(e)) => f.render_widget(
+                    Paragraph::new(format!("Failed to inspect image: {}", e))
+                        .block(
+                            Block::default()
+                                .borders(Borders::ALL)
+                                .title("Image Details"),
+                        )
+                        .wrap(Wrap { trim: true }),
+                    columns[1],
+                ),
+                None if app_state.selected_image().is_some() => f.render_widget(
+                    Paragraph::new("Inspecting image...").block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .title("Image Details"),
+                    ),
+                    columns[1],
+                ),
+                None => {}
+            }
+        }
+        DockerView::Networks => render_docker_networks(f, main_chunks[0], app_state, view_title),
+        DockerView::Volumes => render_docker_volumes(f, main_chunks[0], app_state, view_title),
+        DockerView::Disk => render_docker_disk(f, main_chunks[0], app_state, view_title),
+    }
+
+    if app_state.show_docker_events {
+        render_docker_events(f, main_chunks[1], app_state);
+    }
+
+    let help = Paragraph::new(app_state.message.clone())
+        .block(Block::default().borders(Borders::ALL).title("Message"))
+        .wrap(Wrap { trim: true });
+    f.render_widget(help, main_chunks[2]);
+}
+
+/// The most recent Docker events, newest at the bottom.
+fn render_docker_events(f: &mut ratatui::Frame, area: Rect, app_state: &AppState) {
+    let events = app_state.filtered_docker_events();
+    let visible = area.height.saturating_sub(2) as usize;
+    let lines: Vec<Line> = events[events.len().saturating_sub(visible)..]
+        .iter()
+        .map(|event| {
+            let color = if event.is_failure() {
+                Color::Red
+            } else {
+                match event.action.as_str() {
+                    "start" | "unpause" | "pull" => Color::Green,
+                    "create" | "connect" | "mount" => Color::Cyan,
+                    "health_status" if event.detail == "starting" => Color::Yellow,
+                    "health_status" => Color::Green,
+                    "kill" | "stop" | "pause" | "die" => Color::Yellow,
+                    "destroy" | "delete" | "untag" | "disconnect" => Color::DarkGray,
+                    _ => Color::White,
+                }
+            };
+            let mut spans = vec![Span::styled(
+                event.local_time(),
+                Style::default().fg(Color::DarkGray),
+            )];
+            if app_state.all_docker_hosts {
+                spans.push(Span::styled(
+                    format!(" {:<12}", event.host),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            spans.extend([
+                Span::raw(format!(" {:<9} ", event.kind)),
+                Span::styled(
+                    format!("{:<14}", event.action),
+                    Style::default().fg(color).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(" {:<30} ", event.name)),
+                Span::styled(event.detail.clone(), Style::default().fg(color)),
+            ]);
+            Line::from(spans)
+        })
+        .collect();
+
+    let stream = app_state.docker_events.as_ref();
+    let status = match stream {
+        Some(stream) if stream.connected => "live".to_string(),
+        Some(EventStream {
+            last_error: Some(error),
+            ..
+        }) => format!("disconnected: {}", error),
+        _ => "connecting".to_string(),
+    };
+    let title = format!(
+        "Events ({}) [type: {}] [container: {}] | E:Hide Shift+E:Type F:Selected container",
+        status,
+        app_state.event_kind_filter.unwrap_or("all"),
+        app_state
+            .event_container_filter
+            .as_ref()
+            .map_or("all", |(_, name)| name.as_str()),
+    );
+    let panel = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(panel, area);
+}
+
+fn render_image_details(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    details: &ImageDetails,
+    containers: &[String],
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let none = || "<none>".to_string();
+    let list = |items: &[String]| {
+        if items.is_empty() {
+            none()
+        } else {
+            items.join(" ")
+        }
+    };
+    let mut lines = vec![
+        Line::from(format!("ID: {}", details.id)),
+        Line::from(format!("Created: {}", details.created)),
+        Line::from(format!(
+            "Platform: {}/{} | Size: {}",
+            details.os,
+            details.architecture,
+            format_bytes(details.size)
+        )),
+        Line::from(format!("Entrypoint: {}", list(&details.entrypoint))),
+        Line::from(format!("Cmd: {}", list(&details.cmd))),
+        Line::from(format!("Exposed ports: {}", list(&details.exposed_ports))),
+        Line::from(format!(
+            "Used by: {}",
+            if containers.is_empty() {
+                none()
+            } else {
+                containers.join(", ")
+            }
+        )),
+    ];
+    if let Some(digest) = details.digests.first() {
+        lines.push(Line::from(format!("Digest: {}", digest)));
+    }
+    lines.push(Line::from(Span::styled(
+        "Labels",
+        Style::default().add_modifier(Modifier::BOLD),
+    )));
+    if details.labels.is_empty() {
+        lines.push(Line::from(none()));
+    }
+    lines.extend(
+        details
+            .labels
+            .iter()
+            .map(|(key, value)| Line::from(format!("  {}={}", key, value))),
+    );
+    let info = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Image Details"),
+        )
+        .wrap(Wrap { trim: true });
+    f.render_widget(info, chunks[0]);
+
+    let rows: Vec<Row> = details
+        .history
+        .iter()
+        .map(|layer| {
+            // Metadata-only steps (ENV, CMD, ...) add no bytes.
+            let style = if layer.size == "0B" {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            Row::new(vec![
+                Cell::from(layer.size.clone()),
+                Cell::from(layer.created_since.clone()),
+                Cell::from(layer.created_by.clone()),
+            ])
+            .style(style)
+        })
+        .collect();
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(9),
+            Constraint::Length(14),
+            Constraint::Min(20),
+        ],
+    )
+    .header(
+        Row::new(vec!["Size", "Created", "Command"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("History ({} layers)", details.history.len())),
+    );
+    f.render_widget(table, chunks[1]);
+}
+
+fn render_image_pulls(f: &mut ratatui::Frame, area: Rect, pulls: &[ImagePull]) {
+    let mut lines = Vec::new();
+    for pull in pulls {
+        let (downloaded, total) = pull.totals();
+        let ratio = if total > 0 {
+            downloaded as f64 / total as f64
+        } else {
+            0.0
+        };
+        let (summary, color) = match (&pull.error, &pull.digest, pull.finished) {
+            (Some(error), _, _) => (format!("failed: {}", error), Color::Red),
+            (None, Some(digest), true) => (digest.clone(), Color::Green),
+            (None, _, true) => (pull.status.clone(), Color::Green),
+            (None, _, false) => (
+                format!(
+                    "{} {} / {}",
+                    progress_bar(ratio, 30),
+                    format_bytes(downloaded),
+                    format_bytes(total)
+                ),
+                Color::Yellow,
+            ),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} ", pull.reference),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(summary, Style::default().fg(color)),
+        ]));
+        // Finished pulls collapse to their summary line.
+        if pull.finished {
+            continue;
+        }
+        for layer in &pull.layers {
+            let size = if layer.size > 0 {
+                format!(
+                    "{} / {}",
+                    format_bytes(layer.downloaded),
+                    format_bytes(layer.size)
+                )
+            } else {
+                String::new()
+            };
+            lines.push(Line::from(format!(
+                "  {} {} {:<18} {}",
+                layer.id,
+                progress_bar(layer.ratio(), 20),
+                layer.status,
+                size
+            )));
+        }
+    }
+
+    let active = pulls.iter().filter(|pull| !pull.finished).count();
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Image Pulls ({} running)", active)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn render_file_transfers(f: &mut ratatui::Frame, area: Rect, transfers: &[Transfer]) {
+    let lines: Vec<Line> = transfers
+        .iter()
+        .rev()
+        .take(area.height.saturating_sub(2) as usize)
+        .map(|transfer| {
+            let progress = match &transfer.result {
+                Some(Ok(())) => Span::styled(
+                    format!("done, {}", format_bytes(transfer.transferred)),
+                    Style::default().fg(Color::Green),
+                ),
+                Some(Err(e)) => {
+                    Span::styled(format!("failed: {}", e), Style::default().fg(Color::Red))
+                }
+                None => match (transfer.ratio(), transfer.total) {
+                    (Some(ratio), Some(total)) => Span::raw(format!(
+                        "{} {:>3.0}% {} / {}",
+                        progress_bar(ratio, 20),
+                        ratio * 100.0,
+                        format_bytes(transfer.transferred),
+                        format_bytes(total)
+                    )),
+                    _ => Span::raw(format!("{} so far", format_bytes(transfer.transferred))),
+                },
+            };
+            Line::from(vec![Span::raw(format!("{}  ", transfer.label)), progress])
+        })
+        .collect();
+    let running = transfers.iter().filter(|t| t.result.is_none()).count();
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Transfers ({} running)", running)),
+    );
+    f.render_widget(paragraph, area);
+}
+
+fn progress_bar(ratio: f64, width: usize) -> String {
+    let filled = ((ratio.clamp(0.0, 1.0) * width as f64).round() as usize).min(width);
+    format!("[{}{}]", "=".repeat(filled), " ".repeat(width - filled))
+}
+
+fn render_docker_networks(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    app_state: &mut AppState,
+    title: &str,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .split(area);
+
+    let rows: Vec<Row> = app_state
+        .docker_networks
+        .iter()
+        .map(|network| {
+            let name_style = if network.is_built_in() {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+            let containers_style = if network.containers.is_empty() {
+                Style::default()
+            } else {
+                Style::default().fg(Color::Green)
+            };
+            Row::new(vec![
+                Cell::from(Span::styled(network.name.clone(), name_style)),
+                Cell::from(network.id.chars().take(12).collect::<String>()),
+                Cell::from(network.driver.clone()),
+                Cell::from(network.scope.clone()),
+                Cell::from(network.subnets.join(", ")),
+                Cell::from(Span::styled(
+                    network.containers.len().to_string(),
+                    containers_style,
+                )),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(25),
+            Constraint::Length(13),
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(20),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Name",
+            "ID",
+            "Driver",
+            "Scope",
+            "Subnet",
+            "Containers",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
+    f.render_stateful_widget(table, chunks[0], &mut app_state.docker_list_state);
+
+    let Some(network) = app_state.selected_network() else {
+        return;
+    };
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            format!("Network {}", network.name),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(format!("Gateway: {}", network.gateways.join(", "))),
+        Line::from(format!("Internal: {}", network.internal)),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Attached containers",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+    if network.containers.is_empty() {
+        lines.push(Line::from("<none>"));
+    }
+    lines.extend(
+        network
+            .containers
+            .iter()
+            .map(|(name, address)| Line::from(format!("{}  {}", name, address))),
+    );
+    let details = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    f.render_widget(details, chunks[1]);
+}
+
+fn render_docker_volumes(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    app_state: &mut AppState,
+    title: &str,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(65), Constraint::Percentage(35)])
+        .split(area);
+
+    let title = if app_state.volume_task.is_some() {
+        format!("{} (listing...)", title)
+    } else {
+        title.to_string()
+    };
+    let rows: Vec<Row> = app_state
+        .docker_volumes
+        .iter()
+        .map(|volume| {
+            let used_by = if volume.containers.is_empty() {
+                Span::styled("unused", Style::default().fg(Color::DarkGray))
+            } else {
+                Span::styled(
+                    format!("{} containers", volume.containers.len()),
+                    Style::default().fg(Color::Green),
+                )
+            };
+            Row::new(vec![
+                Cell::from(volume.name.clone()),
+                Cell::from(volume.driver.clone()),
+                Cell::from(volume.size.clone().unwrap_or_else(|| "-".to_string())),
+                Cell::from(used_by),
+                Cell::from(volume.mountpoint.clone()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(30),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(14),
+            Constraint::Min(20),
+        ],
+    )
+    .header(
+        Row::new(vec!["Name", "Driver", "Size", "Used By", "Mountpoint"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
+    f.render_stateful_widget(table, chunks[0], &mut app_state.docker_list_state);
+
+    let Some(volume) = app_state.selected_volume() else {
+        return;
+    };
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            format!("Volume {}", volume.name),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(format!("Created: {}", volume.created)),
+        Line::from(format!("Mountpoint: {}", volume.mountpoint)),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Used by",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+    if volume.containers.is_empty() {
+        lines.push(Line::from("<none>"));
+    }
+    lines.extend(volume.containers.iter().map(|user| {
+        if user.running {
+            Line::from(user.name.clone())
+        } else {
+            Line::from(Span::styled(
+                format!("{} (stopped)", user.name),
+                Style::default().fg(Color::DarkGray),
+            ))
+        }
+    }));
+    let details = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    f.render_widget(details, chunks[1]);
+}
+
+fn render_docker_disk(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState, title: &str) {
+    let usage = match &app_state.disk_usage {
+        Some(Ok(usage)) => usage,
+        Some(Err(e)) => {
+            let error = Paragraph::new(format!("Failed to compute disk usage: {}", e))
+                .style(Style::default().fg(Color::Red))
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .wrap(Wrap { trim: true });
+            f.render_widget(error, area);
+            return;
+        }
+        None => {
+            let loading = Paragraph::new("Calculating disk usage...")
+                .block(Block::default().borders(Borders::ALL).title(title));
+            f.render_widget(loading, area);
+            return;
+        }
+    };
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(DISK_CATEGORIES.len() as u16 + 3),
+            Constraint::Min(0),
+        ])
+        .split(area);
+
+    let header_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let rows: Vec<Row> = DISK_CATEGORIES
+        .iter()
+        .map(
+            |kind| match usage.summary.iter().find(|c| c.kind == *kind) {
+                Some(category) => Row::new(vec![
+                    Cell::from(category.kind.clone()),
+                    Cell::from(category.total_count.clone()),
+                    Cell::from(category.active.clone()),
+                    Cell::from(category.size.clone()),
+                    Cell::from(category.reclaimable.clone())
+                        .style(Style::default().fg(Color::Yellow)),
+                ]),
+                None => Row::new(vec![kind.to_string(), "-".to_string()]),
+            },
+        )
+        .collect();
+    let summary = Table::new(
+        rows,
+        [
+            Constraint::Length(16),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(12),
+            Constraint::Min(16),
+        ],
+    )
+    .header(Row::new(vec!["Type", "Total", "Active", "Size", "Reclaimable"]).style(header_style))
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
+    f.render_stateful_widget(summary, chunks[0], &mut app_state.docker_list_state);
+
+    // Rows a prune would remove are yellow; dangling images red.
+    let unused = Style::default().fg(Color::Yellow);
+    let category = app_state.docker_list_state.selected().unwrap_or(0);
+    let (header, widths, rows): (Vec<&str>, Vec<Constraint>, Vec<Row>) = match category {
+        0 => (
+            vec!["Image", "ID", "Size", "Shared", "Unique", "Containers"],
+            vec![
+                Constraint::Min(30),
+                Constraint::Length(14),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+            usage
+                .images
+                .iter()
+                .map(|image| {
+                    let style = if image.dangling() && !image.in_use() {
+                        Style::default().fg(Color::Red)
+                    } else if !image.in_use() {
+                        unused
+                    } else {
+                        Style::default()
+                    };
+                    Row::new(vec![
+                        image.name(),
+                        image
+                            .id
+                            .trim_start_matches("sha256:")
+                            .chars()
+                            .take(12)
+                            .collect(),
+                        image.size.clone(),
+                        image.shared_size.clone(),
+                        image.unique_size.clone(),
+                        image.containers.clone(),
+                    ])
+                    .style(style)
+                })
+                .collect(),
+        ),
+        1 => (
+            vec!["Container", "Image", "Size", "State", "Status"],
+            vec![
+                Constraint::Length(30),
+                Constraint::Length(30),
+                Constraint::Length(24),
+                Constraint::Length(10),
+                Constraint::Min(16),
+            ],
+            usage
+                .containers
+                .iter()
+                .map(|container| {
+                    Row::new(vec![
+                        container.names.clone(),
+                        container.image.clone(),
+                        container.size.clone(),
+                        container.state.clone(),
+                        container.status.clone(),
+                    ])
+                    .style(if container.stopped() {
+                        unused
+                    } else {
+                        Style::default()
+                    })
+                })
+                .collect(),
+        ),
+        2 => (
+            vec!["Volume", "Links", "Size"],
+            vec![
+                Constraint::Min(30),
+                Constraint::Length(8),
+                Constraint::Length(12),
+            ],
+            usage
+                .volumes
+                .iter()
+                .map(|volume| {
+                    Row::new(vec![
+                        volume.name.clone(),
+                        volume.links.clone(),
+                        volume.size.clone(),
+                    ])
+                    .style(if volume.in_use() {
+                        Style::default()
+                    } else {
+                        unused
+                    })
+                })
+                .collect(),
+        ),
+        _ => (
+            vec![
+                "Cache ID",
+                "Type",
+                "Size",
+                "In Use",
+                "Last Used",
+                "Description",
+            ],
+            vec![
+                Constraint::Length(14),
+                Constraint::Length(12),
+                Constraint::Length(10),
+                Constraint::Length(7),
+                Constraint::Length(16),
+                Constraint::Min(20),
+            ],
+            usage
+                .build_cache
+                .iter()
+                .map(|cache| {
+                    Row::new(vec![
+                        cache.id.chars().take(12).collect(),
+                        cache.cache_type.clone(),
+                        cache.size.clone(),
+                        cache.in_use.clone(),
+                        cache.last_used_since.clone(),
+                        cache.description.clone(),
+                    ])
+                    .style(if cache.in_use() {
+                        Style::default()
+                    } else {
+                        unused
+                    })
+                })
+                .collect(),
+        ),
+    };
+    let mut details_title = DISK_CATEGORIES[category.min(DISK_CATEGORIES.len() - 1)].to_string();
+    if let Err(e) = Runtime::current().prune_args(disk_prune(category, false)) {
+        details_title.push_str(&format!(" ({})", e));
+    }
+    let details = Table::new(rows, widths)
+        .header(Row::new(header).style(header_style))
+        .block(Block::default().borders(Borders::ALL).title(details_title));
+    f.render_widget(details, chunks[1]);
+}
+
+fn render_prune_preview(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState) {
+    let Some(preview) = app_state.prune_preview.as_mut() else {
+        return;
+    };
+    let popup_area = centered_rect(70, 70, area);
+    f.render_widget(Clear, popup_area);
+
+    let rows: Vec<Row> = preview
+        .items
+        .iter()
+        .map(|(name, size)| Row::new(vec![name.clone(), size.clone()]))
+        .collect();
+    let title = format!(
+        "{}: {} items, {} | y/Enter: Prune | Esc: Cancel",
+        preview.title,
+        preview.items.len(),
+        format_bytes(preview.total_bytes as u64)
+    );
+    let table = Table::new(rows, [Constraint::Min(30), Constraint::Length(24)])
+        .header(
+            Row::new(vec!["Will be removed", "Size"]).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .style(Style::default().bg(Color::Black)),
+        )
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol(">> ");
+    f.render_stateful_widget(table, popup_area, &mut preview.list_state);
+}
+
+/// Builds a Containers view row; `wide` adds the Net I/O and Block I/O columns.
+fn docker_row(row: &DockerRow, app_state: &AppState, wide: bool) -> Row<'static> {
+    match row {
+        DockerRow::Group(group) => {
+            let marker = if app_state.collapsed_projects.contains(&group.key()) {
+                "▸"
+            } else {
+                "▾"
+            };
+            let total = group.containers.len();
+            let status_color = if group.running == total {
+                Color::Green
+            } else if group.running == 0 {
+                Color::Red
+            } else {
+                Color::Yellow
+            };
+
+            let mut cells = vec![
+                Cell::from(match group.kind {
+                    GroupKind::Compose(_) => format!("{} compose", marker),
+                    GroupKind::Pod(_) => format!("{} pod", marker),
+                }),
+                Cell::from(Span::styled(
+                    group.kind.name().to_string(),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )),
+                Cell::from(format!("{} containers", total)),
+                Cell::from(Span::styled(
+                    format!("{}/{} running", group.running, total),
+                    Style::default().fg(status_color),
+                )),
+                Cell::from(""),
+                Cell::from(format!("{:.1}%", group.cpu_percent)),
+                Cell::from(format_bytes(group.mem_bytes as u64)),
+            ];
+            if wide {
+                cells.extend([Cell::from(""), Cell::from("")]);
+            }
+            if app_state.all_docker_hosts {
+                cells.insert(1, Cell::from(group.host.clone()));
+            }
+            Row::new(cells)
+        }
+        DockerRow::Container(i) => {
+            let c = &app_state.docker_containers[*i];
+            let restarts_style = if c.state.restart_count > 0 {
+                Style::default().fg(Color::Magenta)
+            } else {
+                Style::default()
+            };
+            // Indent project and pod members under their header.
+            let name = if c.compose.is_some() || c.pod.is_some() {
+                format!("  {}", c.name)
+            } else {
+                c.name.clone()
+            };
+
+            let mut cells = vec![
+                Cell::from(c.id[..12].to_string()),
+                Cell::from(name),
+                Cell::from(c.image.clone()),
+                Cell::from(Span::styled(
+                    c.status.clone(),
+                    Style::default().fg(c.status_color()),
+                )),
+                Cell::from(Span::styled(
+                    c.state.restart_count.to_string(),
+                    restarts_style,
+                )),
+                Cell::from(format!("{:.1}%", c.cpu_percent)),
+                Cell::from(c.mem_usage.clone()),
+            ];
+            if wide {
+                cells.extend([Cell::from(c.net_io.clone()), Cell::from(c.block_io.clone())]);
+            }
+            if app_state.all_docker_hosts {
+                cells.insert(1, Cell::from(c.host.clone()));
+            }
+            Row::new(cells)
+        }
+    }
+}
+
+fn render_container_group(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    group: &ContainerGroup,
+    app_state: &AppState,
+) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(6), Constraint::Min(0)])
+        .split(area);
+
+    let mut lines = match &group.kind {
+        GroupKind::Compose(info) => {
+            let config_files = if info.config_files.is_empty() {
+                "<unknown>".to_string()
+            } else {
+                info.config_files.join(", ")
+            };
+            vec![
+                Line::from(vec![Span::styled(
+                    format!("Compose Project {}", info.project),
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )]),
+                Line::from(format!("Directory: {}", info.working_dir)),
+                Line::from(format!("Files: {}", config_files)),
+            ]
+        }
+        GroupKind::Pod(name) => vec![
+            Line::from(vec![Span::styled(
+                format!("Pod {}", name),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            )]),
+            Line::from("Containers share the pod's network and IPC namespaces"),
+        ],
+    };
+    lines.push(Line::from(format!(
+        "Running: {}/{} | CPU: {:.1}% | Mem: {}",
+        group.running,
+        group.containers.len(),
+        group.cpu_percent,
+        format_bytes(group.mem_bytes as u64)
+    )));
+    let info = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    f.render_widget(info, chunks[0]);
+
+    let rows: Vec<Row> = group
+        .containers
+        .iter()
+        .map(|&i| {
+            let c = &app_state.docker_containers[i];
+            // Pod members have no service, so they go by container name.
+            let service = c
+                .compose
+                .as_ref()
+                .map_or_else(|| c.name.clone(), |info| info.service.clone());
+            Row::new(vec![
+                Cell::from(service),
+                Cell::from(Span::styled(
+                    c.status.clone(),
+                    Style::default().fg(c.status_color()),
+                )),
+                Cell::from(format!("{:.1}%", c.cpu_percent)),
+                Cell::from(
+                    c.mem_usage
+                        .split('/')
+                        .next()
+                        .unwrap_or("")
+                        .trim()
+                        .to_string(),
+                ),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(18),
+            Constraint::Length(20),
+            Constraint::Length(8),
+            Constraint::Min(8),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            match group.kind {
+                GroupKind::Compose(_) => "Service",
+                GroupKind::Pod(_) => "Container",
+            },
+            "Status",
+            "CPU%",
+            "Mem",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title("Services"));
+    f.render_widget(table, chunks[1]);
+}
+
+fn render_container_stats(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    container: &DockerContainer,
+    app_state: &AppState,
+) {
+    // Container info
+    let mut lines = vec![
+        Line::from(vec![Span::styled(
+            "Container Details",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(format!("Name: {}", container.name)),
+        Line::from(format!("Ports: {}", container.ports)),
+        Line::from(format!(
+            "Restarts: {} | Last exit code: {}{}",
+            container.state.restart_count,
+            container.state.exit_code,
+            if container.state.oom_killed {
+                " (OOM killed)"
+            } else {
+                ""
+            }
+        )),
+    ];
+    match &app_state.container_health {
+        Some((id, _, Ok(Some(health)))) if *id == container.id => {
+            let color = match health.status.as_str() {
+                "healthy" => Color::Green,
+                "unhealthy" => Color::Red,
+                _ => Color::Yellow,
+            };
+            lines.push(Line::from(vec![
+                Span::raw("Health: "),
+                Span::styled(health.status.clone(), Style::default().fg(color)),
+                Span::raw(format!(" (failing streak {})", health.failing_streak)),
+            ]));
+            // Docker keeps the last five results; show the newest first.
+            for check in health.log.iter().rev() {
+                let output = check.output.lines().next().unwrap_or_default().trim();
+                lines.push(Line::from(Span::styled(
+                    format!(
+                        "  {} exit {}: {}",
+                        check.local_time(),
+                        check.exit_code,
+                        output
+                    ),
+                    Style::default().fg(if check.exit_code == 0 {
+                        Color::DarkGray
+                    } else {
+                        Color::Red
+                    }),
+                )));
+            }
+        }
+        Some((id, _, Err(e))) if *id == container.id => {
+            lines.push(Line::from(format!("Health: {}", e)));
+        }
+        _ => {}
+    }
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(lines.len() as u16 + 2),
+            Constraint::Percentage(33),
+            Constraint::Percentage(33),
+            Constraint::Percentage(34),
+        ])
+        .split(area);
+
+    let info = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL))
+        .wrap(Wrap { trim: true });
+    f.render_widget(info, chunks[0]);
+
+    if let Some(history) = app_state.container_stats_history.get(&container.id) {
+        let cpu_data: Vec<u64> = history.iter().map(|s| s.cpu_percent as u64).collect();
+        let cpu_sparkline = Sparkline::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("CPU History ({:.1}%)", container.cpu_percent)),
+            )
+            .style(Style::default().fg(Color::Blue))
+            .data(&cpu_data);
+        f.render_widget(cpu_sparkline, chunks[1]);
+
+        let mem_data: Vec<u64> = history.iter().map(|s| s.mem_percent as u64).collect();
+        let mem_sparkline = Sparkline::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Memory History ({:.1}%)", container.mem_percent)),
+            )
+            .style(Style::default().fg(Color::Green))
+            .data(&mem_data);
+        f.render_widget(mem_sparkline, chunks[2]);
+
+        let net_data: Vec<u64> = history
+            .iter()
+            .map(|s| (s.net_rx_kb + s.net_tx_kb) as u64)
+            .collect();
+        let net_sparkline = Sparkline::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!("Network I/O ({})", container.net_io)),
+            )
+            .style(Style::default().fg(Color::Cyan))
+            .data(&net_data);
+        f.render_widget(net_sparkline, chunks[3]);
+    }
+}
+
+fn render_create_dialog(f: &mut ratatui::Frame, area: Rect, state: &CreateDialogState) {
+    let popup_area = centered_rect(90, 90, area);
+
+    f.render_widget(
+        Block::default().style(Style::default().bg(Color::Black)),
+        area,
+    );
+
+    let block = Block::default()
+        .title("Create Docker Container (Tab/Shift+Tab: Navigate, ←/→: Choose, Ctrl+P/N: History, Enter: Create or new line in Env/Volumes/Labels, Esc: Cancel)")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(block, popup_area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(0), Constraint::Length(6)])
+        .margin(1)
+        .split(popup_area);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[0]);
+    let left = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3); 8])
+        .split(columns[0]);
+    let right = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Ratio(2, 4),
+            Constraint::Ratio(1, 4),
+            Constraint::Ratio(1, 4),
+        ])
+        .split(columns[1]);
+
+    let values = state.values();
+    let labels = [
+        "Image*, e.g. postgres:16, redis:alpine (Tab: complete)",
+        "Container Name",
+        "Ports, e.g. 8080:80, 127.0.0.1:5353:53/udp",
+        "Network (←/→)",
+        "Restart Policy (←/→)",
+        "Memory Limit, e.g. 512m or 2g",
+        "CPUs, e.g. 0.5 or 2",
+        "Command, quoted like a shell, e.g. sh -c 'echo hi'",
+        "Env Vars, one KEY=value per line",
+        "Volumes, one per line, e.g. ./data:/data or name:/data:ro",
+        "Labels, one KEY=value per line",
+    ];
+
+    for (i, label) in labels.iter().enumerate() {
+        let chunk = if i < 8 { left[i] } else { right[i - 8] };
+        let error = state.field_error(i);
+        let style = if i == state.selected_field {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+        let border_style = if error.is_some() {
+            style.fg(Color::Red)
+        } else {
+            style
+        };
+        let title = match &error {
+            Some(error) => format!("{} - {}", label, error),
+            None => label.to_string(),
+        };
+
+        let mut block = Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .border_style(border_style);
+        let input = &state.inputs[i];
+        match i {
+            CreateDialogState::NETWORK_FIELD | CreateDialogState::RESTART_FIELD => {
+                let value = match input.value() {
+                    "" => "default (bridge)",
+                    value => value,
+                };
+                let choice = Paragraph::new(format!("◀ {} ▶", value))
+                    .style(style)
+                    .block(block);
+                f.render_widget(choice, chunk);
+            }
+            _ => {
+                let focused = i == state.selected_field;
+                let matches = input.matches(&state.images);
+                if i == 0 && focused && matches.len() > 1 {
+                    let shown: Vec<&str> = matches.iter().take(6).copied().collect();
+                    block = block.title_bottom(format!(" {} ", shown.join(", ")));
+                }
+                input.render(f, chunk, block, style, focused);
+            }
+        }
+    }
+
+    let preview = match values.run_args() {
+        Ok(args) => Paragraph::new(format!(
+            "docker {}",
+            args.iter()
+                .map(|arg| shell_quote(arg))
+                .collect::<Vec<_>>()
+                .join(" ")
+        ))
+        .style(Style::default().fg(Color::Green)),
+        Err(e) => Paragraph::new(e).style(Style::default().fg(Color::Red)),
+    };
+    f.render_widget(
+        preview.wrap(Wrap { trim: false }).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Equivalent command"),
+        ),
+        rows[1],
+    );
+}
+
+fn render_update_dialog(f: &mut ratatui::Frame, area: Rect, state: &UpdateDialogState) {
+    let popup_area = centered_rect(70, 80, area);
+
+    f.render_widget(
+        Block::default().style(Style::default().bg(Color::Black)),
+        area,
+    );
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Min(0),
+        ])
+        .margin(1)
+        .split(popup_area);
+
+    let block = Block::default()
+        .title(format!(
+            "Update {} (Tab/Shift+Tab: Navigate, Enter: Apply, Esc: Cancel)",
+            state.container_name
+        ))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(Color::Black));
+    f.render_widget(block, popup_area);
+
+    let current = &state.current;
+    let unlimited = |value: &str| {
+        if value.is_empty() {
+            "unlimited".to_string()
+        } else {
+            value.to_string()
+        }
+    };
+    let fields = [
+        (
+            format!(
+                "CPU Shares, relative weight (current: {}, default 1024)",
+                if current.cpu_shares.is_empty() {
+                    "default"
+                } else {
+                    &current.cpu_shares
+                }
+            ),
+            &state.inputs[0],
+        ),
+        (
+            format!(
+                "CPUs, e.g. 0.5 or 2 (current: {})",
+                unlimited(&current.cpus)
+            ),
+            &state.inputs[1],
+        ),
+        (
+            format!(
+                "Memory, e.g. 512m or 2g (current: {})",
+                unlimited(&current.memory)
+            ),
+            &state.inputs[2],
+        ),
+        (
+            format!(
+                "Memory + Swap, -1 for unlimited swap (current: {})",
+                unlimited(&current.memory_swap)
+            ),
+            &state.inputs[3],
+        ),
+        (
+            format!(
+                "PIDs Limit, empty for unlimited (current: {})",
+                unlimited(&current.pids_limit)
+            ),
+            &state.inputs[4],
+        ),
+        (
+            format!(
+                "Restart Policy: no, always, unless-stopped, on-failure[:N] (current: {})",
+                current.restart_policy
+            ),
+            &state.inputs[5],
+        ),
+    ];
+
+    for (i, (label, input)) in fields.iter().enumerate() {
+        let style = if i == state.selected_field {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(label.as_str())
+            .border_style(style);
+        input.render(f, chunks[i], block, style, i == state.selected_field);
+    }
+
+    let help = match &state.error {
+        Some(error) => Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)),
+        None => Paragraph::new("Only changed fields are passed to docker update.")
+            .style(Style::default().fg(Color::DarkGray)),
+    };
+    f.render_widget(help.wrap(Wrap { trim: true }), chunks[6]);
+}
+
+fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+fn render_k8s_tab(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints(
+            [
                 Constraint::Percentage(60),
+                Constraint::Min(0),
+                Constraint::Length(4),
             ]
             .as_ref(),
         )
         .split(area);
 
-    let load = System::load_average();
-    let uptime =
-        format_duration(Duration::from_secs(System::uptime())).unwrap_or_else(|| "N/A".to_string());
-    let host_name = System::host_name().unwrap_or_else(|| "Unknown host".to_string());
-    let summary_lines = vec![
-        Line::from(vec![Span::styled(
-            "System Overview",
-            Style::default().fg(Color::Yellow),
-        )]),
-        Line::from(format!(
-            "Host: {}  |  CPUs: {}  |  Uptime: {}",
-            host_name,
-            sys.cpus().len(),
-            uptime
-        )),
-        Line::from(format!(
-            "Load Avg (1/5/15m): {:.2} / {:.2} / {:.2}  |  Press 'q' to quit",
-            load.one, load.five, load.fifteen
-        )),
-    ];
-    let summary = Paragraph::new(summary_lines)
-        .block(Block::default().borders(Borders::ALL))
+    let context = app_state
+        .k8s_client
+        .as_ref()
+        .map(|c| c.config().context.clone())
+        .unwrap_or_else(|| "not connected".to_string());
+
+    match app_state.k8s_view {
+        K8sView::Pods => render_k8s_pods(f, &chunks, app_state, &context),
+        K8sView::Services => render_k8s_services(f, &chunks, app_state, &context),
+        K8sView::Ingresses => render_k8s_ingresses(f, &chunks, app_state, &context),
+        K8sView::ConfigMaps | K8sView::Secrets => {
+            render_k8s_config_data(f, &chunks, app_state, &context)
+        }
+        K8sView::Jobs => render_k8s_jobs(f, &chunks, app_state, &context),
+        K8sView::CronJobs => render_k8s_cron_jobs(f, &chunks, app_state, &context),
+        K8sView::Resources => render_k8s_resources(f, &chunks, app_state, &context),
+    }
+
+    let help = Paragraph::new(app_state.message.clone())
+        .block(Block::default().borders(Borders::ALL).title("Message"))
         .wrap(Wrap { trim: true });
-    f.render_widget(summary, chunks[0]);
+    f.render_widget(help, chunks[2]);
+}
+
+fn render_k8s_pods(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    app_state: &mut AppState,
+    context: &str,
+) {
+    let syncing = match &app_state.pod_watcher {
+        Some(watcher) if !watcher.is_synced() => " | syncing...",
+        _ => "",
+    };
+    let filter = match &app_state.pod_selector {
+        Some(filter) => format!(
+            " | svc/{}: {}",
+            filter.service,
+            format_selector(&filter.selector)
+        ),
+        None => String::new(),
+    };
+    let title = format!(
+        "Kubernetes Pods [{}{}{}] (Tab Views | ↑↓ Navigate | D Delete | F Port-forward | Shift+F Service | W Forwards | L Logs | Y YAML | Shift+Y Owner)",
+        context, filter, syncing
+    );
+
+    let rows: Vec<Row> = app_state
+        .k8s_pods
+        .iter()
+        .map(|pod| {
+            let last_termination = pod.last_termination.clone().unwrap_or_default();
+            let last_termination_color = if last_termination == "Completed" {
+                Color::DarkGray
+            } else {
+                Color::Red
+            };
+
+            let usage = app_state
+                .pod_metrics
+                .get(&(pod.namespace.clone(), pod.name.clone()));
+            let (cpu, mem) = match usage {
+                Some(usage) => (
+                    Cell::from(Span::styled(
+                        format_cpu(usage.cpu_millis),
+                        usage_style(usage.cpu_millis, pod.cpu_limit()),
+                    )),
+                    Cell::from(Span::styled(
+                        format_memory(usage.memory_bytes),
+                        usage_style(usage.memory_bytes, pod.memory_limit()),
+                    )),
+                ),
+                None => (Cell::from("-"), Cell::from("-")),
+            };
+
+            Row::new(vec![
+                Cell::from(pod.name.clone()),
+                Cell::from(pod.namespace.clone()),
+                Cell::from(pod.ready.clone()),
+                Cell::from(Span::styled(
+                    pod.status.clone(),
+                    Style::default().fg(pod_status_color(&pod.status)),
+                )),
+                Cell::from(pod.restarts_display()),
+                cpu,
+                mem,
+                Cell::from(pod.age()),
+                Cell::from(pod.ip.clone()),
+                Cell::from(pod.node.clone()),
+                Cell::from(Span::styled(
+                    last_termination,
+                    Style::default().fg(last_termination_color),
+                )),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(35),
+            Constraint::Length(16),
+            Constraint::Length(6),
+            Constraint::Length(26),
+            Constraint::Length(16),
+            Constraint::Length(7),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(16),
+            Constraint::Length(20),
+            Constraint::Min(12),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Name",
+            "Namespace",
+            "Ready",
+            "Status",
+            "Restarts",
+            "CPU",
+            "Mem",
+            "Age",
+            "IP",
+            "Node",
+            "Last Termination",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(table, chunks[0], &mut app_state.k8s_list_state);
+
+    if app_state.show_port_forwards {
+        render_port_forwards(f, chunks[1], app_state);
+    } else if let Some(selected) = app_state.k8s_list_state.selected() {
+        if let Some(pod) = app_state.k8s_pods.get(selected) {
+            render_pod_resources(f, chunks[1], pod, app_state);
+        }
+    }
+}
+
+fn render_k8s_services(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    app_state: &mut AppState,
+    context: &str,
+) {
+    let syncing = match &app_state.service_watcher {
+        Some(watcher) if !watcher.is_synced() => " | syncing...",
+        _ => "",
+    };
+    let title = format!(
+        "Kubernetes Services [{}{}] (Tab Views | ↑↓ Navigate | Enter Pods | F Port-forward | Y YAML)",
+        context, syncing
+    );
+
+    let rows: Vec<Row> = app_state
+        .k8s_services
+        .iter()
+        .map(|service| {
+            Row::new(vec![
+                Cell::from(service.name.clone()),
+                Cell::from(service.namespace.clone()),
+                Cell::from(service.service_type.clone()),
+                Cell::from(service.cluster_ip.clone()),
+                Cell::from(service.external_ip.clone()),
+                Cell::from(service.ports.clone()),
+                Cell::from(service.age()),
+                Cell::from(service.selector_display()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(30),
+            Constraint::Length(16),
+            Constraint::Length(13),
+            Constraint::Length(16),
+            Constraint::Length(16),
+            Constraint::Length(22),
+            Constraint::Length(8),
+            Constraint::Min(20),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Name",
+            "Namespace",
+            "Type",
+            "Cluster-IP",
+            "External-IP",
+            "Ports",
+            "Age",
+            "Selector",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(table, chunks[0], &mut app_state.service_list_state);
+
+    let Some(service) = app_state
+        .service_list_state
+        .selected()
+        .and_then(|i| app_state.k8s_services.get(i))
+    else {
+        return;
+    };
+    let endpoints = match &app_state.endpoint_slice_watcher {
+        Some(watcher) => service_endpoints(watcher.items(), &service.namespace, &service.name),
+        None => Vec::new(),
+    };
+    let ready = endpoints.iter().filter(|e| e.ready).count();
+
+    let rows: Vec<Row> = endpoints
+        .iter()
+        .map(|endpoint| {
+            let (state, color) = if endpoint.ready {
+                ("Ready", Color::Green)
+            } else {
+                ("NotReady", Color::Red)
+            };
+            Row::new(vec![
+                Cell::from(endpoint.address.clone()),
+                Cell::from(endpoint.ports.clone()),
+                Cell::from(Span::styled(state, Style::default().fg(color))),
+                Cell::from(endpoint.target.clone()),
+                Cell::from(endpoint.node.clone()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(40),
+            Constraint::Length(24),
+            Constraint::Length(10),
+            Constraint::Length(40),
+            Constraint::Min(12),
+        ],
+    )
+    .header(
+        Row::new(vec!["Address", "Ports", "State", "Target", "Node"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Endpoints of {} ({} ready, {} not ready)",
+        service.name,
+        ready,
+        endpoints.len() - ready
+    )));
+
+    f.render_widget(table, chunks[1]);
+}
+
+fn render_k8s_ingresses(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    app_state: &mut AppState,
+    context: &str,
+) {
+    let syncing = match &app_state.ingress_watcher {
+        Some(watcher) if !watcher.is_synced() => " | syncing...",
+        _ => "",
+    };
+    let title = format!(
+        "Kubernetes Ingresses [{}{}] (Tab Views | ↑↓ Navigate | Y YAML)",
+        context, syncing
+    );
+
+    let rows: Vec<Row> = app_state
+        .k8s_ingresses
+        .iter()
+        .map(|ingress| {
+            Row::new(vec![
+                Cell::from(ingress.name.clone()),
+                Cell::from(ingress.namespace.clone()),
+                Cell::from(ingress.class.clone()),
+                Cell::from(ingress.hosts.clone()),
+                Cell::from(ingress.address.clone()),
+                Cell::from(ingress.ports.clone()),
+                Cell::from(ingress.age()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(30),
+            Constraint::Length(16),
+            Constraint::Length(14),
+            Constraint::Length(40),
+            Constraint::Length(20),
+            Constraint::Length(8),
+            Constraint::Min(8),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Name",
+            "Namespace",
+            "Class",
+            "Hosts",
+            "Address",
+            "Ports",
+            "Age",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(table, chunks[0], &mut app_state.ingress_list_state);
+
+    let Some(ingress) = app_state
+        .ingress_list_state
+        .selected()
+        .and_then(|i| app_state.k8s_ingresses.get(i))
+    else {
+        return;
+    };
+
+    let rows: Vec<Row> = ingress
+        .rules
+        .iter()
+        .map(|rule| {
+            Row::new(vec![
+                Cell::from(rule.host.clone()),
+                Cell::from(rule.path.clone()),
+                Cell::from(rule.path_type.clone()),
+                Cell::from(rule.backend.clone()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(40),
+            Constraint::Length(30),
+            Constraint::Length(24),
+            Constraint::Min(20),
+        ],
+    )
+    .header(
+        Row::new(vec!["Host", "Path", "Path Type", "Backend"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Rules of {}", ingress.name)),
+    );
 
-    let cpu_ram_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(chunks[1]);
+    f.render_widget(table, chunks[1]);
+}
 
-    let cpu_percent = clamp_percent(cpu_usage);
-    let cpu_gauge = Gauge::default()
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("CPU Usage (%)"),
+fn render_k8s_config_data(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    app_state: &mut AppState,
+    context: &str,
+) {
+    let secrets = app_state.k8s_view == K8sView::Secrets;
+    let (kind, watcher_synced) = if secrets {
+        let namespace = app_state
+            .k8s_client
+            .as_ref()
+            .map(|client| client.config().namespace.clone())
+            .unwrap_or_default();
+        (
+            format!("Secrets in {}", namespace),
+            app_state.secret_watcher.as_ref().map(|w| w.is_synced()),
         )
-        .label(format!("{:.1}%", cpu_usage))
-        .gauge_style(Style::default().fg(Color::Blue))
-        .percent(cpu_percent);
-    f.render_widget(cpu_gauge, cpu_ram_chunks[0]);
-
-    let memory_label = format!(
-        "{:.1}% ({:.1} / {:.1} GiB)",
-        memory_usage_percentage,
-        kib_to_gib(used_memory as f64),
-        kib_to_gib(memory as f64)
+    } else {
+        (
+            "ConfigMaps".to_string(),
+            app_state.config_map_watcher.as_ref().map(|w| w.is_synced()),
+        )
+    };
+    let syncing = if watcher_synced == Some(false) {
+        " | syncing..."
+    } else {
+        ""
+    };
+    let actions = if secrets {
+        "Enter Keys | V Value | R Reveal | C Copy | Y YAML"
+    } else {
+        "Enter Keys | V Value | C Copy | Y YAML"
+    };
+    let title = format!(
+        "Kubernetes {} [{}{}] (Tab Views | ↑↓ Navigate | {})",
+        kind, context, syncing, actions
     );
-    let ram_gauge = Gauge::default()
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("RAM Usage (%)"),
+
+    let objects = if secrets {
+        &app_state.k8s_secrets
+    } else {
+        &app_state.k8s_config_maps
+    };
+    let rows: Vec<Row> = objects
+        .iter()
+        .map(|object| {
+            let mut cells = vec![
+                Cell::from(object.name.clone()),
+                Cell::from(object.namespace.clone()),
+            ];
+            if secrets {
+                cells.push(Cell::from(object.data_type.clone()));
+            }
+            cells.push(Cell::from(object.entries.len().to_string()));
+            cells.push(Cell::from(format_bytes(object.total_size())));
+            cells.push(Cell::from(object.age()));
+            Row::new(cells)
+        })
+        .collect();
+
+    let mut widths = vec![Constraint::Length(40), Constraint::Length(20)];
+    let mut header = vec!["Name", "Namespace"];
+    if secrets {
+        widths.push(Constraint::Length(38));
+        header.push("Type");
+    }
+    widths.extend([
+        Constraint::Length(6),
+        Constraint::Length(12),
+        Constraint::Min(8),
+    ]);
+    header.extend(["Data", "Size", "Age"]);
+
+    let table = Table::new(rows, widths)
+        .header(
+            Row::new(header).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
         )
-        .label(memory_label)
-        .gauge_style(Style::default().fg(Color::Green))
-        .percent(memory_usage_percentage as u16);
-    f.render_widget(ram_gauge, cpu_ram_chunks[1]);
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol(">> ");
 
-    let network_info = Paragraph::new(vec![
-        Line::from(vec![Span::styled(
-            "Network",
-            Style::default().fg(Color::Cyan),
-        )]),
-        Line::from(format!(
-            "Download: {:>7.1} KB/s  |  Upload: {:>7.1} KB/s",
-            receive_rate_kbs, send_rate_kbs
-        )),
-        Line::from(format!(
-            "Total Received: {:>8.2} MiB  |  Total Sent: {:>8.2} MiB",
-            total_received as f64 / 1024.0 / 1024.0,
-            total_transmitted as f64 / 1024.0 / 1024.0
-        )),
-    ])
-    .block(Block::default().borders(Borders::ALL))
-    .wrap(Wrap { trim: true });
-    f.render_widget(network_info, chunks[2]);
+    let list_state = if secrets {
+        &mut app_state.secret_list_state
+    } else {
+        &mut app_state.config_map_list_state
+    };
+    f.render_stateful_widget(table, chunks[0], list_state);
 
-    let graph_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
-        .split(chunks[3]);
+    let Some(object) = app_state.selected_data_object().cloned() else {
+        return;
+    };
+    let detail_chunks = if app_state.show_data_value {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(chunks[1])
+    } else {
+        Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(100)])
+            .split(chunks[1])
+    };
 
-    let cpu_graph_data: Vec<(String, u64)> = cpu_data
+    let rows: Vec<Row> = object
+        .entries
         .iter()
-        .enumerate()
-        .map(|(i, &value)| (i.to_string(), value))
+        .map(|entry| {
+            let preview = if secrets {
+                "••••••••".to_string()
+            } else {
+                match entry.text() {
+                    Some(text) => text.lines().next().unwrap_or_default().to_string(),
+                    None => "<binary>".to_string(),
+                }
+            };
+            Row::new(vec![
+                Cell::from(entry.key.clone()),
+                Cell::from(format_bytes(entry.value.len() as u64)),
+                Cell::from(Span::styled(preview, Style::default().fg(Color::DarkGray))),
+            ])
+        })
         .collect();
 
-    let cpu_graph_data_ref: Vec<(&str, u64)> = cpu_graph_data
-        .iter()
-        .map(|(label, value)| (label.as_str(), *value))
-        .collect();
+    let border_style = if app_state.data_focus {
+        Style::default().fg(Color::Yellow)
+    } else {
+        Style::default()
+    };
+    let keys_title = if app_state.data_focus {
+        format!("Keys of {} (↑↓ Select | Esc Back)", object.name)
+    } else {
+        format!("Keys of {} (Enter to select)", object.name)
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(30),
+            Constraint::Length(10),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(vec!["Key", "Size", "Value"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(border_style)
+            .title(keys_title),
+    )
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
+    f.render_stateful_widget(table, detail_chunks[0], &mut app_state.data_key_state);
 
-    let cpu_graph = BarChart::default()
+    if !app_state.show_data_value {
+        return;
+    }
+    let Some(entry) = app_state
+        .data_key_state
+        .selected()
+        .and_then(|i| object.entries.get(i))
+    else {
+        return;
+    };
+
+    let (value, style) = if secrets && !app_state.reveal_secret {
+        (
+            "Hidden, press R to reveal".to_string(),
+            Style::default().fg(Color::DarkGray),
+        )
+    } else {
+        match entry.text() {
+            Some(text) => (text.to_string(), Style::default()),
+            None => (
+                format!(
+                    "Binary data ({}), press C to copy",
+                    format_bytes(entry.value.len() as u64)
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        }
+    };
+    let paragraph = Paragraph::new(value)
+        .style(style)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title("CPU Usage Over Time"),
+                .title(format!("{} (V Close)", entry.key)),
         )
-        .data(&cpu_graph_data_ref)
-        .bar_width(3)
-        .bar_gap(1)
-        .bar_style(Style::default().fg(Color::Blue))
-        .value_style(Style::default().fg(Color::Black).bg(Color::Blue));
-    f.render_widget(cpu_graph, graph_chunks[0]);
+        .wrap(Wrap { trim: false });
+    f.render_widget(paragraph, detail_chunks[1]);
+}
 
-    let ram_graph_data: Vec<(String, u64)> = ram_data
-        .iter()
-        .enumerate()
-        .map(|(i, &value)| (i.to_string(), value))
-        .collect();
+fn render_k8s_jobs(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    app_state: &mut AppState,
+    context: &str,
+) {
+    let syncing = match &app_state.job_watcher {
+        Some(watcher) if !watcher.is_synced() => " | syncing...",
+        _ => "",
+    };
+    let title = format!(
+        "Kubernetes Jobs [{}{}] (Tab Views | ↑↓ Navigate | L Logs | Y YAML)",
+        context, syncing
+    );
 
-    let ram_graph_data_ref: Vec<(&str, u64)> = ram_graph_data
+    let rows: Vec<Row> = app_state
+        .k8s_jobs
         .iter()
-        .map(|(label, value)| (label.as_str(), *value))
+        .map(|job| {
+            Row::new(vec![
+                Cell::from(job.name.clone()),
+                Cell::from(job.namespace.clone()),
+                Cell::from(Span::styled(
+                    job.status.clone(),
+                    Style::default().fg(job_status_color(&job.status)),
+                )),
+                Cell::from(job.completions.clone()),
+                Cell::from(job.active.to_string()),
+                Cell::from(job.succeeded.to_string()),
+                Cell::from(Span::styled(
+                    job.failed.to_string(),
+                    if job.failed > 0 {
+                        Style::default().fg(Color::Red)
+                    } else {
+                        Style::default()
+                    },
+                )),
+                Cell::from(job.duration()),
+                Cell::from(job.age()),
+                Cell::from(job.cron_job.clone().unwrap_or_default()),
+            ])
+        })
         .collect();
 
-    let ram_graph = BarChart::default()
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("RAM Usage Over Time"),
-        )
-        .data(&ram_graph_data_ref)
-        .bar_width(3)
-        .bar_gap(1)
-        .bar_style(Style::default().fg(Color::Green))
-        .value_style(Style::default().fg(Color::Black).bg(Color::Green));
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(40),
+            Constraint::Length(16),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(7),
+            Constraint::Length(10),
+            Constraint::Length(7),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Min(12),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Name",
+            "Namespace",
+            "Status",
+            "Completions",
+            "Active",
+            "Succeeded",
+            "Failed",
+            "Duration",
+            "Age",
+            "CronJob",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
 
-    let right_graph_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(graph_chunks[1]);
+    f.render_stateful_widget(table, chunks[0], &mut app_state.job_list_state);
 
-    f.render_widget(ram_graph, right_graph_chunks[0]);
+    let Some(job) = app_state
+        .job_list_state
+        .selected()
+        .and_then(|i| app_state.k8s_jobs.get(i))
+    else {
+        return;
+    };
+    let pods = app_state.job_pods(job);
 
-    let spark_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(right_graph_chunks[1]);
+    let rows: Vec<Row> = pods
+        .iter()
+        .map(|pod| {
+            Row::new(vec![
+                Cell::from(pod.name.clone()),
+                Cell::from(pod.ready.clone()),
+                Cell::from(Span::styled(
+                    pod.status.clone(),
+                    Style::default().fg(pod_status_color(&pod.status)),
+                )),
+                Cell::from(pod.restarts_display()),
+                Cell::from(pod.node.clone()),
+                Cell::from(pod.age()),
+            ])
+        })
+        .collect();
 
-    let download_sparkline = Sparkline::default()
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Download KB/s"),
-        )
-        .style(Style::default().fg(Color::Cyan))
-        .data(network_receive_speed_data);
-    f.render_widget(download_sparkline, spark_chunks[0]);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(45),
+            Constraint::Length(6),
+            Constraint::Length(20),
+            Constraint::Length(16),
+            Constraint::Length(20),
+            Constraint::Min(8),
+        ],
+    )
+    .header(
+        Row::new(vec!["Pod", "Ready", "Status", "Restarts", "Node", "Age"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Pods of {} ({})",
+        job.name,
+        pods.len()
+    )));
 
-    let upload_sparkline = Sparkline::default()
-        .block(Block::default().borders(Borders::ALL).title("Upload KB/s"))
-        .style(Style::default().fg(Color::Magenta))
-        .data(network_send_speed_data);
-    f.render_widget(upload_sparkline, spark_chunks[1]);
+    f.render_widget(table, chunks[1]);
 }
 
-fn render_docker_tab(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState) {
-    let main_chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(5)].as_ref())
-        .split(area);
+fn render_k8s_cron_jobs(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    app_state: &mut AppState,
+    context: &str,
+) {
+    let syncing = match &app_state.cron_job_watcher {
+        Some(watcher) if !watcher.is_synced() => " | syncing...",
+        _ => "",
+    };
+    let title = format!(
+        "Kubernetes CronJobs [{}{}] (Tab Views | ↑↓ Navigate | T Trigger | S Suspend/Resume | Y YAML)",
+        context, syncing
+    );
+
+    let rows: Vec<Row> = app_state
+        .k8s_cron_jobs
+        .iter()
+        .map(|cron_job| {
+            let suspend = if cron_job.suspend {
+                Span::styled("True", Style::default().fg(Color::Yellow))
+            } else {
+                Span::raw("False")
+            };
+            Row::new(vec![
+                Cell::from(cron_job.name.clone()),
+                Cell::from(cron_job.namespace.clone()),
+                Cell::from(cron_job.schedule.clone()),
+                Cell::from(cron_job.time_zone.clone()),
+                Cell::from(suspend),
+                Cell::from(cron_job.active.to_string()),
+                Cell::from(cron_job.last_schedule_display()),
+                Cell::from(cron_job.last_successful_display()),
+                Cell::from(cron_job.age()),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(35),
+            Constraint::Length(16),
+            Constraint::Length(16),
+            Constraint::Length(14),
+            Constraint::Length(8),
+            Constraint::Length(7),
+            Constraint::Length(14),
+            Constraint::Length(16),
+            Constraint::Min(8),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Name",
+            "Namespace",
+            "Schedule",
+            "Timezone",
+            "Suspend",
+            "Active",
+            "Last Schedule",
+            "Last Successful",
+            "Age",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title))
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
+
+    f.render_stateful_widget(table, chunks[0], &mut app_state.cron_job_list_state);
 
-    let view_title = match app_state.docker_view {
-        DockerView::Containers => {
-            "Docker Containers | Tab:Switch | ↑↓:Nav | N:New | P:Postgres | R:Redis | M:Mongo | G:Grafana | S:Start | X:Stop | T:Restart | Del:Remove"
-        }
-        DockerView::Images => "Docker Images | Tab:Switch | ↑↓:Nav | X:Delete",
+    let Some(cron_job) = app_state.selected_cron_job() else {
+        return;
     };
+    let rows: Vec<Row> = app_state
+        .k8s_jobs
+        .iter()
+        .filter(|job| {
+            job.namespace == cron_job.namespace && job.cron_job.as_ref() == Some(&cron_job.name)
+        })
+        .map(|job| {
+            Row::new(vec![
+                Cell::from(job.name.clone()),
+                Cell::from(Span::styled(
+                    job.status.clone(),
+                    Style::default().fg(job_status_color(&job.status)),
+                )),
+                Cell::from(job.completions.clone()),
+                Cell::from(job.duration()),
+                Cell::from(job.age()),
+            ])
+        })
+        .collect();
 
-    match app_state.docker_view {
-        DockerView::Containers => {
-            let has_selection = app_state.docker_list_state.selected().is_some();
-
-            if has_selection && !app_state.docker_containers.is_empty() {
-                let chunks = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-                    .split(main_chunks[0]);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(45),
+            Constraint::Length(12),
+            Constraint::Length(12),
+            Constraint::Length(10),
+            Constraint::Min(8),
+        ],
+    )
+    .header(
+        Row::new(vec!["Job", "Status", "Completions", "Duration", "Age"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(format!(
+        "Jobs of {} (Tab to Jobs view for logs)",
+        cron_job.name
+    )));
 
-                let rows: Vec<Row> = app_state
-                    .docker_containers
-                    .iter()
-                    .map(|c| {
-                        let status_color = if c.status.contains("Up") {
-                            Color::Green
-                        } else {
-                            Color::Red
-                        };
+    f.render_widget(table, chunks[1]);
+}
 
-                        Row::new(vec![
-                            Cell::from(c.id[..12].to_string()),
-                            Cell::from(c.name.clone()),
-                            Cell::from(c.image.clone()),
-                            Cell::from(Span::styled(
-                                c.status.clone(),
-                                Style::default().fg(status_color),
-                            )),
-                            Cell::from(format!("{:.1}%", c.cpu_percent)),
-                            Cell::from(c.mem_usage.clone()),
-                        ])
-                    })
-                    .collect();
+fn render_k8s_resources(
+    f: &mut ratatui::Frame,
+    chunks: &[Rect],
+    app_state: &mut AppState,
+    context: &str,
+) {
+    let Some((resource, columns)) = &app_state.browsed_resource else {
+        let text = if app_state.discovery.is_some() {
+            "Discovering API resources..."
+        } else {
+            "Press : to pick a resource kind"
+        };
+        let paragraph = Paragraph::new(text).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Kubernetes Resources [{}]", context)),
+        );
+        f.render_widget(paragraph, chunks[0]);
+        return;
+    };
 
-                let table = Table::new(
-                    rows,
-                    [
-                        Constraint::Length(13),
-                        Constraint::Length(20),
-                        Constraint::Length(20),
-                        Constraint::Length(15),
-                        Constraint::Length(8),
-                        Constraint::Min(10),
-                    ],
-                )
-                .header(
-                    Row::new(vec!["ID", "Name", "Image", "Status", "CPU%", "Mem"]).style(
-                        Style::default()
-                            .fg(Color::Yellow)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                )
-                .block(Block::default().borders(Borders::ALL).title(view_title))
-                .highlight_style(Style::default().bg(Color::DarkGray))
-                .highlight_symbol(">> ");
+    let syncing = match &app_state.generic_watcher {
+        Some(watcher) if !watcher.is_synced() => " | syncing...",
+        _ => "",
+    };
+    let title = format!(
+        "Kubernetes {} [{}{}] (Tab Views | ↑↓ Navigate | : Kinds | Y YAML)",
+        resource.full_name(),
+        context,
+        syncing
+    );
 
-                f.render_stateful_widget(table, chunks[0], &mut app_state.docker_list_state);
+    let mut header = vec!["Name".to_string()];
+    let mut widths = vec![Constraint::Length(40)];
+    if resource.namespaced {
+        header.push("Namespace".to_string());
+        widths.push(Constraint::Length(16));
+    }
+    // Like kubectl, kinds without printer columns just show their age.
+    if columns.is_empty() {
+        header.push("Age".to_string());
+        widths.push(Constraint::Min(8));
+    } else {
+        header.extend(columns.iter().map(|c| c.name.clone()));
+        widths.extend(columns.iter().map(|c| {
+            Constraint::Length((c.name.len() as u16 + 2).max(match c.column_type.as_str() {
+                "string" => 16,
+                _ => 8,
+            }))
+        }));
+    }
 
-                if let Some(selected) = app_state.docker_list_state.selected() {
-                    if let Some(container) = app_state.docker_containers.get(selected) {
-                        render_container_stats(f, chunks[1], container, app_state);
-                    }
-                }
+    let rows: Vec<Row> = app_state
+        .k8s_generic_rows
+        .iter()
+        .map(|row| {
+            let mut cells = vec![Cell::from(row.name.clone())];
+            if resource.namespaced {
+                cells.push(Cell::from(row.namespace.clone()));
+            }
+            if columns.is_empty() {
+                cells.push(Cell::from(row.age()));
             } else {
-                let rows: Vec<Row> = app_state
-                    .docker_containers
-                    .iter()
-                    .map(|c| {
-                        let status_color = if c.status.contains("Up") {
-                            Color::Green
-                        } else {
-                            Color::Red
-                        };
+                cells.extend(row.cells.iter().map(|c| Cell::from(c.clone())));
+            }
+            Row::new(cells)
+        })
+        .collect();
 
-                        Row::new(vec![
-                            Cell::from(c.id[..12].to_string()),
-                            Cell::from(c.name.clone()),
-                            Cell::from(c.image.clone()),
-                            Cell::from(Span::styled(
-                                c.status.clone(),
-                                Style::default().fg(status_color),
-                            )),
-                            Cell::from(format!("{:.1}%", c.cpu_percent)),
-                            Cell::from(c.mem_usage.clone()),
-                            Cell::from(c.net_io.clone()),
-                            Cell::from(c.block_io.clone()),
-                        ])
-                    })
-                    .collect();
+    let table = Table::new(rows, widths)
+        .header(
+            Row::new(header).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol(">> ");
 
-                let table = Table::new(
-                    rows,
-                    [
-                        Constraint::Length(13),
-                        Constraint::Length(20),
-                        Constraint::Length(20),
-                        Constraint::Length(15),
-                        Constraint::Length(8),
-                        Constraint::Length(15),
-                        Constraint::Length(15),
-                        Constraint::Min(10),
-                    ],
-                )
-                .header(
-                    Row::new(vec![
-                        "ID",
-                        "Name",
-                        "Image",
-                        "Status",
-                        "CPU%",
-                        "Mem",
-                        "Net I/O",
-                        "Block I/O",
-                    ])
-                    .style(
-                        Style::default()
-                            .fg(Color::Yellow)
-                            .add_modifier(Modifier::BOLD),
-                    ),
-                )
-                .block(Block::default().borders(Borders::ALL).title(view_title))
-                .highlight_style(Style::default().bg(Color::DarkGray))
-                .highlight_symbol(">> ");
+    let scope = if resource.namespaced {
+        "Namespaced"
+    } else {
+        "Cluster"
+    };
+    let short_names = if resource.short_names.is_empty() {
+        "<none>".to_string()
+    } else {
+        resource.short_names.join(", ")
+    };
+    let details = vec![
+        Line::from(format!("Kind:        {}", resource.kind)),
+        Line::from(format!("API version: {}", resource.group_version)),
+        Line::from(format!("Scope:       {}", scope)),
+        Line::from(format!("Short names: {}", short_names)),
+        Line::from(format!("Objects:     {}", app_state.k8s_generic_rows.len())),
+    ];
+    let details =
+        Paragraph::new(details).block(Block::default().borders(Borders::ALL).title("Resource"));
 
-                f.render_stateful_widget(table, main_chunks[0], &mut app_state.docker_list_state);
-            }
-        }
-        DockerView::Images => {
-            let rows: Vec<Row> = app_state
-                .docker_images
-                .iter()
-                .map(|img| {
-                    Row::new(vec![
-                        Cell::from(img.repository.clone()),
-                        Cell::from(img.tag.clone()),
-                        Cell::from(img.image_id[..12].to_string()),
-                        Cell::from(img.size.clone()),
-                    ])
-                })
-                .collect();
+    f.render_stateful_widget(table, chunks[0], &mut app_state.generic_list_state);
+    f.render_widget(details, chunks[1]);
+}
 
-            let table = Table::new(
-                rows,
-                [
-                    Constraint::Length(30),
-                    Constraint::Length(15),
-                    Constraint::Length(15),
-                    Constraint::Min(15),
-                ],
-            )
-            .header(
-                Row::new(vec!["Repository", "Tag", "Image ID", "Size"]).style(
-                    Style::default()
-                        .fg(Color::Yellow)
-                        .add_modifier(Modifier::BOLD),
-                ),
-            )
-            .block(Block::default().borders(Borders::ALL).title(view_title))
-            .highlight_style(Style::default().bg(Color::DarkGray))
-            .highlight_symbol(">> ");
+/// Kinds matching the picker filter by kind, plural, short name or group.
+/// Opens a manifest, with Secret values masked until revealed.
+fn yaml_view_for(title: String, path: String, manifest: &serde_json::Value) -> YamlView {
+    let yaml = manifest_to_yaml(manifest);
+    match mask_secret_values(manifest) {
+        Some(masked) => YamlView::masked(title, path, manifest_to_yaml(&masked), yaml),
+        None => YamlView::new(title, path, yaml),
+    }
+}
 
-            f.render_stateful_widget(table, main_chunks[0], &mut app_state.docker_list_state);
-        }
+/// `Kind namespace/name`, or `Kind name` for cluster-scoped objects.
+fn manifest_title(manifest: &serde_json::Value) -> String {
+    let field = |pointer: &str| manifest.pointer(pointer).and_then(|v| v.as_str());
+    let kind = field("/kind").unwrap_or("Object");
+    let name = field("/metadata/name").unwrap_or_default();
+    match field("/metadata/namespace") {
+        Some(namespace) => format!("{} {}/{}", kind, namespace, name),
+        None => format!("{} {}", kind, name),
     }
+}
 
-    let help = Paragraph::new(app_state.message.clone())
-        .block(Block::default().borders(Borders::ALL).title("Message"))
-        .wrap(Wrap { trim: true });
-    f.render_widget(help, main_chunks[1]);
+fn filter_api_resources<'a>(resources: &'a [ApiResource], filter: &str) -> Vec<&'a ApiResource> {
+    let filter = filter.to_lowercase();
+    resources
+        .iter()
+        .filter(|r| {
+            r.kind.to_lowercase().contains(&filter)
+                || r.full_name().contains(&filter)
+                || r.short_names.iter().any(|s| s == &filter)
+        })
+        .collect()
 }
 
-fn render_container_stats(
-    f: &mut ratatui::Frame,
-    area: Rect,
-    container: &DockerContainer,
-    app_state: &AppState,
-) {
+fn render_resource_picker(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState) {
+    let Some(picker) = app_state.resource_picker.as_mut() else {
+        return;
+    };
+    let popup_area = centered_rect(70, 70, area);
+    f.render_widget(Clear, popup_area);
+
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(5),
-            Constraint::Percentage(33),
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
-        ])
-        .split(area);
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(popup_area);
 
-    // Container info
-    let info = Paragraph::new(vec![
-        Line::from(vec![Span::styled(
-            "Container Details",
+    let input = Paragraph::new(picker.filter.as_str())
+        .style(
             Style::default()
-                .fg(Color::Cyan)
+                .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(format!("Name: {}", container.name)),
-        Line::from(format!("Ports: {}", container.ports)),
-    ])
-    .block(Block::default().borders(Borders::ALL))
-    .wrap(Wrap { trim: true });
-    f.render_widget(info, chunks[0]);
-
+        )
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Resource kind (type to filter | ↑↓ Navigate | Enter: Browse | Esc: Cancel)")
+                .style(Style::default().bg(Color::Black)),
+        );
+    f.render_widget(input, chunks[0]);
 
-    if let Some(history) = app_state.container_stats_history.get(&container.id) {
-        let cpu_data: Vec<u64> = history.iter().map(|s| s.cpu_percent as u64).collect();
-        let cpu_sparkline = Sparkline::default()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(format!("CPU History ({:.1}%)", container.cpu_percent)),
-            )
-            .style(Style::default().fg(Color::Blue))
-            .data(&cpu_data);
-        f.render_widget(cpu_sparkline, chunks[1]);
+    let resources = filter_api_resources(&app_state.api_resources, &picker.filter);
+    let title = if app_state.discovery.is_some() {
+        "Discovering API resources...".to_string()
+    } else {
+        format!("{} kinds", resources.len())
+    };
+    let rows: Vec<Row> = resources
+        .iter()
+        .map(|r| {
+            Row::new(vec![
+                Cell::from(r.kind.clone()),
+                Cell::from(r.plural.clone()),
+                Cell::from(r.group().to_string()),
+                Cell::from(
+                    r.group_version
+                        .rsplit('/')
+                        .next()
+                        .unwrap_or_default()
+                        .to_string(),
+                ),
+                Cell::from(if r.namespaced { "true" } else { "false" }),
+                Cell::from(r.short_names.join(",")),
+            ])
+        })
+        .collect();
 
-        let mem_data: Vec<u64> = history.iter().map(|s| s.mem_percent as u64).collect();
-        let mem_sparkline = Sparkline::default()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(format!("Memory History ({:.1}%)", container.mem_percent)),
-            )
-            .style(Style::default().fg(Color::Green))
-            .data(&mem_data);
-        f.render_widget(mem_sparkline, chunks[2]);
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(30),
+            Constraint::Length(30),
+            Constraint::Length(30),
+            Constraint::Length(10),
+            Constraint::Length(11),
+            Constraint::Min(10),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            "Kind",
+            "Name",
+            "Group",
+            "Version",
+            "Namespaced",
+            "Short Names",
+        ])
+        .style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .style(Style::default().bg(Color::Black)),
+    )
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
 
-        let net_data: Vec<u64> = history
-            .iter()
-            .map(|s| (s.net_rx_kb + s.net_tx_kb) as u64)
-            .collect();
-        let net_sparkline = Sparkline::default()
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(format!("Network I/O ({})", container.net_io)),
-            )
-            .style(Style::default().fg(Color::Cyan))
-            .data(&net_data);
-        f.render_widget(net_sparkline, chunks[3]);
-    }
+    f.render_stateful_widget(table, chunks[1], &mut picker.list_state);
 }
 
-fn render_create_dialog(f: &mut ratatui::Frame, area: Rect, state: &CreateDialogState) {
-    let popup_area = centered_rect(70, 80, area);
+fn render_host_picker(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState) {
+    let Some(picker) = app_state.host_picker.as_mut() else {
+        return;
+    };
+    let popup_area = centered_rect(70, 50, area);
+    f.render_widget(Clear, popup_area);
+
+    let active = active_endpoint_name();
+    let rows: Vec<Row> = app_state
+        .docker_endpoints
+        .iter()
+        .map(|endpoint| {
+            let marker = if !app_state.all_docker_hosts && endpoint.name == active {
+                "*"
+            } else {
+                ""
+            };
+            Row::new(vec![
+                Cell::from(marker),
+                Cell::from(endpoint.name.clone()),
+                Cell::from(endpoint.host.clone()),
+                Cell::from(endpoint.tls_label()),
+                Cell::from(if endpoint.context.is_some() {
+                    "context"
+                } else {
+                    "added"
+                }),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(1),
+            Constraint::Length(20),
+            Constraint::Min(20),
+            Constraint::Length(10),
+            Constraint::Length(8),
+        ],
+    )
+    .header(
+        Row::new(vec!["", "Name", "Host", "TLS", "Source"]).style(
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Docker hosts (↑↓ Navigate | Enter: Use | n: Add host | a: All hosts | Esc: Cancel)")
+            .style(Style::default().bg(Color::Black)),
+    )
+    .highlight_style(Style::default().bg(Color::DarkGray))
+    .highlight_symbol(">> ");
 
-    f.render_widget(
-        Block::default().style(Style::default().bg(Color::Black)),
-        area,
-    );
+    f.render_stateful_widget(table, popup_area, &mut picker.list_state);
+}
 
+fn render_pod_resources(f: &mut ratatui::Frame, area: Rect, pod: &K8sPod, app_state: &AppState) {
     let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Length(3),
-            Constraint::Min(0),
-        ])
-        .margin(1)
-        .split(popup_area);
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area);
 
-    let block = Block::default()
-        .title("Create Docker Container (Tab/Shift+Tab: Navigate, Enter: Create, Esc: Cancel)")
-        .borders(Borders::ALL)
-        .style(Style::default().bg(Color::Black));
-    f.render_widget(block, popup_area);
+    let usage = app_state
+        .pod_metrics
+        .get(&(pod.namespace.clone(), pod.name.clone()));
 
-    let fields = [
-        ("Image Name*", &state.image_name),
-        ("Container Name", &state.container_name),
-        ("Ports (e.g. 8080:80,443:443)", &state.ports),
-        ("Env Vars (e.g. KEY=value,KEY2=value2)", &state.env_vars),
-        ("Volumes (e.g. /host:/container)", &state.volumes),
-        ("Command (optional)", &state.command),
-    ];
+    let rows: Vec<Row> = pod
+        .containers
+        .iter()
+        .map(|container| {
+            let container_usage =
+                usage.and_then(|u| u.containers.iter().find(|c| c.name == container.name));
+            let (cpu, mem) = match container_usage {
+                Some(u) => (
+                    Cell::from(Span::styled(
+                        format_cpu(u.cpu_millis),
+                        usage_style(u.cpu_millis, container.cpu_limit),
+                    )),
+                    Cell::from(Span::styled(
+                        format_memory(u.memory_bytes),
+                        usage_style(u.memory_bytes, container.memory_limit),
+                    )),
+                ),
+                None => (Cell::from("-"), Cell::from("-")),
+            };
 
-    for (i, (label, value)) in fields.iter().enumerate() {
-        let style = if i == state.selected_field {
+            Row::new(vec![
+                Cell::from(container.name.clone()),
+                cpu,
+                Cell::from(request_limit(container, true)),
+                mem,
+                Cell::from(request_limit(container, false)),
+            ])
+        })
+        .collect();
+
+    let title = match &app_state.pod_metrics_error {
+        Some(error) => format!("Containers of {} ({})", pod.name, error),
+        None => format!("Containers of {}", pod.name),
+    };
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(25),
+            Constraint::Length(8),
+            Constraint::Length(16),
+            Constraint::Length(9),
+            Constraint::Min(16),
+        ],
+    )
+    .header(
+        Row::new(vec!["Container", "CPU", "Req/Lim", "Mem", "Req/Lim"]).style(
             Style::default()
                 .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::White)
-        };
-
-        let input = Paragraph::new(value.as_str()).style(style).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(*label)
-                .border_style(style),
-        );
-        f.render_widget(input, chunks[i]);
-    }
-
-    let help_text = "Examples: postgres:latest, redis:alpine, nginx:latest, mysql:8.0";
-    let help = Paragraph::new(help_text)
-        .style(Style::default().fg(Color::DarkGray))
-        .wrap(Wrap { trim: true });
-    f.render_widget(help, chunks[6]);
-}
+                .add_modifier(Modifier::BOLD),
+        ),
+    )
+    .block(Block::default().borders(Borders::ALL).title(title));
+    f.render_widget(table, chunks[0]);
 
-fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
-    let popup_layout = Layout::default()
+    let spark_chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Percentage(percent_y),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(chunks[1]);
 
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Percentage(percent_x),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
+    let history = app_state
+        .pod_stats_history
+        .get(&format!("{}/{}", pod.namespace, pod.name));
+    let cpu_data: Vec<u64> = history
+        .map(|h| h.iter().map(|s| s.cpu_millis as u64).collect())
+        .unwrap_or_default();
+    let mem_data: Vec<u64> = history
+        .map(|h| {
+            h.iter()
+                .map(|s| (s.memory_bytes / 1024.0 / 1024.0) as u64)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let cpu_sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "CPU History ({})",
+            usage.map(|u| format_cpu(u.cpu_millis)).unwrap_or_default()
+        )))
+        .style(Style::default().fg(Color::Blue))
+        .data(&cpu_data);
+    f.render_widget(cpu_sparkline, spark_chunks[0]);
+
+    let mem_sparkline = Sparkline::default()
+        .block(Block::default().borders(Borders::ALL).title(format!(
+            "Memory History ({})",
+            usage
+                .map(|u| format_memory(u.memory_bytes))
+                .unwrap_or_default()
+        )))
+        .style(Style::default().fg(Color::Green))
+        .data(&mem_data);
+    f.render_widget(mem_sparkline, spark_chunks[1]);
 }
 
-fn render_k8s_tab(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState) {
-    let chunks = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([Constraint::Min(0), Constraint::Length(4)].as_ref())
-        .split(area);
-
+fn render_port_forwards(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState) {
     let rows: Vec<Row> = app_state
-        .k8s_pods
+        .port_forwards
         .iter()
-        .map(|pod| {
-            let status_color = match pod.status.as_str() {
-                "Running" => Color::Green,
-                "Pending" => Color::Yellow,
-                "Failed" | "CrashLoopBackOff" => Color::Red,
-                _ => Color::White,
+        .map(|forward| {
+            let status = forward.status();
+            let status_color = if status.starts_with("Error") {
+                Color::Red
+            } else if status == "Active" {
+                Color::Green
+            } else {
+                Color::Yellow
             };
 
+            let last_error = forward
+                .last_error()
+                .map(|(at, error)| format!("{} ago: {}", format_age(Some(at)), error))
+                .unwrap_or_default();
+
             Row::new(vec![
-                Cell::from(pod.name.clone()),
-                Cell::from(pod.namespace.clone()),
-                Cell::from(Span::styled(
-                    pod.status.clone(),
-                    Style::default().fg(status_color),
-                )),
-                Cell::from(pod.restarts.clone()),
-                Cell::from(pod.age.clone()),
+                Cell::from(forward.target.describe()),
+                Cell::from(forward.pod.clone()),
+                Cell::from(format!("127.0.0.1:{}", forward.local_port)),
+                Cell::from(forward.remote_port.to_string()),
+                Cell::from(Span::styled(status, Style::default().fg(status_color))),
+                Cell::from(forward.connections().to_string()),
+                Cell::from(format_bytes(forward.bytes_in())),
+                Cell::from(format_bytes(forward.bytes_out())),
+                Cell::from(Span::styled(last_error, Style::default().fg(Color::Red))),
             ])
         })
         .collect();
@@ -1114,14 +7149,29 @@ fn render_k8s_tab(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState)
         rows,
         [
             Constraint::Length(35),
+            Constraint::Length(25),
+            Constraint::Length(16),
+            Constraint::Length(7),
             Constraint::Length(20),
-            Constraint::Length(15),
+            Constraint::Length(6),
+            Constraint::Length(10),
             Constraint::Length(10),
             Constraint::Min(10),
         ],
     )
     .header(
-        Row::new(vec!["Name", "Namespace", "Status", "Restarts", "Age"]).style(
+        Row::new(vec![
+            "Target",
+            "Pod",
+            "Local",
+            "Remote",
+            "Status",
+            "Conns",
+            "In",
+            "Out",
+            "Last Error",
+        ])
+        .style(
             Style::default()
                 .fg(Color::Yellow)
                 .add_modifier(Modifier::BOLD),
@@ -1130,31 +7180,133 @@ fn render_k8s_tab(f: &mut ratatui::Frame, area: Rect, app_state: &mut AppState)
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .title("Kubernetes Pods (↑↓ Navigate | D Delete)"),
+            .title("Port Forwards (↑↓ Navigate | X Stop | W Close)"),
     )
     .highlight_style(Style::default().bg(Color::DarkGray))
     .highlight_symbol(">> ");
 
-    f.render_stateful_widget(table, chunks[0], &mut app_state.k8s_list_state);
+    f.render_stateful_widget(table, area, &mut app_state.port_forward_list_state);
+}
 
-    let help = Paragraph::new(app_state.message.clone())
-        .block(Block::default().borders(Borders::ALL).title("Message"))
-        .wrap(Wrap { trim: true });
-    f.render_widget(help, chunks[1]);
+fn render_input_prompt(
+    f: &mut ratatui::Frame,
+    area: Rect,
+    prompt: &InputPrompt,
+    completions: &[String],
+) {
+    let width = area.width * 60 / 100;
+    let popup_area = Rect {
+        x: area.x + (area.width - width) / 2,
+        y: area.y + area.height.saturating_sub(3) / 2,
+        width,
+        height: area.height.min(3),
+    };
+
+    f.render_widget(Clear, popup_area);
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("{} (Enter: OK, Esc: Cancel)", prompt.title))
+        .style(Style::default().bg(Color::Black));
+    let matches = prompt.input.matches(completions);
+    if matches.len() > 1 {
+        let shown: Vec<&str> = matches.iter().take(8).copied().collect();
+        let more = if matches.len() > shown.len() {
+            ", …"
+        } else {
+            ""
+        };
+        block = block.title_bottom(format!(" Tab: {}{} ", shown.join(", "), more));
+    }
+    prompt.input.render(
+        f,
+        popup_area,
+        block,
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+        true,
+    );
+}
+
+/// `repository:tag` of every tagged local image.
+fn image_references(images: &[DockerImage]) -> Vec<String> {
+    let mut references: Vec<String> = images
+        .iter()
+        .filter(|image| image.repository != "<none>" && image.tag != "<none>")
+        .map(|image| format!("{}:{}", image.repository, image.tag))
+        .collect();
+    references.sort();
+    references.dedup();
+    references
+}
+
+fn request_limit(container: &ContainerResources, cpu: bool) -> String {
+    let (request, limit, format): (_, _, fn(f64) -> String) = if cpu {
+        (container.cpu_request, container.cpu_limit, format_cpu)
+    } else {
+        (
+            container.memory_request,
+            container.memory_limit,
+            format_memory,
+        )
+    };
+    let show = |value: Option<f64>| value.map(format).unwrap_or_else(|| "-".to_string());
+    format!("{} / {}", show(request), show(limit))
+}
+
+/// Highlights usage that is getting close to its limit.
+fn usage_style(usage: f64, limit: Option<f64>) -> Style {
+    match limit {
+        Some(limit) if limit > 0.0 && usage / limit >= 0.9 => Style::default().fg(Color::Red),
+        Some(limit) if limit > 0.0 && usage / limit >= 0.75 => Style::default().fg(Color::Yellow),
+        _ => Style::default(),
+    }
+}
+
+fn job_status_color(status: &str) -> Color {
+    match status {
+        "Complete" | "SuccessCriteriaMet" => Color::Green,
+        "Running" => Color::Cyan,
+        "Suspended" => Color::Yellow,
+        _ => Color::Red,
+    }
+}
+
+fn pod_status_color(status: &str) -> Color {
+    match status {
+        "Running" => Color::Green,
+        "Completed" | "Succeeded" => Color::Cyan,
+        "Terminating" => Color::Magenta,
+        "Pending" | "ContainerCreating" | "PodInitializing" | "NotReady" | "SchedulingGated" => {
+            Color::Yellow
+        }
+        s if s.starts_with("Init:") && s[5..].contains('/') => Color::Yellow,
+        "Unknown" => Color::White,
+        _ => Color::Red,
+    }
 }
 
 // Docker functions
-fn get_docker_containers_with_stats() -> Vec<DockerContainer> {
-    let output = Command::new("docker")
-        .args([
-            "ps",
-            "-a",
-            "--format",
-            "{{.ID}}|{{.Image}}|{{.Names}}|{{.Status}}|{{.Ports}}",
-        ])
+/// Lists containers with `docker ps`, without stats.
+fn get_docker_containers() -> Vec<DockerContainer> {
+    let mut format = format!(
+        "{{{{.ID}}}}|{{{{.Image}}}}|{{{{.Names}}}}|{{{{.Status}}}}|{{{{.Ports}}}}|{}",
+        ComposeInfo::format_fields()
+    );
+    match Runtime::current() {
+        Runtime::Podman => format.push_str("|{{.PodName}}"),
+        // The labels the kubelet puts on CRI containers.
+        Runtime::Nerdctl => format.push_str(
+            "|{{.Label \"io.kubernetes.pod.namespace\"}}/{{.Label \"io.kubernetes.pod.name\"}}",
+        ),
+        Runtime::Docker => {}
+    }
+    let output = docker::cli()
+        .args(["ps", "-a", "--format", &format])
         .output();
+    let host = active_endpoint_name();
 
-    let containers_basic = match output {
+    match output {
         Ok(output) if output.status.success() => {
             let stdout = String::from_utf8_lossy(&output.stdout);
             stdout
@@ -1162,24 +7314,81 @@ fn get_docker_containers_with_stats() -> Vec<DockerContainer> {
                 .filter_map(|line| {
                     let parts: Vec<&str> = line.split('|').collect();
                     if parts.len() >= 5 {
-                        Some((
-                            parts[0].to_string(),
-                            parts[1].to_string(),
-                            parts[2].to_string(),
-                            parts[3].to_string(),
-                            parts[4].to_string(),
-                        ))
+                        Some(DockerContainer {
+                            id: parts[0].to_string(),
+                            image: parts[1].to_string(),
+                            name: parts[2].to_string(),
+                            status: parts[3].to_string(),
+                            ports: parts[4].to_string(),
+                            cpu_percent: 0.0,
+                            mem_usage: "N/A".to_string(),
+                            mem_percent: 0.0,
+                            net_io: "N/A".to_string(),
+                            block_io: "N/A".to_string(),
+                            compose: ComposeInfo::from_fields(&parts[5..]),
+                            pod: parts
+                                .get(9)
+                                .map(|pod| pod.trim())
+                                .filter(|pod| !pod.is_empty() && *pod != "/")
+                                .map(|pod| pod.to_string()),
+                            state: ContainerState::default(),
+                            host: host.clone(),
+                        })
                     } else {
                         None
                     }
                 })
-                .collect::<Vec<_>>()
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// The current endpoint's containers, with their stats when `stats` is set,
+/// and their restart and OOM state.
+/// Carries the last stats of each container over from `previous`.
+fn keep_stats(containers: &mut [DockerContainer], previous: &[DockerContainer]) {
+    for container in containers {
+        if let Some(old) = previous
+            .iter()
+            .find(|old| old.id == container.id && old.host == container.host)
+        {
+            container.cpu_percent = old.cpu_percent;
+            container.mem_usage = old.mem_usage.clone();
+            container.mem_percent = old.mem_percent;
+            container.net_io = old.net_io.clone();
+            container.block_io = old.block_io.clone();
         }
-        _ => return Vec::new(),
+    }
+}
+
+fn host_containers(stats: bool) -> Vec<DockerContainer> {
+    let mut containers = if stats {
+        get_docker_containers_with_stats()
+    } else {
+        get_docker_containers()
     };
+    let ids: Vec<&str> = containers.iter().map(|c| c.id.as_str()).collect();
+    let mut states = container_states(&ids);
+    for container in &mut containers {
+        if let Some((_, state)) = states
+            .iter_mut()
+            .find(|(id, _)| id.starts_with(&container.id))
+        {
+            container.state = std::mem::take(state);
+        }
+    }
+    containers
+}
+
+fn get_docker_containers_with_stats() -> Vec<DockerContainer> {
+    let mut containers = get_docker_containers();
+    if containers.is_empty() {
+        return containers;
+    }
 
     // Get stats
-    let stats_output = Command::new("docker")
+    let stats_output = docker::cli()
         .args([
             "stats",
             "--no-stream",
@@ -1188,52 +7397,29 @@ fn get_docker_containers_with_stats() -> Vec<DockerContainer> {
         ])
         .output();
 
-    let mut stats_map: HashMap<String, (f64, String, f64, String, String)> = HashMap::new();
-
     if let Ok(output) = stats_output {
         if output.status.success() {
             let stdout = String::from_utf8_lossy(&output.stdout);
             for line in stdout.lines() {
                 let parts: Vec<&str> = line.split('|').collect();
-                if parts.len() >= 6 {
-                    let id = parts[0].to_string();
-                    let cpu = parts[1].trim_end_matches('%').parse::<f64>().unwrap_or(0.0);
-                    let mem_usage = parts[2].to_string();
-                    let mem_percent = parts[3].trim_end_matches('%').parse::<f64>().unwrap_or(0.0);
-                    let net_io = parts[4].to_string();
-                    let block_io = parts[5].to_string();
-                    stats_map.insert(id, (cpu, mem_usage, mem_percent, net_io, block_io));
+                if parts.len() < 6 {
+                    continue;
                 }
+                let Some(container) = containers.iter_mut().find(|c| c.id == parts[0]) else {
+                    continue;
+                };
+                container.cpu_percent =
+                    parts[1].trim_end_matches('%').parse::<f64>().unwrap_or(0.0);
+                container.mem_usage = parts[2].to_string();
+                container.mem_percent =
+                    parts[3].trim_end_matches('%').parse::<f64>().unwrap_or(0.0);
+                container.net_io = parts[4].to_string();
+                container.block_io = parts[5].to_string();
             }
         }
     }
 
-    containers_basic
-        .into_iter()
-        .map(|(id, image, name, status, ports)| {
-            let (cpu_percent, mem_usage, mem_percent, net_io, block_io) =
-                stats_map.get(&id).cloned().unwrap_or((
-                    0.0,
-                    "N/A".to_string(),
-                    0.0,
-                    "N/A".to_string(),
-                    "N/A".to_string(),
-                ));
-
-            DockerContainer {
-                id,
-                image,
-                name,
-                status,
-                ports,
-                cpu_percent,
-                mem_usage,
-                mem_percent,
-                net_io,
-                block_io,
-            }
-        })
-        .collect()
+    containers
 }
 
 fn parse_net_io(net_io: &str) -> (f64, f64) {
@@ -1259,7 +7445,7 @@ fn parse_net_io(net_io: &str) -> (f64, f64) {
 }
 
 fn get_docker_images() -> Vec<DockerImage> {
-    let output = Command::new("docker")
+    let output = docker::cli()
         .args([
             "images",
             "--format",
@@ -1280,6 +7466,7 @@ fn get_docker_images() -> Vec<DockerImage> {
                             tag: parts[1].to_string(),
                             image_id: parts[2].to_string(),
                             size: parts[3].to_string(),
+                            in_use: false,
                         })
                     } else {
                         None
@@ -1291,239 +7478,176 @@ fn get_docker_images() -> Vec<DockerImage> {
     }
 }
 
-fn create_custom_container(state: &CreateDialogState) -> String {
-    if state.image_name.is_empty() {
-        return "Error: Image name is required!".to_string();
-    }
-
-    let mut args = vec!["run", "-d"];
-
-    // Container name
-    if !state.container_name.is_empty() {
-        args.push("--name");
-        args.push(&state.container_name);
-    }
-
-    // Ports
-    let port_args: Vec<String> = if !state.ports.is_empty() {
-        state
-            .ports
-            .split(',')
-            .flat_map(|p| vec!["-p".to_string(), p.trim().to_string()])
-            .collect()
-    } else {
-        Vec::new()
-    };
-
-    // Env vars
-    let env_args: Vec<String> = if !state.env_vars.is_empty() {
-        state
-            .env_vars
-            .split(',')
-            .flat_map(|e| vec!["-e".to_string(), e.trim().to_string()])
-            .collect()
-    } else {
-        Vec::new()
-    };
-
-    // Volumes
-    let vol_args: Vec<String> = if !state.volumes.is_empty() {
-        state
-            .volumes
-            .split(',')
-            .flat_map(|v| vec!["-v".to_string(), v.trim().to_string()])
-            .collect()
-    } else {
-        Vec::new()
-    };
-
-    let port_refs: Vec<&str> = port_args.iter().map(|s| s.as_str()).collect();
-    let env_refs: Vec<&str> = env_args.iter().map(|s| s.as_str()).collect();
-    let vol_refs: Vec<&str> = vol_args.iter().map(|s| s.as_str()).collect();
-
-    args.extend(port_refs);
-    args.extend(env_refs);
-    args.extend(vol_refs);
-    args.push(&state.image_name);
-
-    // Command
-    let cmd_args: Vec<String> = if !state.command.is_empty() {
-        state
-            .command
-            .split_whitespace()
-            .map(|s| s.to_string())
-            .collect()
-    } else {
-        Vec::new()
-    };
-    let cmd_refs: Vec<&str> = cmd_args.iter().map(|s| s.as_str()).collect();
-    args.extend(cmd_refs);
-
-    match Command::new("docker").args(&args).output() {
-        Ok(output) => {
-            if output.status.success() {
-                format!(
-                    "Container created successfully from image: {}",
-                    state.image_name
-                )
-            } else {
-                let error = String::from_utf8_lossy(&output.stderr);
-                format!("Error creating container: {}", error)
-            }
-        }
-        Err(e) => format!("Failed to execute docker command: {}", e),
-    }
+fn postgres_container_args() -> Vec<String> {
+    docker_args(&[
+        "run",
+        "-d",
+        "--name",
+        &format!("postgres-{}", chrono::Utc::now().timestamp()),
+        "-e",
+        "POSTGRES_PASSWORD=password",
+        "-p",
+        "5432:5432",
+        "postgres:latest",
+    ])
 }
 
-fn create_postgres_container() {
-    Command::new("docker")
-        .args([
-            "run",
-            "-d",
-            "--name",
-            &format!("postgres-{}", chrono::Utc::now().timestamp()),
-            "-e",
-            "POSTGRES_PASSWORD=password",
-            "-p",
-            "5432:5432",
-            "postgres:latest",
-        ])
-        .spawn()
-        .ok();
+fn redis_container_args() -> Vec<String> {
+    docker_args(&[
+        "run",
+        "-d",
+        "--name",
+        &format!("redis-{}", chrono::Utc::now().timestamp()),
+        "-p",
+        "6379:6379",
+        "redis:latest",
+    ])
 }
 
-fn create_redis_container() {
-    Command::new("docker")
-        .args([
-            "run",
-            "-d",
-            "--name",
-            &format!("redis-{}", chrono::Utc::now().timestamp()),
-            "-p",
-            "6379:6379",
-            "redis:latest",
-        ])
-        .spawn()
-        .ok();
+fn mongodb_container_args() -> Vec<String> {
+    docker_args(&[
+        "run",
+        "-d",
+        "--name",
+        &format!("mongodb-{}", chrono::Utc::now().timestamp()),
+        "-e",
+        "MONGO_INITDB_ROOT_USERNAME=admin",
+        "-e",
+        "MONGO_INITDB_ROOT_PASSWORD=password",
+        "-p",
+        "27017:27017",
+        "mongo:latest",
+    ])
 }
 
-fn create_mongodb_container() {
-    Command::new("docker")
-        .args([
-            "run",
-            "-d",
-            "--name",
-            &format!("mongodb-{}", chrono::Utc::now().timestamp()),
-            "-e",
-            "MONGO_INITDB_ROOT_USERNAME=admin",
-            "-e",
-            "MONGO_INITDB_ROOT_PASSWORD=password",
-            "-p",
-            "27017:27017",
-            "mongo:latest",
-        ])
-        .spawn()
-        .ok();
+fn grafana_container_args() -> Vec<String> {
+    docker_args(&[
+        "run",
+        "-d",
+        "--name",
+        &format!("grafana-{}", chrono::Utc::now().timestamp()),
+        "-p",
+        "3000:3000",
+        "grafana/grafana:latest",
+    ])
 }
 
-fn create_grafana_container() {
-    Command::new("docker")
-        .args([
-            "run",
-            "-d",
-            "--name",
-            &format!("grafana-{}", chrono::Utc::now().timestamp()),
-            "-p",
-            "3000:3000",
-            "grafana/grafana:latest",
-        ])
-        .spawn()
-        .ok();
+// Kubernetes functions
+fn delete_k8s_pod(client: &KubeClient, pod_name: &str, namespace: &str) -> Result<(), String> {
+    client.delete(&format!(
+        "/api/v1/namespaces/{}/pods/{}",
+        namespace, pod_name
+    ))
 }
 
-fn stop_docker_container(container_id: &str) {
-    Command::new("docker")
-        .args(["stop", container_id])
-        .spawn()
-        .ok();
-}
+// Helper functions
+/// Suspends the TUI, opens `yaml` in `$VISUAL`/`$EDITOR` (like `kubectl edit`) and
+/// returns the saved contents.
+fn edit_in_editor(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    yaml: &str,
+) -> Result<String, String> {
+    let path = env::temp_dir().join(format!("hello-world-tui-edit-{}.yaml", std::process::id()));
+    fs::write(&path, yaml).map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        terminal.backend_mut(),
+        DisableBracketedPaste,
+        LeaveAlternateScreen
+    );
+    let _ = terminal.show_cursor();
 
-fn start_docker_container(container_id: &str) {
-    Command::new("docker")
-        .args(["start", container_id])
-        .spawn()
-        .ok();
+    let status = Command::new(program).args(parts).arg(&path).status();
+
+    let _ = enable_raw_mode();
+    let _ = execute!(
+        terminal.backend_mut(),
+        EnterAlternateScreen,
+        EnableBracketedPaste
+    );
+    let _ = terminal.clear();
+
+    let result = match status {
+        Ok(status) if status.success() => fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e)),
+        Ok(status) => Err(format!("Editor exited with {}", status)),
+        Err(e) => Err(format!("Failed to start editor '{}': {}", program, e)),
+    };
+    let _ = fs::remove_file(&path);
+    result
 }
 
-fn restart_docker_container(container_id: &str) {
-    Command::new("docker")
-        .args(["restart", container_id])
-        .spawn()
-        .ok();
+fn docker_args(args: &[&str]) -> Vec<String> {
+    args.iter().map(|arg| arg.to_string()).collect()
 }
 
-fn delete_docker_container(container_id: &str) {
-    Command::new("docker")
-        .args(["rm", "-f", container_id])
-        .spawn()
-        .ok();
+fn kib_to_gib(kib: f64) -> f64 {
+    kib / 1024.0 / 1024.0
 }
 
-fn delete_docker_image(image_id: &str) {
-    Command::new("docker")
-        .args(["rmi", "-f", image_id])
-        .spawn()
-        .ok();
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
 }
 
-// Kubernetes functions
-fn get_k8s_pods() -> Vec<K8sPod> {
-    let output = Command::new("kubectl")
-        .args([
-            "get",
-            "pods",
-            "--all-namespaces",
-            "-o",
-            "custom-columns=NAME:.metadata.name,NAMESPACE:.metadata.namespace,STATUS:.status.phase,RESTARTS:.status.containerStatuses[0].restartCount,AGE:.metadata.creationTimestamp",
-            "--no-headers",
-        ])
-        .output();
+/// Normalizes `SIGTERM`, `term` or `15` to what `kill` and `docker kill` accept.
+fn parse_signal(value: &str) -> Option<String> {
+    let signal = value.trim().to_uppercase();
+    let signal = signal.trim_start_matches("SIG");
+    (!signal.is_empty()
+        && signal
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '+'))
+    .then(|| signal.to_string())
+}
 
-    match output {
-        Ok(output) if output.status.success() => {
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            stdout
-                .lines()
-                .filter_map(|line| {
-                    let parts: Vec<&str> = line.split_whitespace().collect();
-                    if parts.len() >= 5 {
-                        Some(K8sPod {
-                            name: parts[0].to_string(),
-                            namespace: parts[1].to_string(),
-                            status: parts[2].to_string(),
-                            restarts: parts[3].to_string(),
-                            age: parts[4].to_string(),
-                        })
-                    } else {
-                        None
-                    }
-                })
-                .collect()
+/// Parses `8080:80` (local:remote), `8080` (same port) or `:80` (any free local port).
+fn parse_port_pair(value: &str) -> Option<(u16, u16)> {
+    let value = value.trim();
+    match value.split_once(':') {
+        Some(("", remote)) => Some((0, remote.trim().parse().ok()?)),
+        Some((local, remote)) => Some((local.trim().parse().ok()?, remote.trim().parse().ok()?)),
+        None => {
+            let port = value.parse().ok()?;
+            Some((port, port))
         }
-        _ => Vec::new(),
     }
 }
 
-fn delete_k8s_pod(pod_name: &str, namespace: &str) {
-    Command::new("kubectl")
-        .args(["delete", "pod", pod_name, "-n", namespace])
-        .spawn()
-        .ok();
+/// Copies `data` to the system clipboard with an OSC 52 escape sequence, which
+/// works through SSH and tmux without a local clipboard tool.
+fn copy_to_clipboard(data: &[u8]) -> io::Result<()> {
+    use base64::Engine;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(data);
+    let mut stdout = io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)?;
+    stdout.flush()
 }
 
-// Helper functions
-fn kib_to_gib(kib: f64) -> f64 {
-    kib / 1024.0 / 1024.0
+/// Keeps a table selection inside a list that may have shrunk.
+fn clamp_selection(state: &mut TableState, len: usize) {
+    match state.selected() {
+        Some(selected) if selected >= len && len > 0 => state.select(Some(len - 1)),
+        // Rendering an empty table clears the selection; pick the first row again.
+        None if len > 0 => state.select(Some(0)),
+        _ => {}
+    }
 }
 
 fn format_duration(duration: Duration) -> Option<String> {
@@ -0,0 +1,148 @@
+use crate::docker::{container_top, ContainerProcess};
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
+};
+use std::time::{Duration, Instant};
+
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Full-screen, live `docker top` of one container.
+pub struct ProcessView {
+    pub container_id: String,
+    pub container_name: String,
+    processes: Result<Vec<ContainerProcess>, String>,
+    list_state: TableState,
+    refreshed_at: Instant,
+}
+
+impl ProcessView {
+    pub fn open(container_id: &str, container_name: &str) -> Self {
+        let mut view = ProcessView {
+            container_id: container_id.to_string(),
+            container_name: container_name.to_string(),
+            processes: Ok(Vec::new()),
+            list_state: TableState::default(),
+            refreshed_at: Instant::now(),
+        };
+        view.refresh();
+        view
+    }
+
+    /// Re-lists the processes, keeping the selected PID selected while it lives.
+    pub fn refresh(&mut self) {
+        let selected_pid = self.selected().map(|process| process.pid);
+        self.processes = container_top(&self.container_id);
+        self.refreshed_at = Instant::now();
+        let Ok(processes) = &self.processes else {
+            self.list_state.select(None);
+            return;
+        };
+        let index = selected_pid
+            .and_then(|pid| processes.iter().position(|process| process.pid == pid))
+            .or_else(|| {
+                let selected = self.list_state.selected().unwrap_or(0);
+                (!processes.is_empty()).then(|| selected.min(processes.len() - 1))
+            });
+        self.list_state.select(index);
+    }
+
+    pub fn refresh_if_stale(&mut self) {
+        if self.refreshed_at.elapsed() >= REFRESH_INTERVAL {
+            self.refresh();
+        }
+    }
+
+    pub fn move_selection(&mut self, delta: isize) {
+        let len = self
+            .processes
+            .as_ref()
+            .map_or(0, |processes| processes.len());
+        if len == 0 {
+            return;
+        }
+        let selected = self.list_state.selected().unwrap_or(0) as isize;
+        self.list_state
+            .select(Some((selected + delta).clamp(0, len as isize - 1) as usize));
+    }
+
+    pub fn selected(&self) -> Option<&ContainerProcess> {
+        let processes = self.processes.as_ref().ok()?;
+        processes.get(self.list_state.selected()?)
+    }
+
+    pub fn render(&mut self, f: &mut ratatui::Frame, area: Rect) {
+        let count = self
+            .processes
+            .as_ref()
+            .map_or(0, |processes| processes.len());
+        let title = format!(
+            "Processes in {} [{} | every {}s] (↑↓ Navigate | K Send signal | Esc Close)",
+            self.container_name,
+            count,
+            REFRESH_INTERVAL.as_secs()
+        );
+        let block = Block::default().borders(Borders::ALL).title(title);
+        let processes = match &self.processes {
+            Ok(processes) => processes,
+            Err(e) => {
+                let error = Paragraph::new(format!(
+                    "Cannot list processes: {}\nThe container must be running.",
+                    e
+                ))
+                .style(Style::default().fg(Color::Red))
+                .block(block);
+                f.render_widget(error, area);
+                return;
+            }
+        };
+
+        let rows: Vec<Row> = processes
+            .iter()
+            .map(|process| {
+                let style = if process.cpu >= 50.0 {
+                    Style::default().fg(Color::Red)
+                } else if process.cpu >= 10.0 {
+                    Style::default().fg(Color::Yellow)
+                } else {
+                    Style::default()
+                };
+                Row::new(vec![
+                    Cell::from(process.pid.to_string()),
+                    Cell::from(process.user.clone()),
+                    Cell::from(format!("{:.1}", process.cpu)),
+                    Cell::from(
+                        process
+                            .memory
+                            .map_or("-".to_string(), |memory| format!("{:.1}", memory)),
+                    ),
+                    Cell::from(process.command.clone()),
+                ])
+                .style(style)
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Length(12),
+                Constraint::Length(7),
+                Constraint::Length(7),
+                Constraint::Min(20),
+            ],
+        )
+        .header(
+            Row::new(vec!["PID", "User", "CPU %", "Mem %", "Command"]).style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray))
+        .highlight_symbol(">> ");
+        f.render_stateful_widget(table, area, &mut self.list_state);
+    }
+}
@@ -0,0 +1,562 @@
+use crate::docker::host_path;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    widgets::{Block, Paragraph},
+};
+use std::{collections::HashMap, fs};
+
+const HISTORY_LIMIT: usize = 50;
+
+/// Editable text with a cursor and readline-style keys. Newlines are kept, so
+/// a multi-line field is the same input with Enter inserting `\n`; line-wise
+/// keys (Home, End, Ctrl+U, Ctrl+K) then act on the cursor's line.
+#[derive(Clone, Default)]
+pub struct TextInput {
+    value: String,
+    /// Byte offset into `value`, always on a char boundary.
+    cursor: usize,
+    /// Position while browsing history, and the text typed before browsing.
+    history_index: Option<usize>,
+    draft: String,
+}
+
+impl TextInput {
+    pub fn new(value: impl Into<String>) -> Self {
+        let value = value.into();
+        TextInput {
+            cursor: value.len(),
+            value,
+            history_index: None,
+            draft: String::new(),
+        }
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, value: impl Into<String>) {
+        self.value = value.into();
+        self.cursor = self.value.len();
+    }
+
+    pub fn insert(&mut self, c: char) {
+        self.value.insert(self.cursor, c);
+        self.cursor += c.len_utf8();
+        self.history_index = None;
+    }
+
+    /// Inserts pasted text. Line breaks become spaces unless `multi_line`.
+    pub fn paste(&mut self, text: &str, multi_line: bool) {
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let text = if multi_line {
+            text
+        } else {
+            text.trim_end_matches('\n').replace('\n', " ")
+        };
+        self.value.insert_str(self.cursor, &text);
+        self.cursor += text.len();
+        self.history_index = None;
+    }
+
+    /// Applies an editing key. Returns false for keys it does not handle.
+    pub fn handle_key(&mut self, key: &KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = key.modifiers.contains(KeyModifiers::ALT);
+        match key.code {
+            KeyCode::Left if ctrl || alt => self.cursor = self.word_start(),
+            KeyCode::Right if ctrl || alt => self.cursor = self.word_end(),
+            KeyCode::Left => self.cursor = self.previous_boundary(),
+            KeyCode::Right => self.cursor = self.next_boundary(),
+            KeyCode::Home => self.cursor = self.line_start(),
+            KeyCode::End => self.cursor = self.line_end(),
+            KeyCode::Backspace if ctrl || alt => self.delete_to(self.word_start()),
+            KeyCode::Backspace => self.delete_to(self.previous_boundary()),
+            KeyCode::Delete => self.delete_to(self.next_boundary()),
+            KeyCode::Char(c) if ctrl => match c {
+                'a' => self.cursor = self.line_start(),
+                'e' => self.cursor = self.line_end(),
+                'b' => self.cursor = self.previous_boundary(),
+                'f' => self.cursor = self.next_boundary(),
+                'w' => self.delete_to(self.word_start()),
+                'h' => self.delete_to(self.previous_boundary()),
+                'd' => self.delete_to(self.next_boundary()),
+                'u' => self.delete_to(self.line_start()),
+                'k' => self.delete_to(self.line_end()),
+                _ => return false,
+            },
+            KeyCode::Char(c) if alt => match c {
+                'b' => self.cursor = self.word_start(),
+                'f' => self.cursor = self.word_end(),
+                'd' => self.delete_to(self.word_end()),
+                _ => return false,
+            },
+            KeyCode::Char(c) => self.insert(c),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Removes the text between the cursor and `position`.
+    fn delete_to(&mut self, position: usize) {
+        let (start, end) = if position < self.cursor {
+            (position, self.cursor)
+        } else {
+            (self.cursor, position)
+        };
+        self.value.replace_range(start..end, "");
+        self.cursor = start;
+        self.history_index = None;
+    }
+
+    fn previous_boundary(&self) -> usize {
+        self.value[..self.cursor]
+            .chars()
+            .next_back()
+            .map_or(0, |c| self.cursor - c.len_utf8())
+    }
+
+    fn next_boundary(&self) -> usize {
+        self.value[self.cursor..]
+            .chars()
+            .next()
+            .map_or(self.cursor, |c| self.cursor + c.len_utf8())
+    }
+
+    fn line_start(&self) -> usize {
+        self.value[..self.cursor].rfind('\n').map_or(0, |i| i + 1)
+    }
+
+    fn line_end(&self) -> usize {
+        self.value[self.cursor..]
+            .find('\n')
+            .map_or(self.value.len(), |i| self.cursor + i)
+    }
+
+    /// Start of the word before the cursor, skipping separators first.
+    fn word_start(&self) -> usize {
+        self.value[..self.cursor]
+            .trim_end_matches(is_word_separator)
+            .rfind(is_word_separator)
+            .map_or(0, |i| i + 1)
+    }
+
+    /// End of the word after the cursor, skipping separators first.
+    fn word_end(&self) -> usize {
+        let after = &self.value[self.cursor..];
+        let word = after.trim_start_matches(is_word_separator);
+        let length = word.find(is_word_separator).unwrap_or(word.len());
+        self.cursor + after.len() - word.len() + length
+    }
+
+    /// Replaces the text with the previous history entry, newest first.
+    pub fn history_previous(&mut self, history: &[String]) {
+        let index = match self.history_index {
+            None if history.is_empty() => return,
+            None => {
+                self.draft = self.value.clone();
+                history.len() - 1
+            }
+            Some(index) => index.saturating_sub(1),
+        };
+        self.set_value(history[index].clone());
+        self.history_index = Some(index);
+    }
+
+    /// Moves back towards the newest entry, then to the text typed before browsing.
+    pub fn history_next(&mut self, history: &[String]) {
+        let Some(index) = self.history_index else {
+            return;
+        };
+        if index + 1 < history.len() {
+            self.set_value(history[index + 1].clone());
+            self.history_index = Some(index + 1);
+        } else {
+            let draft = std::mem::take(&mut self.draft);
+            self.set_value(draft);
+            self.history_index = None;
+        }
+    }
+
+    /// Candidates that extend the current text.
+    pub fn matches<'a>(&self, candidates: &'a [String]) -> Vec<&'a str> {
+        candidates
+            .iter()
+            .map(String::as_str)
+            .filter(|candidate| candidate.starts_with(&self.value) && *candidate != self.value)
+            .collect()
+    }
+
+    /// Extends the text to the longest prefix shared by all matching candidates.
+    /// Returns false when there is nothing to add.
+    pub fn complete(&mut self, candidates: &[String]) -> bool {
+        let matches = self.matches(candidates);
+        let Some(first) = matches.first() else {
+            return false;
+        };
+        let common = matches[1..].iter().fold(first.len(), |length, candidate| {
+            let shared: usize = first[..length]
+                .chars()
+                .zip(candidate.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a.len_utf8())
+                .sum();
+            shared
+        });
+        if common <= self.value.len() {
+            return false;
+        }
+        self.set_value(&first[..common]);
+        self.history_index = None;
+        true
+    }
+
+    /// Draws the text in `block`, scrolled so the cursor stays visible, and
+    /// places the terminal cursor when `focused`.
+    pub fn render(
+        &self,
+        f: &mut ratatui::Frame,
+        area: Rect,
+        block: Block,
+        style: Style,
+        focused: bool,
+    ) {
+        let inner = block.inner(area);
+        let before = &self.value[..self.cursor];
+        let row = before.matches('\n').count() as u16;
+        let column = before[self.line_start()..].chars().count() as u16;
+        let scroll_y = row.saturating_sub(inner.height.saturating_sub(1));
+        let scroll_x = column.saturating_sub(inner.width.saturating_sub(1));
+        let paragraph = Paragraph::new(self.value.as_str())
+            .style(style)
+            .block(block)
+            .scroll((scroll_y, scroll_x));
+        f.render_widget(paragraph, area);
+        if focused && inner.width > 0 && inner.height > 0 {
+            f.set_cursor_position(Position::new(
+                inner.x + column - scroll_x,
+                inner.y + row - scroll_y,
+            ));
+        }
+    }
+}
+
+/// Words end at whitespace and at the punctuation of paths, ports and assignments.
+fn is_word_separator(c: char) -> bool {
+    c.is_whitespace() || "/:=,".contains(c)
+}
+
+/// Previously submitted values per field, oldest first.
+#[derive(Default)]
+pub struct InputHistory {
+    entries: HashMap<&'static str, Vec<String>>,
+}
+
+impl InputHistory {
+    pub fn entries(&self, field: &str) -> &[String] {
+        self.entries.get(field).map_or(&[], Vec::as_slice)
+    }
+
+    /// Adds `value` as the newest entry, moving it up if it was used before.
+    pub fn record(&mut self, field: &'static str, value: &str) {
+        if value.trim().is_empty() {
+            return;
+        }
+        let entries = self.entries.entry(field).or_default();
+        entries.retain(|entry| entry != value);
+        entries.push(value.to_string());
+        if entries.len() > HISTORY_LIMIT {
+            entries.remove(0);
+        }
+    }
+}
+
+/// Host files and directories completing `value`, keeping `~/` as typed.
+/// Directories end in `/` so completion can continue into them.
+pub fn path_completions(value: &str) -> Vec<String> {
+    let (directory, prefix) = match value.rfind('/') {
+        Some(i) => (&value[..=i], &value[i + 1..]),
+        None => ("", value),
+    };
+    let Ok(entries) = fs::read_dir(host_path(if directory.is_empty() {
+        "."
+    } else {
+        directory
+    })) else {
+        return Vec::new();
+    };
+    let mut completions: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            // Hidden files only when asked for.
+            if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.')) {
+                return None;
+            }
+            let suffix = if entry.path().is_dir() { "/" } else { "" };
+            Some(format!("{}{}{}", directory, name, suffix))
+        })
+        .collect();
+    completions.sort();
+    completions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ctrl(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)
+    }
+
+    fn alt(c: char) -> KeyEvent {
+        KeyEvent::new(KeyCode::Char(c), KeyModifiers::ALT)
+    }
+
+    fn press(input: &mut TextInput, keys: &[KeyEvent]) {
+        for key in keys {
+            input.handle_key(key);
+        }
+    }
+
+    /// The value with `|` at the cursor.
+    fn shown(input: &TextInput) -> String {
+        format!(
+            "{}|{}",
+            &input.value[..input.cursor],
+            &input.value[input.cursor..]
+        )
+    }
+
+    fn history(entries: &[&str]) -> Vec<String> {
+        entries.iter().map(|entry| entry.to_string()).collect()
+    }
+
+    #[test]
+    fn moves_over_whole_characters() {
+        let mut input = TextInput::new("aé😀b");
+        press(&mut input, &[key(KeyCode::Left), key(KeyCode::Left)]);
+        assert_eq!(shown(&input), "aé|😀b");
+
+        press(&mut input, &[key(KeyCode::Backspace)]);
+        assert_eq!(shown(&input), "a|😀b");
+        press(&mut input, &[key(KeyCode::Delete)]);
+        assert_eq!(shown(&input), "a|b");
+
+        input.insert('ß');
+        press(&mut input, &[key(KeyCode::Right), key(KeyCode::Right)]);
+        assert_eq!(shown(&input), "aßb|");
+        press(&mut input, &[ctrl('b'), ctrl('b'), ctrl('h'), ctrl('f')]);
+        assert_eq!(shown(&input), "ß|b");
+    }
+
+    #[test]
+    fn stops_at_both_ends() {
+        let mut input = TextInput::new("ü");
+        press(&mut input, &[key(KeyCode::Right), key(KeyCode::Delete)]);
+        assert_eq!(shown(&input), "ü|");
+        press(&mut input, &[key(KeyCode::Left), key(KeyCode::Left)]);
+        press(&mut input, &[key(KeyCode::Backspace), ctrl('w')]);
+        assert_eq!(shown(&input), "|ü");
+
+        let mut empty = TextInput::default();
+        press(&mut empty, &[ctrl('w'), alt('d'), ctrl('u'), ctrl('k')]);
+        assert_eq!(shown(&empty), "|");
+    }
+
+    #[test]
+    fn jumps_over_words() {
+        let mut input = TextInput::new("run -p 8080:80 ~/data=x");
+        press(&mut input, &[alt('b')]);
+        assert_eq!(shown(&input), "run -p 8080:80 ~/data=|x");
+        press(&mut input, &[alt('b')]);
+        assert_eq!(shown(&input), "run -p 8080:80 ~/|data=x");
+        let ctrl_left = KeyEvent::new(KeyCode::Left, KeyModifiers::CONTROL);
+        press(&mut input, &[ctrl_left, ctrl_left]);
+        assert_eq!(shown(&input), "run -p 8080:|80 ~/data=x");
+
+        let ctrl_right = KeyEvent::new(KeyCode::Right, KeyModifiers::CONTROL);
+        press(&mut input, &[ctrl_right, alt('f')]);
+        assert_eq!(shown(&input), "run -p 8080:80 ~|/data=x");
+
+        press(&mut input, &[key(KeyCode::Home), alt('d')]);
+        assert_eq!(shown(&input), "| -p 8080:80 ~/data=x");
+    }
+
+    #[test]
+    fn deletes_words_with_multibyte_characters() {
+        let mut input = TextInput::new("grüße wörld  ");
+        press(&mut input, &[ctrl('w')]);
+        assert_eq!(shown(&input), "grüße |");
+        let alt_backspace = KeyEvent::new(KeyCode::Backspace, KeyModifiers::ALT);
+        press(&mut input, &[alt_backspace]);
+        assert_eq!(shown(&input), "|");
+    }
+
+    #[test]
+    fn line_keys_act_on_the_cursor_line() {
+        let mut input = TextInput::new("first\nsecond\nthird");
+        press(&mut input, &[key(KeyCode::Left), key(KeyCode::Left)]);
+        press(&mut input, &[ctrl('a')]);
+        assert_eq!(shown(&input), "first\nsecond\n|third");
+
+        // Moving left from a line start crosses the newline.
+        press(&mut input, &[key(KeyCode::Left), ctrl('a')]);
+        assert_eq!(shown(&input), "first\n|second\nthird");
+        press(&mut input, &[ctrl('e')]);
+        assert_eq!(shown(&input), "first\nsecond|\nthird");
+
+        press(&mut input, &[ctrl('u')]);
+        assert_eq!(shown(&input), "first\n|\nthird");
+        press(&mut input, &[key(KeyCode::End), ctrl('k')]);
+        assert_eq!(shown(&input), "first\n|\nthird");
+        press(&mut input, &[key(KeyCode::Up), key(KeyCode::Home)]);
+        press(&mut input, &[ctrl('k')]);
+        assert_eq!(shown(&input), "first\n|\nthird");
+    }
+
+    #[test]
+    fn unhandled_keys_are_reported() {
+        let mut input = TextInput::new("x");
+        assert!(!input.handle_key(&key(KeyCode::Enter)));
+        assert!(!input.handle_key(&key(KeyCode::Up)));
+        assert!(!input.handle_key(&ctrl('z')));
+        assert!(!input.handle_key(&alt('z')));
+        assert!(input.handle_key(&key(KeyCode::Char('y'))));
+        assert_eq!(shown(&input), "xy|");
+    }
+
+    #[test]
+    fn paste_inserts_at_the_cursor() {
+        let mut input = TextInput::new("ab");
+        press(&mut input, &[key(KeyCode::Left)]);
+        input.paste("1\r\n2\r3\n", false);
+        assert_eq!(shown(&input), "a1 2 3|b");
+
+        let mut multi = TextInput::new("é");
+        multi.paste("x\r\ny\n", true);
+        assert_eq!(shown(&multi), "éx\ny\n|");
+        press(&mut multi, &[ctrl('u')]);
+        assert_eq!(shown(&multi), "éx\ny\n|");
+    }
+
+    #[test]
+    fn browses_history_and_restores_the_draft() {
+        let entries = history(&["one", "two", "three"]);
+        let mut input = TextInput::new("dra");
+        input.history_next(&entries);
+        assert_eq!(shown(&input), "dra|");
+
+        input.history_previous(&entries);
+        assert_eq!(shown(&input), "three|");
+        input.history_previous(&entries);
+        input.history_previous(&entries);
+        input.history_previous(&entries);
+        assert_eq!(input.value(), "one");
+
+        input.history_next(&entries);
+        assert_eq!(input.value(), "two");
+        input.history_next(&entries);
+        input.history_next(&entries);
+        assert_eq!(shown(&input), "dra|");
+        input.history_next(&entries);
+        assert_eq!(input.value(), "dra");
+
+        // Editing an entry ends browsing; the edit becomes the next draft.
+        input.history_previous(&entries);
+        input.insert('!');
+        input.history_previous(&entries);
+        input.history_next(&entries);
+        assert_eq!(input.value(), "three!");
+
+        let mut none = TextInput::new("x");
+        none.history_previous(&[]);
+        assert_eq!(none.value(), "x");
+    }
+
+    #[test]
+    fn records_history_per_field() {
+        let mut history = InputHistory::default();
+        history.record("image", "nginx");
+        history.record("image", "redis");
+        history.record("image", "nginx");
+        history.record("image", "  ");
+        history.record("port", "8080:80");
+
+        assert_eq!(history.entries("image"), ["redis", "nginx"]);
+        assert_eq!(history.entries("port"), ["8080:80"]);
+        assert!(history.entries("volume").is_empty());
+
+        for i in 0..HISTORY_LIMIT + 5 {
+            history.record("port", &i.to_string());
+        }
+        let ports = history.entries("port");
+        assert_eq!(ports.len(), HISTORY_LIMIT);
+        assert_eq!(ports.first().map(String::as_str), Some("5"));
+    }
+
+    #[test]
+    fn completes_the_common_prefix() {
+        let candidates = history(&["nginx:1.27", "nginx:1.26", "node:22", "nginx:1.27"]);
+        let mut input = TextInput::new("n");
+        assert_eq!(
+            input.matches(&candidates),
+            ["nginx:1.27", "nginx:1.26", "node:22", "nginx:1.27"]
+        );
+        assert!(!input.complete(&candidates));
+
+        input.set_value("ng");
+        assert!(input.complete(&candidates));
+        assert_eq!(shown(&input), "nginx:1.2|");
+        assert!(!input.complete(&candidates));
+
+        input.insert('7');
+        assert!(input.matches(&candidates).is_empty());
+        assert!(!input.complete(&candidates));
+    }
+
+    #[test]
+    fn completion_keeps_multibyte_characters_whole() {
+        let candidates = history(&["café-a", "cafè-b"]);
+        let mut input = TextInput::new("c");
+        assert!(input.complete(&candidates));
+        assert_eq!(shown(&input), "caf|");
+
+        let candidates = history(&["größe", "größte"]);
+        let mut input = TextInput::new("g");
+        assert!(input.complete(&candidates));
+        assert_eq!(shown(&input), "größ|");
+    }
+
+    #[test]
+    fn completes_host_paths() {
+        let dir = std::env::temp_dir().join(format!("text-input-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("data")).unwrap();
+        fs::write(dir.join("deploy.yaml"), "").unwrap();
+        fs::write(dir.join(".hidden"), "").unwrap();
+        let base = format!("{}/", dir.display());
+
+        assert_eq!(
+            path_completions(&format!("{}d", base)),
+            [format!("{}data/", base), format!("{}deploy.yaml", base)]
+        );
+        assert_eq!(
+            path_completions(&base),
+            [format!("{}data/", base), format!("{}deploy.yaml", base)]
+        );
+        assert_eq!(
+            path_completions(&format!("{}.h", base)),
+            [format!("{}.hidden", base)]
+        );
+        assert!(path_completions(&format!("{}missing/", base)).is_empty());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
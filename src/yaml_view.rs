@@ -0,0 +1,474 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+};
+use std::collections::BTreeSet;
+
+/// Full-screen YAML viewer with folding, search and a diff preview mode.
+pub struct YamlView {
+    pub title: String,
+    pub object_path: String,
+    pub yaml: String,
+    lines: Vec<String>,
+    folded: BTreeSet<usize>,
+    cursor: usize,
+    scroll: usize,
+    search: Option<String>,
+    pub search_input: Option<String>,
+    pub pending_apply: Option<serde_json::Value>,
+    diff: Vec<DiffLine>,
+    /// The real YAML while sensitive values are shown masked.
+    hidden: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DiffKind {
+    Same,
+    Added,
+    Removed,
+}
+
+struct DiffLine {
+    kind: DiffKind,
+    text: String,
+}
+
+impl YamlView {
+    pub fn new(title: String, object_path: String, yaml: String) -> Self {
+        YamlView {
+            title,
+            object_path,
+            lines: yaml.lines().map(str::to_string).collect(),
+            yaml,
+            folded: BTreeSet::new(),
+            cursor: 0,
+            scroll: 0,
+            search: None,
+            search_input: None,
+            pending_apply: None,
+            diff: Vec::new(),
+            hidden: None,
+        }
+    }
+
+    /// Shows `masked_yaml` until `reveal` swaps in the real `yaml`.
+    pub fn masked(title: String, object_path: String, masked_yaml: String, yaml: String) -> Self {
+        YamlView {
+            hidden: Some(yaml),
+            ..YamlView::new(title, object_path, masked_yaml)
+        }
+    }
+
+    pub fn is_masked(&self) -> bool {
+        self.hidden.is_some()
+    }
+
+    pub fn reveal(&mut self) {
+        if let Some(yaml) = self.hidden.take() {
+            self.lines = yaml.lines().map(str::to_string).collect();
+            self.yaml = yaml;
+            self.folded.clear();
+            self.cursor = self.cursor.min(self.lines.len().saturating_sub(1));
+        }
+    }
+
+    /// Switches to the diff preview of an edit that is waiting for confirmation.
+    pub fn show_diff(&mut self, edited_yaml: &str, edited: serde_json::Value) {
+        self.diff = diff_lines(&self.lines, &edited_yaml.lines().collect::<Vec<_>>());
+        self.pending_apply = Some(edited);
+        self.cursor = 0;
+        self.scroll = 0;
+    }
+
+    pub fn discard_diff(&mut self) {
+        self.diff.clear();
+        self.pending_apply = None;
+        self.cursor = 0;
+        self.scroll = 0;
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.diff.iter().any(|d| d.kind != DiffKind::Same)
+    }
+
+    fn in_diff(&self) -> bool {
+        self.pending_apply.is_some()
+    }
+
+    /// Indices of lines that are not hidden inside a folded block.
+    fn visible_lines(&self) -> Vec<usize> {
+        if self.in_diff() {
+            return (0..self.diff.len()).collect();
+        }
+
+        let mut visible = Vec::with_capacity(self.lines.len());
+        let mut i = 0;
+        while i < self.lines.len() {
+            visible.push(i);
+            if self.folded.contains(&i) {
+                i = self.block_end(i);
+            } else {
+                i += 1;
+            }
+        }
+        visible
+    }
+
+    /// First line after the block that starts at `start`. A `key:` line owns deeper
+    /// lines and list items at its own indent; a `- item` line owns deeper lines.
+    fn block_end(&self, start: usize) -> usize {
+        let header = &self.lines[start];
+        let indent = indent_of(header);
+        let is_key = header.trim_end().ends_with(':') && !header.trim_start().starts_with('#');
+
+        let mut end = start + 1;
+        while end < self.lines.len() {
+            let line = &self.lines[end];
+            let line_indent = indent_of(line);
+            let owned = line.trim().is_empty()
+                || line_indent > indent
+                || (is_key && line_indent == indent && line.trim_start().starts_with("- "));
+            if !owned {
+                break;
+            }
+            end += 1;
+        }
+        end
+    }
+
+    fn is_foldable(&self, line: usize) -> bool {
+        self.block_end(line) > line + 1
+    }
+
+    pub fn toggle_fold(&mut self) {
+        if self.in_diff() {
+            return;
+        }
+        if let Some(&line) = self.visible_lines().get(self.cursor) {
+            if !self.folded.remove(&line) && self.is_foldable(line) {
+                self.folded.insert(line);
+            }
+        }
+    }
+
+    pub fn unfold_all(&mut self) {
+        let line = self.visible_lines().get(self.cursor).copied();
+        self.folded.clear();
+        if let Some(line) = line {
+            self.cursor = line;
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let count = self.visible_lines().len();
+        if count == 0 {
+            return;
+        }
+        self.cursor = (self.cursor as isize + delta).clamp(0, count as isize - 1) as usize;
+    }
+
+    pub fn move_to_start(&mut self) {
+        self.cursor = 0;
+    }
+
+    pub fn move_to_end(&mut self) {
+        self.cursor = self.visible_lines().len().saturating_sub(1);
+    }
+
+    pub fn commit_search(&mut self) {
+        self.search = self.search_input.take().filter(|q| !q.is_empty());
+        self.find_match(true, true);
+    }
+
+    /// Jumps to the next (or previous) line containing the search text, unfolding
+    /// whatever hides it.
+    pub fn find_match(&mut self, forward: bool, include_current: bool) {
+        let Some(query) = self.search.clone().map(|q| q.to_lowercase()) else {
+            return;
+        };
+        let texts: Vec<&str> = if self.in_diff() {
+            self.diff.iter().map(|d| d.text.as_str()).collect()
+        } else {
+            self.lines.iter().map(String::as_str).collect()
+        };
+        let current = self.visible_lines().get(self.cursor).copied().unwrap_or(0);
+        let count = texts.len();
+        let offset = if include_current { 0 } else { 1 };
+
+        let found = (0..count)
+            .map(|step| {
+                let step = step + offset;
+                if forward {
+                    (current + step) % count
+                } else {
+                    (current + count * 2 - step) % count
+                }
+            })
+            .find(|&i| texts[i].to_lowercase().contains(&query));
+
+        if let Some(line) = found {
+            if !self.in_diff() {
+                let hidden_by: Vec<usize> = self
+                    .folded
+                    .iter()
+                    .copied()
+                    .filter(|&start| start < line && line < self.block_end(start))
+                    .collect();
+                for start in hidden_by {
+                    self.folded.remove(&start);
+                }
+            }
+            if let Some(position) = self.visible_lines().iter().position(|&l| l == line) {
+                self.cursor = position;
+            }
+        }
+    }
+
+    pub fn render(&mut self, f: &mut ratatui::Frame, area: Rect) {
+        let visible = self.visible_lines();
+        let height = area.height.saturating_sub(2) as usize;
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if height > 0 && self.cursor >= self.scroll + height {
+            self.scroll = self.cursor + 1 - height;
+        }
+
+        let query = self.search.as_deref().map(str::to_lowercase);
+        let gutter = self.lines.len().max(self.diff.len()).to_string().len();
+        let text: Vec<Line> = visible
+            .iter()
+            .enumerate()
+            .skip(self.scroll)
+            .take(height)
+            .map(|(position, &index)| {
+                let mut spans = Vec::new();
+                if self.in_diff() {
+                    let diff = &self.diff[index];
+                    let (marker, color) = match diff.kind {
+                        DiffKind::Same => ("  ", Color::Reset),
+                        DiffKind::Added => ("+ ", Color::Green),
+                        DiffKind::Removed => ("- ", Color::Red),
+                    };
+                    spans.push(Span::styled(
+                        format!("{}{}", marker, diff.text),
+                        Style::default().fg(color),
+                    ));
+                } else {
+                    let line = &self.lines[index];
+                    let fold_marker = if self.folded.contains(&index) {
+                        "▸ "
+                    } else if self.is_foldable(index) {
+                        "▾ "
+                    } else {
+                        "  "
+                    };
+                    spans.push(Span::styled(
+                        format!("{:>width$} {}", index + 1, fold_marker, width = gutter),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                    spans.extend(highlight_matches(highlight_yaml(line), query.as_deref()));
+                    if self.folded.contains(&index) {
+                        let hidden = self.block_end(index) - index - 1;
+                        spans.push(Span::styled(
+                            format!("  … {} lines", hidden),
+                            Style::default().fg(Color::DarkGray),
+                        ));
+                    }
+                }
+
+                let line = Line::from(spans);
+                if position == self.cursor {
+                    line.style(Style::default().bg(Color::DarkGray))
+                } else {
+                    line
+                }
+            })
+            .collect();
+
+        let help = if let Some(input) = &self.search_input {
+            format!("Search: {}█", input)
+        } else if self.in_diff() {
+            "Server dry-run diff | Y: Apply | N/Esc: Discard | ↑↓ Scroll".to_string()
+        } else if self.is_masked() {
+            "Values hidden | R Reveal (needed to edit) | ↑↓ Move | Space Fold | / Search | Esc Close"
+                .to_string()
+        } else {
+            "↑↓/PgUp/PgDn Move | Space Fold | Z Unfold all | / Search | n/N Next/Prev | E Edit | Esc Close"
+                .to_string()
+        };
+
+        let paragraph = Paragraph::new(text).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} | {}", self.title, help)),
+        );
+        f.render_widget(paragraph, area);
+    }
+}
+
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Colours one YAML line: keys, list dashes, strings, numbers, booleans and comments.
+fn highlight_yaml(line: &str) -> Vec<(String, Style)> {
+    let mut spans = Vec::new();
+    let indent = indent_of(line);
+    spans.push((line[..indent].to_string(), Style::default()));
+    let mut rest = &line[indent..];
+
+    if rest.starts_with('#') {
+        spans.push((rest.to_string(), Style::default().fg(Color::DarkGray)));
+        return spans;
+    }
+
+    while let Some(stripped) = rest.strip_prefix("- ") {
+        spans.push(("- ".to_string(), Style::default().fg(Color::Yellow)));
+        rest = stripped;
+    }
+    if rest == "-" {
+        spans.push(("-".to_string(), Style::default().fg(Color::Yellow)));
+        return spans;
+    }
+
+    let key_end = if rest.starts_with('"') || rest.starts_with('\'') {
+        let quote = rest.chars().next().unwrap_or('"');
+        rest[1..]
+            .find(quote)
+            .map(|i| i + 2)
+            .filter(|&i| rest[i..].starts_with(':'))
+    } else {
+        rest.find(": ")
+            .or_else(|| rest.ends_with(':').then(|| rest.len() - 1))
+    };
+
+    match key_end {
+        Some(end) => {
+            spans.push((
+                rest[..end].to_string(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ));
+            spans.push((":".to_string(), Style::default()));
+            let value = &rest[end + 1..];
+            if !value.is_empty() {
+                spans.push((value.to_string(), value_style(value.trim())));
+            }
+        }
+        None => spans.push((rest.to_string(), value_style(rest.trim()))),
+    }
+    spans
+}
+
+fn value_style(value: &str) -> Style {
+    let color = match value {
+        "true" | "false" | "null" | "~" => Color::Yellow,
+        "|" | "|-" | "|+" | ">" | ">-" | ">+" | "{}" | "[]" => Color::DarkGray,
+        v if v.parse::<f64>().is_ok() => Color::Magenta,
+        _ => Color::Green,
+    };
+    Style::default().fg(color)
+}
+
+/// Splits spans so every case-insensitive occurrence of `query` gets a highlight.
+fn highlight_matches(spans: Vec<(String, Style)>, query: Option<&str>) -> Vec<Span<'static>> {
+    let match_style = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let Some(query) = query.filter(|q| !q.is_empty()) else {
+        return spans
+            .into_iter()
+            .map(|(text, style)| Span::styled(text, style))
+            .collect();
+    };
+
+    let full: String = spans.iter().map(|(t, _)| t.as_str()).collect();
+    let lower = full.to_lowercase();
+    if lower.len() != full.len() {
+        // Lowercasing changed byte offsets; skip highlighting rather than mis-slice.
+        return spans
+            .into_iter()
+            .map(|(text, style)| Span::styled(text, style))
+            .collect();
+    }
+    let matches: Vec<(usize, usize)> = lower
+        .match_indices(query)
+        .map(|(start, m)| (start, start + m.len()))
+        .collect();
+
+    let mut result = Vec::new();
+    let mut offset = 0;
+    for (text, style) in spans {
+        let span_start = offset;
+        let span_end = offset + text.len();
+        let mut cursor = span_start;
+        for &(m_start, m_end) in &matches {
+            let start = m_start.max(span_start);
+            let end = m_end.min(span_end);
+            if start >= end || start < cursor {
+                continue;
+            }
+            if start > cursor {
+                result.push(Span::styled(full[cursor..start].to_string(), style));
+            }
+            result.push(Span::styled(full[start..end].to_string(), match_style));
+            cursor = end;
+        }
+        if cursor < span_end {
+            result.push(Span::styled(full[cursor..span_end].to_string(), style));
+        }
+        offset = span_end;
+    }
+    result
+}
+
+/// Line diff based on the longest common subsequence.
+fn diff_lines(old: &[String], new: &[&str]) -> Vec<DiffLine> {
+    let n = old.len();
+    let m = new.len();
+    let mut lcs = vec![vec![0u32; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut result = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            result.push(DiffLine {
+                kind: DiffKind::Same,
+                text: old[i].clone(),
+            });
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(DiffLine {
+                kind: DiffKind::Removed,
+                text: old[i].clone(),
+            });
+            i += 1;
+        } else {
+            result.push(DiffLine {
+                kind: DiffKind::Added,
+                text: new[j].to_string(),
+            });
+            j += 1;
+        }
+    }
+    result.extend(old[i..].iter().map(|text| DiffLine {
+        kind: DiffKind::Removed,
+        text: text.clone(),
+    }));
+    result.extend(new[j..].iter().map(|text| DiffLine {
+        kind: DiffKind::Added,
+        text: text.to_string(),
+    }));
+    result
+}